@@ -1,73 +1,71 @@
 use sqlx::SqlitePool;
-use tauri::State;
 
 use crate::db::models::{Account, Currency};
 use crate::db::queries::accounts;
+use crate::error::AppError;
+use crate::services::i18n::{t, t_with};
 
 /// List all active accounts, ordered by type then name.
-#[tauri::command]
-pub async fn list_accounts(pool: State<'_, SqlitePool>) -> Result<Vec<Account>, String> {
-    accounts::get_all(&pool).await.map_err(|e| e.to_string())
+pub async fn list_accounts(pool: &SqlitePool) -> Result<Vec<Account>, AppError> {
+    accounts::get_all(pool).await.map_err(AppError::from)
 }
 
 /// Get a single account by ID.
-#[tauri::command]
-pub async fn get_account(pool: State<'_, SqlitePool>, id: String) -> Result<Account, String> {
-    accounts::get_by_id(&pool, &id)
-        .await
-        .map_err(|e| e.to_string())?
-        .ok_or_else(|| format!("Cuenta no encontrada: {id}"))
+pub async fn get_account(pool: &SqlitePool, id: String) -> Result<Account, AppError> {
+    accounts::get_by_id(pool, &id)
+        .await?
+        .ok_or_else(|| AppError::not_found(t_with("account.not_found", &[("id", &id)])))
 }
 
 /// Create a new account. Validates credit_card required fields.
-#[tauri::command]
 pub async fn create_account(
-    pool: State<'_, SqlitePool>,
+    pool: &SqlitePool,
     name: String,
     account_type: String,
     currency_id: String,
     credit_limit: Option<i64>,
     billing_day: Option<i32>,
-) -> Result<Account, String> {
+    payment_due_day: Option<i32>,
+) -> Result<Account, AppError> {
     // Validate credit_card required fields
     if account_type == "credit_card" {
         if credit_limit.is_none() {
-            return Err("El limite de credito es requerido para tarjetas de credito".into());
+            return Err(AppError::validation(t("account.credit_limit_required")).on("creditLimit"));
         }
         if billing_day.is_none() {
-            return Err("El dia de facturacion es requerido para tarjetas de credito".into());
+            return Err(AppError::validation(t("account.billing_day_required")).on("billingDay"));
         }
     }
 
     let id = uuid::Uuid::new_v4().to_string();
     accounts::create(
-        &pool,
+        pool,
         &id,
         &name,
         &account_type,
         &currency_id,
         credit_limit,
         billing_day,
+        payment_due_day,
     )
     .await
-    .map_err(|e| e.to_string())
+    .map_err(AppError::from)
 }
 
 /// Update an existing account. Type is locked after creation.
-#[tauri::command]
 pub async fn update_account(
-    pool: State<'_, SqlitePool>,
+    pool: &SqlitePool,
     id: String,
     name: Option<String>,
     currency_id: Option<String>,
     credit_limit: Option<i64>,
     billing_day: Option<i32>,
-) -> Result<Account, String> {
+    payment_due_day: Option<i32>,
+) -> Result<Account, AppError> {
     // Fetch existing account to validate credit_card constraints
-    let existing = accounts::get_by_id(&pool, &id)
-        .await
-        .map_err(|e| e.to_string())?
-        .ok_or_else(|| format!("Cuenta no encontrada: {id}"))?;
+    let existing = accounts::get_by_id(pool, &id)
+        .await?
+        .ok_or_else(|| AppError::not_found(t_with("account.not_found", &[("id", &id)])))?;
 
     // For credit_card accounts, ensure credit_limit and billing_day remain present
     if existing.account_type == "credit_card" {
@@ -75,56 +73,125 @@ pub async fn update_account(
         let final_billing_day = billing_day.or(existing.billing_day);
 
         if final_credit_limit.is_none() {
-            return Err("El limite de credito es requerido para tarjetas de credito".into());
+            return Err(AppError::validation(t("account.credit_limit_required")).on("creditLimit"));
         }
         if final_billing_day.is_none() {
-            return Err("El dia de facturacion es requerido para tarjetas de credito".into());
+            return Err(AppError::validation(t("account.billing_day_required")).on("billingDay"));
         }
     }
 
     accounts::update(
-        &pool,
+        pool,
         &id,
         name.as_deref(),
         currency_id.as_deref(),
         credit_limit,
         billing_day,
+        payment_due_day,
     )
     .await
-    .map_err(|e| e.to_string())
+    .map_err(AppError::from)
 }
 
 /// Archive an account (soft delete).
-#[tauri::command]
-pub async fn archive_account(pool: State<'_, SqlitePool>, id: String) -> Result<(), String> {
-    accounts::archive(&pool, &id)
-        .await
-        .map_err(|e| e.to_string())
+pub async fn archive_account(pool: &SqlitePool, id: String) -> Result<(), AppError> {
+    accounts::archive(pool, &id).await.map_err(AppError::from)
 }
 
 /// Permanently delete an account. Blocked if transactions exist.
-#[tauri::command]
-pub async fn delete_account(pool: State<'_, SqlitePool>, id: String) -> Result<(), String> {
-    let has_txns = accounts::has_transactions(&pool, &id)
-        .await
-        .map_err(|e| e.to_string())?;
+pub async fn delete_account(pool: &SqlitePool, id: String) -> Result<(), AppError> {
+    let has_txns = accounts::has_transactions(pool, &id).await?;
 
     if has_txns {
-        return Err("No se puede eliminar una cuenta con transacciones. Use archivar.".into());
+        return Err(AppError::conflict(t("account.has_transactions")));
     }
 
-    accounts::delete(&pool, &id)
-        .await
-        .map_err(|e| e.to_string())
+    accounts::delete(pool, &id).await.map_err(AppError::from)
 }
 
 /// List all available currencies.
-#[tauri::command]
-pub async fn list_currencies(pool: State<'_, SqlitePool>) -> Result<Vec<Currency>, String> {
+pub async fn list_currencies(pool: &SqlitePool) -> Result<Vec<Currency>, AppError> {
     sqlx::query_as::<_, Currency>(
         "SELECT id, code, name, symbol, decimal_places, created_at FROM currencies ORDER BY code",
     )
-    .fetch_all(pool.inner())
+    .fetch_all(pool)
     .await
-    .map_err(|e| e.to_string())
+    .map_err(AppError::from)
+}
+
+/// Tauri handlers. Each forwards to the function of the same name above with the current pool.
+pub mod ipc {
+    use tauri::State;
+
+    use super::{Account, AppError, Currency};
+    use crate::db::Database;
+
+    #[tauri::command]
+    pub async fn list_accounts(db: State<'_, Database>) -> Result<Vec<Account>, AppError> {
+        super::list_accounts(&db.pool()).await
+    }
+
+    #[tauri::command]
+    pub async fn get_account(db: State<'_, Database>, id: String) -> Result<Account, AppError> {
+        super::get_account(&db.pool(), id).await
+    }
+
+    #[tauri::command]
+    pub async fn create_account(
+        db: State<'_, Database>,
+        name: String,
+        account_type: String,
+        currency_id: String,
+        credit_limit: Option<i64>,
+        billing_day: Option<i32>,
+        payment_due_day: Option<i32>,
+    ) -> Result<Account, AppError> {
+        super::create_account(
+            &db.pool(),
+            name,
+            account_type,
+            currency_id,
+            credit_limit,
+            billing_day,
+            payment_due_day,
+        )
+        .await
+    }
+
+    #[tauri::command]
+    pub async fn update_account(
+        db: State<'_, Database>,
+        id: String,
+        name: Option<String>,
+        currency_id: Option<String>,
+        credit_limit: Option<i64>,
+        billing_day: Option<i32>,
+        payment_due_day: Option<i32>,
+    ) -> Result<Account, AppError> {
+        super::update_account(
+            &db.pool(),
+            id,
+            name,
+            currency_id,
+            credit_limit,
+            billing_day,
+            payment_due_day,
+        )
+        .await
+    }
+
+    #[tauri::command]
+    pub async fn archive_account(db: State<'_, Database>, id: String) -> Result<(), AppError> {
+        super::archive_account(&db.pool(), id).await
+    }
+
+    #[tauri::command]
+    pub async fn delete_account(db: State<'_, Database>, id: String) -> Result<(), AppError> {
+        super::delete_account(&db.pool(), id).await
+    }
+
+    #[tauri::command]
+    pub async fn list_currencies(db: State<'_, Database>) -> Result<Vec<Currency>, AppError> {
+        super::list_currencies(&db.pool()).await
+    }
 }
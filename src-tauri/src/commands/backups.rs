@@ -0,0 +1,78 @@
+use crate::commands::settings::restore_locale;
+use crate::db::models::BackupSnapshot;
+use crate::db::{self, Database};
+use crate::error::AppError;
+use crate::services::backup::{self, SnapshotReason};
+use crate::services::i18n::t_with;
+
+/// List the database snapshots, newest first.
+pub fn list_backups(db: &Database) -> Result<Vec<BackupSnapshot>, AppError> {
+    backup::list(&backup::backup_dir(db.path())).map_err(AppError::database)
+}
+
+/// Replace the database with a snapshot. The current data is snapshotted first, so the
+/// restore can itself be undone. The pool is closed while the file is swapped and reopened
+/// on the restored file, running any migrations the snapshot predates after snapshotting it
+/// again as `pre_migration`.
+pub async fn restore_backup(db: &Database, file_name: String) -> Result<(), AppError> {
+    let dir = backup::backup_dir(db.path());
+    let snapshot = backup::list(&dir)
+        .map_err(AppError::database)?
+        .into_iter()
+        .find(|s| s.file_name == file_name)
+        .ok_or_else(|| {
+            AppError::not_found(t_with("backup.not_found", &[("name", &file_name)])).on("fileName")
+        })?;
+    let snapshot_path = dir.join(&snapshot.file_name);
+    backup::verify(&snapshot_path)
+        .await
+        .map_err(|e| AppError::validation(e).on("fileName"))?;
+
+    let now = chrono::Local::now().naive_local();
+    backup::create(&db.pool(), &dir, SnapshotReason::PreRestore, now)
+        .await
+        .map_err(AppError::database)?;
+
+    // Every connection must be closed before the file is swapped
+    db.pool().close().await;
+    let replaced = backup::replace_database(&snapshot_path, db.path());
+
+    // Reopen even if the copy failed, so the app keeps working on the previous file
+    let pool = db::create_pool(db.path()).await?;
+    db.replace(pool.clone());
+    replaced.map_err(AppError::database)?;
+
+    backup::before_migrations(&pool, &dir, now)
+        .await
+        .map_err(AppError::database)?;
+    db::run_migrations(&pool)
+        .await
+        .map_err(|e| AppError::database(e.to_string()))?;
+    restore_locale(&pool).await?;
+    backup::prune(&dir).map_err(AppError::database)?;
+
+    Ok(())
+}
+
+/// Tauri handlers. Each forwards to the function of the same name above with the managed
+/// database.
+pub mod ipc {
+    use tauri::State;
+
+    use super::{AppError, BackupSnapshot};
+    use crate::db::Database;
+
+    #[tauri::command]
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn list_backups(db: State<'_, Database>) -> Result<Vec<BackupSnapshot>, AppError> {
+        super::list_backups(&db)
+    }
+
+    #[tauri::command]
+    pub async fn restore_backup(
+        db: State<'_, Database>,
+        file_name: String,
+    ) -> Result<(), AppError> {
+        super::restore_backup(&db, file_name).await
+    }
+}
@@ -0,0 +1,150 @@
+use std::collections::HashMap;
+
+use chrono::NaiveDate;
+
+use sqlx::SqlitePool;
+
+use crate::commands::dashboard::{base_currency_code, fetch_rate_history};
+use crate::commands::exchange_rates::apply_rate;
+use crate::db::models::{AccountBalanceHistory, BalanceHistory, BalancePoint};
+use crate::db::queries::dashboard;
+use crate::services::i18n::t_with;
+use crate::services::periods;
+use crate::services::settings;
+
+/// Pick the rate in effect on `date`: the latest one dated on or before it, or the earliest
+/// one available when the history starts later.
+fn rate_on_date(history: &[(NaiveDate, f64)], date: NaiveDate) -> Option<f64> {
+    history
+        .iter()
+        .rev()
+        .find(|(rate_date, _)| *rate_date <= date)
+        .or_else(|| history.first())
+        .map(|(_, rate)| *rate)
+}
+
+/// Replay an account's daily changes, returning its balance at the end of each sample date.
+/// `changes` must be sorted by date.
+fn replay(changes: &[(NaiveDate, i64)], samples: &[NaiveDate]) -> Vec<i64> {
+    let mut balances = Vec::with_capacity(samples.len());
+    let mut balance: i64 = 0;
+    let mut next = 0;
+
+    for sample in samples {
+        while let Some((date, change)) = changes.get(next) {
+            if date > sample {
+                break;
+            }
+            balance += change;
+            next += 1;
+        }
+        balances.push(balance);
+    }
+
+    balances
+}
+
+/// Reconstruct daily, weekly or monthly balances of every account over `[date_from, date_to]`
+/// by replaying its transactions, plus a consolidated line converted to base currency with
+/// the exchange rate in effect on each date. Each point is the balance at the end of its
+/// day, week (per the first day of week setting) or month, so monthly points give
+/// month-end net worth.
+pub async fn get_balance_history(
+    pool: &SqlitePool,
+    date_from: String,
+    date_to: String,
+    granularity: String,
+) -> Result<BalanceHistory, String> {
+    let from = periods::parse_date(Some(&date_from), "field.start_date")?;
+    let to = periods::parse_date(Some(&date_to), "field.end_date")?;
+    let week_start = settings::first_day_of_week(pool)
+        .await
+        .map_err(|e| e.to_string())?;
+    let samples = periods::sample_dates(&granularity, from, to, week_start)?;
+
+    let base_cur_id = settings::base_currency_id(pool)
+        .await
+        .map_err(|e| e.to_string())?;
+    let base_currency_code = base_currency_code(pool, &base_cur_id).await?;
+    let history = fetch_rate_history(pool, &base_cur_id).await?;
+
+    let accounts = dashboard::get_accounts_with_currency(pool)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let mut changes: HashMap<String, Vec<(NaiveDate, i64)>> = HashMap::new();
+    for (account_id, date, change) in
+        dashboard::get_daily_balance_changes(pool, &to.format("%Y-%m-%d").to_string())
+            .await
+            .map_err(|e| e.to_string())?
+    {
+        let date = NaiveDate::parse_from_str(&date, "%Y-%m-%d")
+            .map_err(|e| t_with("transaction.invalid_stored_date", &[("error", &e)]))?;
+        changes.entry(account_id).or_default().push((date, change));
+    }
+
+    let mut consolidated = vec![0_i64; samples.len()];
+    let mut account_series = Vec::with_capacity(accounts.len());
+
+    for (account_id, account_name, currency_code) in accounts {
+        let balances = replay(
+            changes.get(&account_id).map_or(&[], Vec::as_slice),
+            &samples,
+        );
+
+        let rates = history.get(&currency_code);
+        for ((total, balance), sample) in consolidated.iter_mut().zip(&balances).zip(&samples) {
+            if currency_code == base_currency_code {
+                *total += balance;
+            } else if let Some(rate) = rates.and_then(|r| rate_on_date(r, *sample)) {
+                *total += apply_rate(*balance, rate);
+            }
+        }
+
+        account_series.push(AccountBalanceHistory {
+            account_id,
+            account_name,
+            currency_code,
+            points: samples
+                .iter()
+                .zip(balances)
+                .map(|(date, balance)| BalancePoint {
+                    date: date.format("%Y-%m-%d").to_string(),
+                    balance,
+                })
+                .collect(),
+        });
+    }
+
+    Ok(BalanceHistory {
+        granularity,
+        base_currency_code,
+        accounts: account_series,
+        consolidated: samples
+            .iter()
+            .zip(consolidated)
+            .map(|(date, balance)| BalancePoint {
+                date: date.format("%Y-%m-%d").to_string(),
+                balance,
+            })
+            .collect(),
+    })
+}
+
+/// Tauri handlers. Each forwards to the function of the same name above with the current pool.
+pub mod ipc {
+    use tauri::State;
+
+    use super::BalanceHistory;
+    use crate::db::Database;
+
+    #[tauri::command]
+    pub async fn get_balance_history(
+        db: State<'_, Database>,
+        date_from: String,
+        date_to: String,
+        granularity: String,
+    ) -> Result<BalanceHistory, String> {
+        super::get_balance_history(&db.pool(), date_from, date_to, granularity).await
+    }
+}
@@ -0,0 +1,303 @@
+use std::collections::HashMap;
+
+use chrono::NaiveDate;
+use sqlx::SqlitePool;
+
+use crate::commands::dashboard::{base_currency_code, convert_to_base, fetch_exchange_rates};
+use crate::db::models::{
+    Budget, BudgetMonthStatus, BudgetStatus, CreateBudgetInput, UpdateBudgetInput,
+};
+use crate::db::queries::{budgets, categories, dashboard};
+use crate::services::dates::month_bounds;
+use crate::services::i18n::{t, t_with};
+use crate::services::settings;
+
+/// Parse a YYYY-MM month into its first day.
+fn parse_month(value: &str) -> Result<NaiveDate, String> {
+    NaiveDate::parse_from_str(&format!("{value}-01"), "%Y-%m-%d")
+        .map_err(|_| t_with("budget.invalid_month", &[("value", &value)]))
+}
+
+/// Format the month of a date as YYYY-MM.
+fn month_key(date: NaiveDate) -> String {
+    date.format("%Y-%m").to_string()
+}
+
+/// Validate that a budget's category exists, is active and is an expense category.
+async fn validate_category(pool: &SqlitePool, category_id: &str) -> Result<(), String> {
+    let category = categories::get_by_id(pool, category_id)
+        .await
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| t_with("category.not_found", &[("id", &category_id)]))?;
+
+    if category.is_active != 1 {
+        return Err(t("category.inactive"));
+    }
+    if category.category_type != "expense" {
+        return Err(t("budget.expense_only"));
+    }
+    Ok(())
+}
+
+/// Sum the month's spending rows that fall under each budget, converted to base currency.
+/// A budget on a parent category matches the rolled-up parent, a budget on a subcategory
+/// matches the transaction's own category. Rows without an exchange rate are skipped.
+fn spent_per_budget(
+    budgets: &[Budget],
+    rows: &[(String, String, String, i64, String)],
+    base_code: &str,
+    rates: &HashMap<String, f64>,
+) -> HashMap<String, i64> {
+    let mut spent: HashMap<String, i64> = HashMap::new();
+
+    for (category_id, _, subcategory_id, amount, currency_code) in rows {
+        let Some(converted) = convert_to_base(*amount, currency_code, base_code, rates) else {
+            continue;
+        };
+        for budget in budgets {
+            if budget.category_id == *category_id || budget.category_id == *subcategory_id {
+                *spent.entry(budget.id.clone()).or_insert(0) += converted;
+            }
+        }
+    }
+
+    spent
+}
+
+/// Build the status of one budget from its carried-over amount and the month's spending.
+fn build_status(
+    budget: &Budget,
+    category_name: String,
+    rollover_amount: i64,
+    spent: i64,
+) -> BudgetStatus {
+    let available = budget.amount + rollover_amount;
+    #[allow(clippy::cast_precision_loss)]
+    let percentage = if available > 0 {
+        (spent as f64 / available as f64) * 100.0
+    } else {
+        0.0
+    };
+
+    BudgetStatus {
+        budget_id: budget.id.clone(),
+        category_id: budget.category_id.clone(),
+        category_name,
+        amount: budget.amount,
+        rollover_amount,
+        available,
+        spent,
+        remaining: available - spent,
+        percentage,
+        is_overspent: spent > available,
+    }
+}
+
+/// List all budgets.
+pub async fn list_budgets(pool: &SqlitePool) -> Result<Vec<Budget>, String> {
+    budgets::get_all(pool).await.map_err(|e| e.to_string())
+}
+
+/// Create a monthly budget for an expense category. Each category can have one budget.
+pub async fn create_budget(pool: &SqlitePool, input: CreateBudgetInput) -> Result<Budget, String> {
+    if input.amount <= 0 {
+        return Err(t("validation.amount_positive"));
+    }
+
+    let start_month = match input.start_month {
+        Some(ref month) => parse_month(month)?,
+        None => chrono::Local::now().date_naive(),
+    };
+
+    validate_category(pool, &input.category_id).await?;
+
+    if budgets::exists_for_category(pool, &input.category_id)
+        .await
+        .map_err(|e| e.to_string())?
+    {
+        return Err(t("budget.duplicate"));
+    }
+
+    let budget = Budget {
+        id: uuid::Uuid::new_v4().to_string(),
+        category_id: input.category_id,
+        amount: input.amount,
+        rollover: i32::from(input.rollover.unwrap_or(false)),
+        start_month: month_key(start_month),
+        created_at: String::new(), // Will be set by DB default
+    };
+
+    budgets::create(pool, &budget)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Update a budget's limit, rollover flag or start month.
+pub async fn update_budget(
+    pool: &SqlitePool,
+    id: String,
+    input: UpdateBudgetInput,
+) -> Result<Budget, String> {
+    let mut budget = budgets::get_by_id(pool, &id)
+        .await
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| t_with("budget.not_found", &[("id", &id)]))?;
+
+    if let Some(amount) = input.amount {
+        if amount <= 0 {
+            return Err(t("validation.amount_positive"));
+        }
+        budget.amount = amount;
+    }
+    if let Some(rollover) = input.rollover {
+        budget.rollover = i32::from(rollover);
+    }
+    if let Some(ref month) = input.start_month {
+        budget.start_month = month_key(parse_month(month)?);
+    }
+
+    budgets::update(pool, &budget)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Delete a budget. Transactions are not affected.
+pub async fn delete_budget(pool: &SqlitePool, id: String) -> Result<(), String> {
+    budgets::get_by_id(pool, &id)
+        .await
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| t_with("budget.not_found", &[("id", &id)]))?;
+
+    budgets::delete(pool, &id).await.map_err(|e| e.to_string())
+}
+
+/// Get spent, remaining and percentage of every budget in effect for `month` (YYYY-MM),
+/// converted to base currency. Budgets with rollover carry the unspent part of each month
+/// since their start month; overspending is not carried.
+pub async fn get_budget_status(
+    pool: &SqlitePool,
+    month: String,
+) -> Result<BudgetMonthStatus, String> {
+    let target = parse_month(&month)?;
+    let target_key = month_key(target);
+
+    let active: Vec<Budget> = budgets::get_all(pool)
+        .await
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .filter(|b| b.start_month <= target_key)
+        .collect();
+
+    let base_cur_id = settings::base_currency_id(pool)
+        .await
+        .map_err(|e| e.to_string())?;
+    let base_currency_code = base_currency_code(pool, &base_cur_id).await?;
+    let rates = fetch_exchange_rates(pool, &base_cur_id).await?;
+
+    let category_names: HashMap<String, String> = categories::get_all(pool)
+        .await
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .map(|c| (c.id, c.name))
+        .collect();
+
+    // Walk forward from the earliest rollover start, carrying unspent amounts month to month
+    let mut cursor = active
+        .iter()
+        .filter(|b| b.rollover == 1)
+        .filter_map(|b| parse_month(&b.start_month).ok())
+        .min()
+        .unwrap_or(target)
+        .min(target);
+    let mut carried: HashMap<String, i64> = HashMap::new();
+
+    loop {
+        let (month_start, month_end) = month_bounds(cursor);
+        let rows = dashboard::get_category_spending(
+            pool,
+            &month_start.format("%Y-%m-%d").to_string(),
+            &month_end.format("%Y-%m-%d").to_string(),
+        )
+        .await
+        .map_err(|e| e.to_string())?;
+        let spent = spent_per_budget(&active, &rows, &base_currency_code, &rates);
+
+        if cursor >= target {
+            let statuses = active
+                .iter()
+                .map(|b| {
+                    build_status(
+                        b,
+                        category_names
+                            .get(&b.category_id)
+                            .cloned()
+                            .unwrap_or_default(),
+                        carried.get(&b.id).copied().unwrap_or(0),
+                        spent.get(&b.id).copied().unwrap_or(0),
+                    )
+                })
+                .collect();
+
+            return Ok(BudgetMonthStatus {
+                month: target_key,
+                base_currency_code,
+                budgets: statuses,
+            });
+        }
+
+        let cursor_key = month_key(cursor);
+        for budget in active
+            .iter()
+            .filter(|b| b.rollover == 1 && b.start_month <= cursor_key)
+        {
+            let entry = carried.entry(budget.id.clone()).or_insert(0);
+            let available = budget.amount + *entry;
+            *entry = (available - spent.get(&budget.id).copied().unwrap_or(0)).max(0);
+        }
+
+        cursor = month_end;
+    }
+}
+
+/// Tauri handlers. Each forwards to the function of the same name above with the current pool.
+pub mod ipc {
+    use tauri::State;
+
+    use super::{Budget, BudgetMonthStatus, CreateBudgetInput, UpdateBudgetInput};
+    use crate::db::Database;
+
+    #[tauri::command]
+    pub async fn list_budgets(db: State<'_, Database>) -> Result<Vec<Budget>, String> {
+        super::list_budgets(&db.pool()).await
+    }
+
+    #[tauri::command]
+    pub async fn create_budget(
+        db: State<'_, Database>,
+        input: CreateBudgetInput,
+    ) -> Result<Budget, String> {
+        super::create_budget(&db.pool(), input).await
+    }
+
+    #[tauri::command]
+    pub async fn update_budget(
+        db: State<'_, Database>,
+        id: String,
+        input: UpdateBudgetInput,
+    ) -> Result<Budget, String> {
+        super::update_budget(&db.pool(), id, input).await
+    }
+
+    #[tauri::command]
+    pub async fn delete_budget(db: State<'_, Database>, id: String) -> Result<(), String> {
+        super::delete_budget(&db.pool(), id).await
+    }
+
+    #[tauri::command]
+    pub async fn get_budget_status(
+        db: State<'_, Database>,
+        month: String,
+    ) -> Result<BudgetMonthStatus, String> {
+        super::get_budget_status(&db.pool(), month).await
+    }
+}
@@ -1,47 +1,41 @@
 use sqlx::SqlitePool;
-use tauri::State;
 
 use crate::db::models::Category;
 use crate::db::queries::categories;
+use crate::error::AppError;
+use crate::services::i18n::{t, t_with};
 
 /// List all active categories, ordered by type, parents first, then by name.
-#[tauri::command]
-pub async fn list_categories(pool: State<'_, SqlitePool>) -> Result<Vec<Category>, String> {
-    categories::get_all(&pool).await.map_err(|e| e.to_string())
+pub async fn list_categories(pool: &SqlitePool) -> Result<Vec<Category>, AppError> {
+    categories::get_all(pool).await.map_err(AppError::from)
 }
 
 /// Get a single category by ID.
-#[tauri::command]
-pub async fn get_category(pool: State<'_, SqlitePool>, id: String) -> Result<Category, String> {
-    categories::get_by_id(&pool, &id)
-        .await
-        .map_err(|e| e.to_string())?
-        .ok_or_else(|| format!("Categoria no encontrada: {id}"))
+pub async fn get_category(pool: &SqlitePool, id: String) -> Result<Category, AppError> {
+    categories::get_by_id(pool, &id)
+        .await?
+        .ok_or_else(|| AppError::not_found(t_with("category.not_found", &[("id", &id)])))
 }
 
 /// Create a new category. If parent_id is provided, inherits type from parent.
-#[tauri::command]
 pub async fn create_category(
-    pool: State<'_, SqlitePool>,
+    pool: &SqlitePool,
     name: String,
     category_type: String,
     icon: Option<String>,
     parent_id: Option<String>,
-) -> Result<Category, String> {
+) -> Result<Category, AppError> {
     let mut final_type = category_type;
 
     // Validate parent if provided
     if let Some(ref pid) = parent_id {
-        let parent = categories::get_by_id(&pool, pid)
-            .await
-            .map_err(|e| e.to_string())?
-            .ok_or_else(|| format!("Categoria padre no encontrada: {pid}"))?;
+        let parent = categories::get_by_id(pool, pid).await?.ok_or_else(|| {
+            AppError::not_found(t_with("category.parent_not_found", &[("id", pid)])).on("parentId")
+        })?;
 
         // Enforce single-level nesting: parent must not have a parent itself
         if parent.parent_id.is_some() {
-            return Err(
-                "No se puede crear una subcategoria de otra subcategoria (solo un nivel de anidamiento permitido)".into(),
-            );
+            return Err(AppError::validation(t("category.nesting")).on("parentId"));
         }
 
         // Inherit type from parent
@@ -50,7 +44,7 @@ pub async fn create_category(
 
     let id = uuid::Uuid::new_v4().to_string();
     categories::create(
-        &pool,
+        pool,
         &id,
         &name,
         &final_type,
@@ -58,60 +52,44 @@ pub async fn create_category(
         parent_id.as_deref(),
     )
     .await
-    .map_err(|e| e.to_string())
+    .map_err(AppError::from)
 }
 
 /// Update an existing category with business rule enforcement.
-#[tauri::command]
 pub async fn update_category(
-    pool: State<'_, SqlitePool>,
+    pool: &SqlitePool,
     id: String,
     name: Option<String>,
     category_type: Option<String>,
     icon: Option<String>,
     parent_id: Option<String>,
-) -> Result<Category, String> {
-    let existing = categories::get_by_id(&pool, &id)
-        .await
-        .map_err(|e| e.to_string())?
-        .ok_or_else(|| format!("Categoria no encontrada: {id}"))?;
+) -> Result<Category, AppError> {
+    let existing = categories::get_by_id(pool, &id)
+        .await?
+        .ok_or_else(|| AppError::not_found(t_with("category.not_found", &[("id", &id)])))?;
 
-    let subcategories = categories::get_subcategories(&pool, &id)
-        .await
-        .map_err(|e| e.to_string())?;
+    let subcategories = categories::get_subcategories(pool, &id).await?;
 
     // Type change logic
     if let Some(ref new_type) = category_type {
         if *new_type != existing.category_type {
             // Check if this category itself has transactions
-            let has_txns = categories::has_transactions(&pool, &id)
-                .await
-                .map_err(|e| e.to_string())?;
+            let has_txns = categories::has_transactions(pool, &id).await?;
             if has_txns {
-                return Err(
-                    "No se puede cambiar el tipo de una categoria con transacciones vinculadas"
-                        .into(),
-                );
+                return Err(AppError::conflict(t("category.type_locked")).on("categoryType"));
             }
 
             // Check if any subcategory has transactions
             for sub in &subcategories {
-                let sub_has_txns = categories::has_transactions(&pool, &sub.id)
-                    .await
-                    .map_err(|e| e.to_string())?;
+                let sub_has_txns = categories::has_transactions(pool, &sub.id).await?;
                 if sub_has_txns {
-                    return Err(
-                        "No se puede cambiar el tipo de una categoria con transacciones vinculadas"
-                            .into(),
-                    );
+                    return Err(AppError::conflict(t("category.type_locked")).on("categoryType"));
                 }
             }
 
             // Cascade type change to subcategories
             if !subcategories.is_empty() {
-                categories::update_subcategory_types(&pool, &id, new_type)
-                    .await
-                    .map_err(|e| e.to_string())?;
+                categories::update_subcategory_types(pool, &id, new_type).await?;
             }
         }
     }
@@ -120,28 +98,30 @@ pub async fn update_category(
     if let Some(ref new_parent_id) = parent_id {
         // Cannot move a parent category (one that has subcategories) under another
         if !subcategories.is_empty() {
-            return Err("No se puede mover una categoria padre a otra categoria".into());
+            return Err(AppError::validation(t("category.parent_move")).on("parentId"));
         }
 
         // The new parent must exist and must be a root category
         if !new_parent_id.is_empty() {
-            let new_parent = categories::get_by_id(&pool, new_parent_id)
-                .await
-                .map_err(|e| e.to_string())?
-                .ok_or_else(|| format!("Categoria padre no encontrada: {new_parent_id}"))?;
+            let new_parent = categories::get_by_id(pool, new_parent_id)
+                .await?
+                .ok_or_else(|| {
+                    AppError::not_found(t_with(
+                        "category.parent_not_found",
+                        &[("id", new_parent_id)],
+                    ))
+                    .on("parentId")
+                })?;
 
             if new_parent.parent_id.is_some() {
-                return Err(
-                    "No se puede crear una subcategoria de otra subcategoria (solo un nivel de anidamiento permitido)".into(),
-                );
+                return Err(AppError::validation(t("category.nesting")).on("parentId"));
             }
 
             // Enforce same-type constraint
             let effective_type = category_type.as_deref().unwrap_or(&existing.category_type);
             if new_parent.category_type != effective_type {
                 return Err(
-                    "El tipo de la subcategoria debe coincidir con el tipo de la categoria padre"
-                        .into(),
+                    AppError::validation(t("category.parent_type_mismatch")).on("categoryType")
                 );
             }
         }
@@ -156,7 +136,7 @@ pub async fn update_category(
     };
 
     categories::update(
-        &pool,
+        pool,
         &id,
         name.as_deref(),
         category_type.as_deref(),
@@ -164,49 +144,85 @@ pub async fn update_category(
         parent_update,
     )
     .await
-    .map_err(|e| e.to_string())
+    .map_err(AppError::from)
 }
 
 /// Delete a category. Blocked if it or its subcategories have transactions.
-#[tauri::command]
-pub async fn delete_category(pool: State<'_, SqlitePool>, id: String) -> Result<(), String> {
+pub async fn delete_category(pool: &SqlitePool, id: String) -> Result<(), AppError> {
     // Check if the category itself has transactions
-    let txn_count = categories::count_transactions(&pool, &id)
-        .await
-        .map_err(|e| e.to_string())?;
+    let txn_count = categories::count_transactions(pool, &id).await?;
 
     if txn_count > 0 {
-        return Err(format!(
-            "Esta categoria tiene {txn_count} transacciones vinculadas. Reasigna las transacciones a otra categoria antes de eliminar."
-        ));
+        return Err(AppError::conflict(t_with(
+            "category.has_transactions",
+            &[("count", &txn_count)],
+        )));
     }
 
     // If category is a parent, check all subcategories
-    let subcategories = categories::get_subcategories(&pool, &id)
-        .await
-        .map_err(|e| e.to_string())?;
+    let subcategories = categories::get_subcategories(pool, &id).await?;
 
     for sub in &subcategories {
-        let sub_txn_count = categories::count_transactions(&pool, &sub.id)
-            .await
-            .map_err(|e| e.to_string())?;
+        let sub_txn_count = categories::count_transactions(pool, &sub.id).await?;
 
         if sub_txn_count > 0 {
-            return Err(format!(
-                "La subcategoria '{}' tiene {} transacciones vinculadas. Reasigna las transacciones a otra categoria antes de eliminar.",
-                sub.name, sub_txn_count
-            ));
+            return Err(AppError::conflict(t_with(
+                "category.subcategory_has_transactions",
+                &[("name", &sub.name), ("count", &sub_txn_count)],
+            )));
         }
     }
 
     // Delete subcategories first, then the parent
     for sub in &subcategories {
-        categories::delete(&pool, &sub.id)
-            .await
-            .map_err(|e| e.to_string())?;
+        categories::delete(pool, &sub.id).await?;
     }
 
-    categories::delete(&pool, &id)
-        .await
-        .map_err(|e| e.to_string())
+    categories::delete(pool, &id).await.map_err(AppError::from)
+}
+
+/// Tauri handlers. Each forwards to the function of the same name above with the current pool.
+pub mod ipc {
+    use tauri::State;
+
+    use super::{AppError, Category};
+    use crate::db::Database;
+
+    #[tauri::command]
+    pub async fn list_categories(db: State<'_, Database>) -> Result<Vec<Category>, AppError> {
+        super::list_categories(&db.pool()).await
+    }
+
+    #[tauri::command]
+    pub async fn get_category(db: State<'_, Database>, id: String) -> Result<Category, AppError> {
+        super::get_category(&db.pool(), id).await
+    }
+
+    #[tauri::command]
+    pub async fn create_category(
+        db: State<'_, Database>,
+        name: String,
+        category_type: String,
+        icon: Option<String>,
+        parent_id: Option<String>,
+    ) -> Result<Category, AppError> {
+        super::create_category(&db.pool(), name, category_type, icon, parent_id).await
+    }
+
+    #[tauri::command]
+    pub async fn update_category(
+        db: State<'_, Database>,
+        id: String,
+        name: Option<String>,
+        category_type: Option<String>,
+        icon: Option<String>,
+        parent_id: Option<String>,
+    ) -> Result<Category, AppError> {
+        super::update_category(&db.pool(), id, name, category_type, icon, parent_id).await
+    }
+
+    #[tauri::command]
+    pub async fn delete_category(db: State<'_, Database>, id: String) -> Result<(), AppError> {
+        super::delete_category(&db.pool(), id).await
+    }
 }
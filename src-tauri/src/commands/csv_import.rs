@@ -0,0 +1,206 @@
+use sqlx::SqlitePool;
+
+use crate::db::models::{
+    CsvImportPreview, CsvImportProfile, CsvImportProfileInput, CsvImportResult, CsvImportRow,
+};
+use crate::db::queries::csv_import;
+use crate::services::csv_import::{commit, decode_content, preview, validate_profile};
+use crate::services::i18n::t_with;
+
+/// Validate that a profile's default category exists and has the expected type.
+async fn validate_default_category(
+    pool: &SqlitePool,
+    category_id: Option<&str>,
+    expected_type: &str,
+) -> Result<(), String> {
+    let Some(category_id) = category_id else {
+        return Ok(());
+    };
+
+    let category: Option<(String,)> = sqlx::query_as("SELECT type FROM categories WHERE id = ?")
+        .bind(category_id)
+        .fetch_optional(pool)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    match category {
+        None => Err(t_with("category.not_found", &[("id", &category_id)])),
+        Some((cat_type,)) if cat_type != expected_type => Err(t_with(
+            "category.default_type",
+            &[("id", &category_id), ("type", &expected_type)],
+        )),
+        _ => Ok(()),
+    }
+}
+
+/// Validate a full profile input, including uniqueness of the name.
+async fn validate_input(
+    pool: &SqlitePool,
+    input: &CsvImportProfileInput,
+    current_id: Option<&str>,
+) -> Result<(), String> {
+    validate_profile(input)?;
+    validate_default_category(
+        pool,
+        input.default_expense_category_id.as_deref(),
+        "expense",
+    )
+    .await?;
+    validate_default_category(pool, input.default_income_category_id.as_deref(), "income").await?;
+
+    let duplicate = csv_import::get_id_by_name(pool, input.name.trim())
+        .await
+        .map_err(|e| e.to_string())?;
+    if duplicate.is_some_and(|id| Some(id.as_str()) != current_id) {
+        return Err(t_with(
+            "csv.profile_exists",
+            &[("name", &input.name.trim())],
+        ));
+    }
+    Ok(())
+}
+
+/// List all saved CSV import profiles.
+pub async fn list_csv_import_profiles(pool: &SqlitePool) -> Result<Vec<CsvImportProfile>, String> {
+    csv_import::get_all(pool).await.map_err(|e| e.to_string())
+}
+
+/// Save a new CSV import profile.
+pub async fn create_csv_import_profile(
+    pool: &SqlitePool,
+    input: CsvImportProfileInput,
+) -> Result<CsvImportProfile, String> {
+    let input = CsvImportProfileInput {
+        name: input.name.trim().to_string(),
+        ..input
+    };
+    validate_input(pool, &input, None).await?;
+
+    let id = uuid::Uuid::new_v4().to_string();
+    csv_import::create(pool, &id, &input)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Replace every field of an existing CSV import profile.
+pub async fn update_csv_import_profile(
+    pool: &SqlitePool,
+    id: String,
+    input: CsvImportProfileInput,
+) -> Result<CsvImportProfile, String> {
+    csv_import::get_by_id(pool, &id)
+        .await
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| t_with("csv.profile_not_found", &[("id", &id)]))?;
+
+    let input = CsvImportProfileInput {
+        name: input.name.trim().to_string(),
+        ..input
+    };
+    validate_input(pool, &input, Some(&id)).await?;
+
+    csv_import::update(pool, &id, &input)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Delete a CSV import profile. Previously imported transactions are untouched.
+pub async fn delete_csv_import_profile(pool: &SqlitePool, id: String) -> Result<(), String> {
+    csv_import::get_by_id(pool, &id)
+        .await
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| t_with("csv.profile_not_found", &[("id", &id)]))?;
+
+    csv_import::delete(pool, &id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Parse the CSV file at `path` with a saved profile and return the rows that would be
+/// imported into the account, with parse errors and likely duplicates flagged.
+pub async fn preview_csv_import(
+    pool: &SqlitePool,
+    account_id: String,
+    profile_id: String,
+    path: String,
+) -> Result<CsvImportPreview, String> {
+    let profile = csv_import::get_by_id(pool, &profile_id)
+        .await
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| t_with("csv.profile_not_found", &[("id", &profile_id)]))?;
+
+    let bytes = std::fs::read(&path).map_err(|e| t_with("file.read_failed", &[("error", &e)]))?;
+    let content = decode_content(bytes);
+
+    preview(pool, &account_id, &profile, &content).await
+}
+
+/// Import the reviewed rows into the account in a single SQL transaction.
+pub async fn commit_csv_import(
+    pool: &SqlitePool,
+    account_id: String,
+    rows: Vec<CsvImportRow>,
+) -> Result<CsvImportResult, String> {
+    commit(pool, &account_id, &rows).await
+}
+
+/// Tauri handlers. Each forwards to the function of the same name above with the current pool.
+pub mod ipc {
+    use tauri::State;
+
+    use super::{
+        CsvImportPreview, CsvImportProfile, CsvImportProfileInput, CsvImportResult, CsvImportRow,
+    };
+    use crate::db::Database;
+
+    #[tauri::command]
+    pub async fn list_csv_import_profiles(
+        db: State<'_, Database>,
+    ) -> Result<Vec<CsvImportProfile>, String> {
+        super::list_csv_import_profiles(&db.pool()).await
+    }
+
+    #[tauri::command]
+    pub async fn create_csv_import_profile(
+        db: State<'_, Database>,
+        input: CsvImportProfileInput,
+    ) -> Result<CsvImportProfile, String> {
+        super::create_csv_import_profile(&db.pool(), input).await
+    }
+
+    #[tauri::command]
+    pub async fn update_csv_import_profile(
+        db: State<'_, Database>,
+        id: String,
+        input: CsvImportProfileInput,
+    ) -> Result<CsvImportProfile, String> {
+        super::update_csv_import_profile(&db.pool(), id, input).await
+    }
+
+    #[tauri::command]
+    pub async fn delete_csv_import_profile(
+        db: State<'_, Database>,
+        id: String,
+    ) -> Result<(), String> {
+        super::delete_csv_import_profile(&db.pool(), id).await
+    }
+
+    #[tauri::command]
+    pub async fn preview_csv_import(
+        db: State<'_, Database>,
+        account_id: String,
+        profile_id: String,
+        path: String,
+    ) -> Result<CsvImportPreview, String> {
+        super::preview_csv_import(&db.pool(), account_id, profile_id, path).await
+    }
+
+    #[tauri::command]
+    pub async fn commit_csv_import(
+        db: State<'_, Database>,
+        account_id: String,
+        rows: Vec<CsvImportRow>,
+    ) -> Result<CsvImportResult, String> {
+        super::commit_csv_import(&db.pool(), account_id, rows).await
+    }
+}
@@ -1,43 +1,35 @@
 use std::collections::HashMap;
 
+use chrono::NaiveDate;
 use sqlx::SqlitePool;
-use tauri::State;
 
+use crate::commands::exchange_rates::{apply_rate, scale_rate};
 use crate::db::models::{
-    AccountBalance, BalanceSummary, CategorySpending, DashboardData, MonthlyIncomeExpense,
+    AccountBalance, BalanceSummary, CategorySpending, DashboardData, DashboardPeriod,
+    MonthlyComparison, MonthlyComparisonFilter, MonthlyComparisonPoint, MonthlyIncomeExpense,
     RecentTransaction,
 };
 use crate::db::queries::dashboard;
+use crate::error::AppError;
+use crate::services::dates::month_bounds;
+use crate::services::i18n;
+use crate::services::periods::{self, DateRange};
+use crate::services::settings;
 
-/// Map a month number (1-12) to its Spanish name.
-fn spanish_month_name(month: i32) -> &'static str {
-    match month {
-        1 => "Enero",
-        2 => "Febrero",
-        3 => "Marzo",
-        4 => "Abril",
-        5 => "Mayo",
-        6 => "Junio",
-        7 => "Julio",
-        8 => "Agosto",
-        9 => "Septiembre",
-        10 => "Octubre",
-        11 => "Noviembre",
-        12 => "Diciembre",
-        _ => "Desconocido",
-    }
-}
+/// Months returned by `get_monthly_comparison` when none are requested.
+const DEFAULT_COMPARISON_MONTHS: u32 = 12;
 
 /// Pre-fetch all latest exchange rates to base currency into a HashMap.
-/// Key: from_currency_code, Value: rate to base currency.
-async fn fetch_exchange_rates(
+/// Key: from_currency_code, Value: rate to base currency, scaled for minor units.
+pub(crate) async fn fetch_exchange_rates(
     pool: &SqlitePool,
     base_cur_id: &str,
-) -> Result<HashMap<String, f64>, String> {
-    let rows: Vec<(String, f64)> = sqlx::query_as(
-        "SELECT c.code, er.rate
+) -> Result<HashMap<String, f64>, AppError> {
+    let rows: Vec<(String, f64, i32, i32)> = sqlx::query_as(
+        "SELECT c.code, er.rate, c.decimal_places, base.decimal_places
          FROM exchange_rates er
          JOIN currencies c ON er.from_currency_id = c.id
+         JOIN currencies base ON er.to_currency_id = base.id
          WHERE er.to_currency_id = ?
            AND er.date = (SELECT MAX(date) FROM exchange_rates er2
                            WHERE er2.from_currency_id = er.from_currency_id
@@ -45,14 +37,18 @@ async fn fetch_exchange_rates(
     )
     .bind(base_cur_id)
     .fetch_all(pool)
-    .await
-    .map_err(|e| e.to_string())?;
+    .await?;
 
-    Ok(rows.into_iter().collect())
+    Ok(rows
+        .into_iter()
+        .map(|(code, rate, from_places, base_places)| {
+            (code, scale_rate(rate, from_places, base_places))
+        })
+        .collect())
 }
 
 /// Convert an amount from a given currency to the base currency using pre-fetched rates.
-fn convert_to_base(
+pub(crate) fn convert_to_base(
     amount: i64,
     currency_code: &str,
     base_code: &str,
@@ -61,32 +57,104 @@ fn convert_to_base(
     if currency_code == base_code {
         Some(amount)
     } else {
-        rates.get(currency_code).map(|rate| {
-            #[allow(clippy::cast_precision_loss)]
-            let converted = (amount as f64 * rate).round() as i64;
-            converted
-        })
+        rates
+            .get(currency_code)
+            .map(|rate| apply_rate(amount, *rate))
+    }
+}
+
+/// Get the code of the base currency, falling back to CLP if it is missing.
+pub(crate) async fn base_currency_code(
+    pool: &SqlitePool,
+    base_cur_id: &str,
+) -> Result<String, AppError> {
+    let row: Option<(String,)> = sqlx::query_as("SELECT code FROM currencies WHERE id = ?")
+        .bind(base_cur_id)
+        .fetch_optional(pool)
+        .await?;
+
+    Ok(row.map_or_else(|| "CLP".to_string(), |(code,)| code))
+}
+
+/// Sum income and expense transactions in a range, converted to base currency.
+/// Transactions without an exchange rate are skipped.
+async fn income_expense(
+    pool: &SqlitePool,
+    range: DateRange,
+    base_currency_code: &str,
+    rates: &HashMap<String, f64>,
+) -> Result<(i64, i64), AppError> {
+    let txns =
+        dashboard::get_monthly_transactions(pool, &range.start_str(), &range.end_str()).await?;
+
+    let mut income: i64 = 0;
+    let mut expense: i64 = 0;
+
+    for (amount, txn_type, currency_code) in &txns {
+        if let Some(converted) = convert_to_base(*amount, currency_code, base_currency_code, rates)
+        {
+            match txn_type.as_str() {
+                "income" => income += converted,
+                "expense" => expense += converted,
+                _ => {}
+            }
+        }
+    }
+
+    Ok((income, expense))
+}
+
+/// Total expense per top-level category in a range, converted to base currency.
+/// Key: `category_id`, Value: `(category_name, amount)`.
+async fn category_totals(
+    pool: &SqlitePool,
+    range: DateRange,
+    base_currency_code: &str,
+    rates: &HashMap<String, f64>,
+) -> Result<HashMap<String, (String, i64)>, AppError> {
+    let rows = dashboard::get_category_spending(pool, &range.start_str(), &range.end_str()).await?;
+
+    // Group by category_id, converting each amount to base currency
+    let mut category_map: HashMap<String, (String, i64)> = HashMap::new();
+
+    for (cat_id, cat_name, _, amount, currency_code) in &rows {
+        if let Some(converted) = convert_to_base(*amount, currency_code, base_currency_code, rates)
+        {
+            let entry = category_map
+                .entry(cat_id.clone())
+                .or_insert_with(|| (cat_name.clone(), 0));
+            entry.1 += converted;
+        }
     }
+
+    Ok(category_map)
 }
 
-/// Get all dashboard data in a single IPC call: balance summary, monthly income/expense,
-/// top spending categories, and recent transactions.
-#[tauri::command]
-pub async fn get_dashboard_data(pool: State<'_, SqlitePool>) -> Result<DashboardData, String> {
-    let base_cur_id = "cur_clp";
+/// Get all dashboard data in a single IPC call: balance summary, income/expense and top
+/// spending categories for the selected period (this month by default) compared against
+/// the previous equivalent period, and the latest transactions up to the period's end.
+pub async fn get_dashboard_data(
+    pool: &SqlitePool,
+    period: Option<DashboardPeriod>,
+) -> Result<DashboardData, AppError> {
+    let period = period.unwrap_or_else(|| DashboardPeriod {
+        period_type: "this_month".to_string(),
+        months: None,
+        date_from: None,
+        date_to: None,
+    });
+    let range = periods::resolve(&period, chrono::Local::now().date_naive())
+        .map_err(|e| AppError::validation(e).on("period"))?;
+    let previous_range = periods::previous(&period.period_type, range)
+        .map_err(|e| AppError::validation(e).on("period"))?;
+
+    let base_cur_id = settings::base_currency_id(pool).await?;
 
     // Get base currency code
-    let base_currency_code: String =
-        sqlx::query_as::<_, (String,)>("SELECT code FROM currencies WHERE id = ?")
-            .bind(base_cur_id)
-            .fetch_optional(pool.inner())
-            .await
-            .map_err(|e| e.to_string())?
-            .map(|(code,)| code)
-            .unwrap_or_else(|| "CLP".to_string());
+    let base_currency_code = base_currency_code(pool, &base_cur_id).await?;
 
     // Pre-fetch exchange rates for all currency conversions
-    let rates = fetch_exchange_rates(pool.inner(), base_cur_id).await?;
+    let rates = fetch_exchange_rates(pool, &base_cur_id).await?;
 
     // ── Balance Summary ──────────────────────────────────────────────
     let accounts: Vec<(String, String, i64, String)> = sqlx::query_as(
@@ -95,9 +163,8 @@ pub async fn get_dashboard_data(pool: State<'_, SqlitePool>) -> Result<Dashboard
          JOIN currencies c ON a.currency_id = c.id
          WHERE a.is_active = 1",
     )
-    .fetch_all(pool.inner())
-    .await
-    .map_err(|e| e.to_string())?;
+    .fetch_all(pool)
+    .await?;
 
     let account_balances: Vec<AccountBalance> = accounts
         .iter()
@@ -127,55 +194,26 @@ pub async fn get_dashboard_data(pool: State<'_, SqlitePool>) -> Result<Dashboard
         base_currency_code: base_currency_code.clone(),
     };
 
-    // ── Monthly Income / Expense ─────────────────────────────────────
-    let monthly_txns = dashboard::get_monthly_transactions(pool.inner())
-        .await
-        .map_err(|e| e.to_string())?;
-
-    let (month_num, year) = dashboard::get_current_month_info(pool.inner())
-        .await
-        .map_err(|e| e.to_string())?;
-
-    let mut income: i64 = 0;
-    let mut expense: i64 = 0;
-
-    for (amount, txn_type, currency_code) in &monthly_txns {
-        if let Some(converted) =
-            convert_to_base(*amount, currency_code, &base_currency_code, &rates)
-        {
-            match txn_type.as_str() {
-                "income" => income += converted,
-                "expense" => expense += converted,
-                _ => {}
-            }
-        }
-    }
+    // ── Income / Expense ─────────────────────────────────────────────
+    let (income, expense) = income_expense(pool, range, &base_currency_code, &rates).await?;
+    let (previous_income, previous_expense) =
+        income_expense(pool, previous_range, &base_currency_code, &rates).await?;
 
     let monthly_income_expense = MonthlyIncomeExpense {
         income,
         expense,
-        month_name: spanish_month_name(month_num).to_string(),
-        year,
+        previous_income,
+        previous_expense,
+        income_change: periods::change_percentage(income, previous_income),
+        expense_change: periods::change_percentage(expense, previous_expense),
+        period: periods::info(&period.period_type, range),
+        previous_period: periods::info(&period.period_type, previous_range),
     };
 
     // ── Top Spending Categories ──────────────────────────────────────
-    let category_rows = dashboard::get_category_spending(pool.inner())
-        .await
-        .map_err(|e| e.to_string())?;
-
-    // Group by category_id, converting each amount to base currency
-    let mut category_map: HashMap<String, (String, i64)> = HashMap::new();
-
-    for (cat_id, cat_name, amount, currency_code) in &category_rows {
-        if let Some(converted) =
-            convert_to_base(*amount, currency_code, &base_currency_code, &rates)
-        {
-            let entry = category_map
-                .entry(cat_id.clone())
-                .or_insert_with(|| (cat_name.clone(), 0));
-            entry.1 += converted;
-        }
-    }
+    let category_map = category_totals(pool, range, &base_currency_code, &rates).await?;
+    let previous_map = category_totals(pool, previous_range, &base_currency_code, &rates).await?;
+    let previous_of = |cat_id: &str| previous_map.get(cat_id).map_or(0, |(_, amount)| *amount);
 
     // Sort descending by amount
     let mut sorted_categories: Vec<(String, String, i64)> = category_map
@@ -186,6 +224,7 @@ pub async fn get_dashboard_data(pool: State<'_, SqlitePool>) -> Result<Dashboard
 
     // Calculate total spending for percentage
     let total_spending: i64 = sorted_categories.iter().map(|(_, _, amt)| amt).sum();
+    let previous_total: i64 = previous_map.values().map(|(_, amt)| amt).sum();
 
     // Build top 5 + "Otros"
     let mut top_categories: Vec<CategorySpending> = Vec::new();
@@ -193,16 +232,22 @@ pub async fn get_dashboard_data(pool: State<'_, SqlitePool>) -> Result<Dashboard
     if total_spending > 0 {
         let top_count = sorted_categories.len().min(5);
         let mut otros_amount: i64 = 0;
+        // Previous spending of everything outside the top categories
+        let mut otros_previous = previous_total;
 
         for (i, (cat_id, cat_name, amount)) in sorted_categories.iter().enumerate() {
             if i < top_count {
                 #[allow(clippy::cast_precision_loss)]
                 let percentage = (*amount as f64 / total_spending as f64) * 100.0;
+                let previous_amount = previous_of(cat_id);
+                otros_previous -= previous_amount;
                 top_categories.push(CategorySpending {
                     category_id: cat_id.clone(),
                     category_name: cat_name.clone(),
                     amount: *amount,
                     percentage,
+                    previous_amount,
+                    change: periods::change_percentage(*amount, previous_amount),
                 });
             } else {
                 otros_amount += amount;
@@ -214,17 +259,17 @@ pub async fn get_dashboard_data(pool: State<'_, SqlitePool>) -> Result<Dashboard
             let percentage = (otros_amount as f64 / total_spending as f64) * 100.0;
             top_categories.push(CategorySpending {
                 category_id: "otros".to_string(),
-                category_name: "Otros".to_string(),
+                category_name: i18n::t("dashboard.others"),
                 amount: otros_amount,
                 percentage,
+                previous_amount: otros_previous,
+                change: periods::change_percentage(otros_amount, otros_previous),
             });
         }
     }
 
     // ── Recent Transactions ──────────────────────────────────────────
-    let recent_rows = dashboard::get_recent_transactions(pool.inner())
-        .await
-        .map_err(|e| e.to_string())?;
+    let recent_rows = dashboard::get_recent_transactions(pool, &range.end_str()).await?;
 
     let recent_transactions: Vec<RecentTransaction> = recent_rows
         .into_iter()
@@ -260,3 +305,148 @@ pub async fn get_dashboard_data(pool: State<'_, SqlitePool>) -> Result<Dashboard
         recent_transactions,
     })
 }
+
+/// Fetch the rate history of every currency into base currency, grouped by currency code.
+/// Rates are scaled for minor units and sorted oldest first.
+pub(crate) async fn fetch_rate_history(
+    pool: &SqlitePool,
+    base_cur_id: &str,
+) -> Result<HashMap<String, Vec<(NaiveDate, f64)>>, AppError> {
+    let rows = dashboard::get_rate_history(pool, base_cur_id).await?;
+
+    let mut history: HashMap<String, Vec<(NaiveDate, f64)>> = HashMap::new();
+    for (code, date, rate, from_places, base_places) in rows {
+        let Ok(date) = NaiveDate::parse_from_str(&date, "%Y-%m-%d") else {
+            continue;
+        };
+        history
+            .entry(code)
+            .or_default()
+            .push((date, scale_rate(rate, from_places, base_places)));
+    }
+    Ok(history)
+}
+
+/// Pick the rate closest to a month: the latest one dated inside it, otherwise the nearest
+/// one before or after it, preferring the earlier rate on ties.
+fn rate_for_month(history: &[(NaiveDate, f64)], month: DateRange) -> Option<f64> {
+    if let Some((_, rate)) = history
+        .iter()
+        .rev()
+        .find(|(date, _)| *date >= month.start && *date < month.end)
+    {
+        return Some(*rate);
+    }
+
+    let before = history.iter().rev().find(|(date, _)| *date < month.start);
+    let after = history.iter().find(|(date, _)| *date >= month.end);
+
+    match (before, after) {
+        (Some((before_date, before_rate)), Some((after_date, after_rate))) => {
+            if month.start - *before_date <= *after_date - month.last_day() {
+                Some(*before_rate)
+            } else {
+                Some(*after_rate)
+            }
+        }
+        (Some((_, rate)), None) | (None, Some((_, rate))) => Some(*rate),
+        (None, None) => None,
+    }
+}
+
+/// Get income, expense and net per month for the last `months` months (12 by default,
+/// counting the current one), oldest first. Each month is converted to base currency with
+/// the exchange rate closest to that month; transactions without any rate are skipped.
+/// Transfers are excluded. Optionally filtered by account and by category, where a parent
+/// category includes its subcategories.
+pub async fn get_monthly_comparison(
+    pool: &SqlitePool,
+    months: Option<u32>,
+    filter: Option<MonthlyComparisonFilter>,
+) -> Result<MonthlyComparison, AppError> {
+    let period = DashboardPeriod {
+        period_type: "last_n_months".to_string(),
+        months: Some(months.unwrap_or(DEFAULT_COMPARISON_MONTHS)),
+        date_from: None,
+        date_to: None,
+    };
+    let range = periods::resolve(&period, chrono::Local::now().date_naive())
+        .map_err(|e| AppError::validation(e).on("months"))?;
+    let filter = filter.unwrap_or_default();
+
+    let base_cur_id = settings::base_currency_id(pool).await?;
+    let base_currency_code = base_currency_code(pool, &base_cur_id).await?;
+    let history = fetch_rate_history(pool, &base_cur_id).await?;
+
+    let rows =
+        dashboard::get_monthly_totals(pool, &range.start_str(), &range.end_str(), &filter).await?;
+
+    let mut points = Vec::new();
+    let mut cursor = range.start;
+
+    while cursor < range.end {
+        let (start, end) = month_bounds(cursor);
+        let month = DateRange { start, end };
+        let key = start.format("%Y-%m").to_string();
+
+        let mut income: i64 = 0;
+        let mut expense: i64 = 0;
+
+        for (_, txn_type, amount, currency_code) in rows.iter().filter(|row| row.0 == key) {
+            let converted = if *currency_code == base_currency_code {
+                Some(*amount)
+            } else {
+                history
+                    .get(currency_code)
+                    .and_then(|rates| rate_for_month(rates, month))
+                    .map(|rate| apply_rate(*amount, rate))
+            };
+            match (txn_type.as_str(), converted) {
+                ("income", Some(converted)) => income += converted,
+                ("expense", Some(converted)) => expense += converted,
+                _ => {}
+            }
+        }
+
+        points.push(MonthlyComparisonPoint {
+            label: periods::info("last_n_months", month).label,
+            month: key,
+            income,
+            expense,
+            net: income - expense,
+        });
+        cursor = end;
+    }
+
+    Ok(MonthlyComparison {
+        base_currency_code,
+        months: points,
+    })
+}
+
+/// Tauri handlers. Each forwards to the function of the same name above with the current pool.
+pub mod ipc {
+    use tauri::State;
+
+    use super::{
+        AppError, DashboardData, DashboardPeriod, MonthlyComparison, MonthlyComparisonFilter,
+    };
+    use crate::db::Database;
+
+    #[tauri::command]
+    pub async fn get_dashboard_data(
+        db: State<'_, Database>,
+        period: Option<DashboardPeriod>,
+    ) -> Result<DashboardData, AppError> {
+        super::get_dashboard_data(&db.pool(), period).await
+    }
+
+    #[tauri::command]
+    pub async fn get_monthly_comparison(
+        db: State<'_, Database>,
+        months: Option<u32>,
+        filter: Option<MonthlyComparisonFilter>,
+    ) -> Result<MonthlyComparison, AppError> {
+        super::get_monthly_comparison(&db.pool(), months, filter).await
+    }
+}
@@ -1,41 +1,33 @@
 use chrono::{Datelike, NaiveDate};
 use sqlx::{Sqlite, SqlitePool};
-use tauri::State;
 
+use crate::commands::transactions::{recalculate_account_balance, recalculate_account_balances};
 use crate::db::models::{
-    CreditUtilization, CreateDebtInput, Debt, DebtFilter, DebtProjectionEntry, DebtWithInstallments,
-    Installment, MonthlyProjection, UpdateDebtInput,
+    CreateDebtInput, CreditUtilization, Debt, DebtFilter, DebtPrepayment, DebtProjectionEntry,
+    DebtStatusChange, DebtWithInstallments, Installment, MonthlyProjection, PayInstallmentInput,
+    PrepayDebtInput, UpdateDebtInput,
 };
-use crate::db::queries::debts;
-
-/// Recalculate and update an account's balance based on the sum of its transactions.
-/// Must be called within an active SQL transaction.
-async fn recalculate_account_balance(
-    db_txn: &mut sqlx::Transaction<'_, Sqlite>,
-    account_id: &str,
-) -> Result<(), String> {
-    sqlx::query(
-        "UPDATE accounts SET balance = (
-            SELECT COALESCE(SUM(CASE WHEN type = 'income' THEN amount ELSE -amount END), 0)
-            FROM transactions WHERE account_id = ?
-         ) WHERE id = ?",
-    )
-    .bind(account_id)
-    .bind(account_id)
-    .execute(&mut **db_txn)
-    .await
-    .map_err(|e| e.to_string())?;
-    Ok(())
-}
+use crate::db::queries::{accounts, debts, transactions};
+use crate::error::AppError;
+use crate::services::amortization::{self, RateType, ScheduleRow};
+use crate::services::dates::last_day_of_month;
+use crate::services::i18n::{t, t_with};
+use crate::services::periods::parse_date;
+use crate::services::settings;
 
 /// Calculate due dates for installments based on billing_day or start_date day-of-month.
 fn calculate_due_dates(
     start_date: &str,
     billing_day: Option<i32>,
     total_installments: i32,
-) -> Result<Vec<String>, String> {
-    let start = NaiveDate::parse_from_str(start_date, "%Y-%m-%d")
-        .map_err(|e| format!("Fecha de inicio invalida: {e}"))?;
+) -> Result<Vec<String>, AppError> {
+    let start = NaiveDate::parse_from_str(start_date, "%Y-%m-%d").map_err(|e| {
+        AppError::validation(t_with(
+            "validation.field_invalid",
+            &[("field", &t("field.start_date")), ("error", &e)],
+        ))
+        .on("startDate")
+    })?;
 
     let day = billing_day.unwrap_or(start.day() as i32);
     let mut dates = Vec::new();
@@ -51,7 +43,10 @@ fn calculate_due_dates(
         let clamped_day = (day as u32).min(last_day);
 
         let date = NaiveDate::from_ymd_opt(target_year, target_month as u32, clamped_day)
-            .ok_or_else(|| format!("No se pudo calcular fecha para cuota {i}"))?;
+            .ok_or_else(|| {
+                AppError::validation(t_with("debt.due_date_failed", &[("number", &i)]))
+                    .on("startDate")
+            })?;
 
         dates.push(date.format("%Y-%m-%d").to_string());
     }
@@ -59,284 +54,479 @@ fn calculate_due_dates(
     Ok(dates)
 }
 
-/// Get the last day of a given month/year.
-fn last_day_of_month(year: i32, month: u32) -> u32 {
-    // Get the first day of the next month and subtract one day
-    if month == 12 {
-        NaiveDate::from_ymd_opt(year + 1, 1, 1)
-    } else {
-        NaiveDate::from_ymd_opt(year, month + 1, 1)
+/// An interest rate must be a finite number, zero or more.
+fn validate_interest_rate(rate: f64) -> Result<(), AppError> {
+    if !rate.is_finite() || rate < 0.0 {
+        return Err(AppError::validation(t("debt.interest_rate_negative")).on("interestRate"));
     }
-    .map(|d| d.pred_opt().map(|p| p.day()).unwrap_or(28))
-    .unwrap_or(28)
+    Ok(())
 }
 
-/// Create a new debt and auto-generate all installment rows.
-#[tauri::command]
-pub async fn create_debt(
-    pool: State<'_, SqlitePool>,
-    input: CreateDebtInput,
-) -> Result<DebtWithInstallments, String> {
-    // Validate inputs
+/// Validate the required fields of a new debt and return how its rate is quoted.
+fn validate_debt_input(input: &CreateDebtInput) -> Result<RateType, AppError> {
     if input.description.is_empty() {
-        return Err("La descripcion es obligatoria".into());
+        return Err(AppError::validation(t("validation.description_required")).on("description"));
     }
     if input.original_amount <= 0 {
-        return Err("El monto original debe ser mayor a 0".into());
+        return Err(AppError::validation(t("debt.original_amount_positive")).on("originalAmount"));
     }
     if input.total_installments <= 0 {
-        return Err("El numero de cuotas debe ser mayor a 0".into());
+        return Err(AppError::validation(t("debt.installments_positive")).on("totalInstallments"));
     }
-    if input.monthly_payment <= 0 {
-        return Err("El monto por cuota debe ser mayor a 0".into());
+    if input.monthly_payment.is_some_and(|payment| payment <= 0) {
+        return Err(AppError::validation(t("debt.monthly_payment_positive")).on("monthlyPayment"));
     }
+    validate_interest_rate(input.interest_rate)?;
     if input.start_date.is_empty() {
-        return Err("La fecha de inicio es obligatoria".into());
+        return Err(AppError::validation(t_with(
+            "validation.field_required",
+            &[("field", &t("field.start_date"))],
+        ))
+        .on("startDate"));
     }
 
+    let rate_type = input.rate_type.as_deref().unwrap_or("monthly");
+    RateType::parse(rate_type).ok_or_else(|| {
+        AppError::validation(t_with("debt.rate_type_invalid", &[("value", &rate_type)]))
+            .on("rateType")
+    })
+}
+
+/// Amortization schedule of a new debt. Without a quoted installment amount the level
+/// payment is computed from the principal, rate and term.
+fn amortize(input: &CreateDebtInput, rate_type: RateType) -> Result<Vec<ScheduleRow>, AppError> {
+    let periods = u32::try_from(input.total_installments).unwrap_or(0);
+    let monthly_rate = rate_type.monthly_rate(input.interest_rate);
+    let payment = input.monthly_payment.unwrap_or_else(|| {
+        amortization::level_payment(input.original_amount, monthly_rate, periods)
+    });
+
+    amortization::schedule(input.original_amount, monthly_rate, periods, payment).ok_or_else(|| {
+        AppError::validation(t_with(
+            "debt.payment_mismatch",
+            &[("amount", &payment), ("count", &periods)],
+        ))
+        .on("monthlyPayment")
+    })
+}
+
+/// Assemble a debt's detail view, totalling what is still owed and the interest paid so far
+/// and still to pay.
+fn with_installments(
+    debt: Debt,
+    installments: Vec<Installment>,
+    account_name: String,
+) -> DebtWithInstallments {
+    let (paid, unpaid): (Vec<&Installment>, Vec<&Installment>) = installments
+        .iter()
+        .partition(|i| matches!(i.status.as_str(), "paid" | "paid_late"));
+
+    DebtWithInstallments {
+        next_due_date: unpaid.first().map(|i| i.due_date.clone()),
+        remaining_amount: unpaid
+            .iter()
+            .map(|i| i.amount - i.paid_amount.unwrap_or(0))
+            .sum(),
+        interest_paid: paid.iter().map(|i| i.interest).sum(),
+        interest_remaining: unpaid.iter().map(|i| i.interest).sum(),
+        debt,
+        installments,
+        account_name,
+    }
+}
+
+/// Lifecycle states of a debt, as stored in `debts.status`.
+const DEBT_STATUSES: [&str; 4] = ["active", "paid_off", "defaulted", "refinanced"];
+
+/// State changes `change_debt_status` accepts. Debts become paid off, and active again, only
+/// as their installments are paid and unpaid.
+const MANUAL_TRANSITIONS: [(&str, &str); 4] = [
+    ("active", "defaulted"),
+    ("active", "refinanced"),
+    ("defaulted", "active"),
+    ("defaulted", "refinanced"),
+];
+
+fn validate_status(status: &str, field: &str) -> Result<(), AppError> {
+    if DEBT_STATUSES.contains(&status) {
+        Ok(())
+    } else {
+        Err(AppError::validation(t_with("debt.status_invalid", &[("value", &status)])).on(field))
+    }
+}
+
+/// Record a debt's state as changed from `from_status` to `to_status` for `reason`.
+/// Must be called within an active SQL transaction.
+async fn record_status(
+    db_txn: &mut sqlx::Transaction<'_, Sqlite>,
+    debt_id: &str,
+    from_status: Option<&str>,
+    to_status: &str,
+    reason: &str,
+    note: Option<String>,
+) -> Result<(), AppError> {
+    let change = DebtStatusChange {
+        id: uuid::Uuid::new_v4().to_string(),
+        debt_id: debt_id.to_string(),
+        from_status: from_status.map(str::to_string),
+        to_status: to_status.to_string(),
+        reason: reason.to_string(),
+        note,
+        changed_at: String::new(),
+    };
+    debts::create_status_change(db_txn, &change).await?;
+    Ok(())
+}
+
+/// Move a debt to `status` and record why. Paid-off and refinanced debts stop being active.
+/// Must be called within an active SQL transaction.
+pub(crate) async fn transition(
+    db_txn: &mut sqlx::Transaction<'_, Sqlite>,
+    debt: &Debt,
+    status: &str,
+    reason: &str,
+    note: Option<String>,
+) -> Result<(), AppError> {
+    let is_active = matches!(status, "active" | "defaulted");
+    debts::set_debt_status(db_txn, &debt.id, status, is_active).await?;
+    record_status(db_txn, &debt.id, Some(&debt.status), status, reason, note).await
+}
+
+/// Create a new debt with one installment row per month of its amortization schedule.
+pub async fn create_debt(
+    pool: &SqlitePool,
+    input: CreateDebtInput,
+) -> Result<DebtWithInstallments, AppError> {
+    let rate_type = validate_debt_input(&input)?;
+    let schedule = amortize(&input, rate_type)?;
+
+    // Fall back to the default account from settings
+    let account_id = match input.account_id.as_deref().filter(|id| !id.is_empty()) {
+        Some(id) => id.to_string(),
+        None => settings::default_account_id(pool)
+            .await?
+            .ok_or_else(|| AppError::validation(t("debt.account_required")).on("accountId"))?,
+    };
+
     // Validate account exists and is active
-    let account: Option<(String, String, i32, Option<i32>)> = sqlx::query_as(
-        "SELECT id, name, is_active, billing_day FROM accounts WHERE id = ?",
-    )
-    .bind(&input.account_id)
-    .fetch_optional(pool.inner())
-    .await
-    .map_err(|e| e.to_string())?;
+    let account: Option<(String, String, i32, Option<i32>)> =
+        sqlx::query_as("SELECT id, name, is_active, billing_day FROM accounts WHERE id = ?")
+            .bind(&account_id)
+            .fetch_optional(pool)
+            .await?;
 
     let (_, account_name, is_active, billing_day) = match account {
-        None => return Err(format!("Cuenta no encontrada: {}", input.account_id)),
+        None => {
+            return Err(
+                AppError::not_found(t_with("account.not_found", &[("id", &account_id)]))
+                    .on("accountId"),
+            )
+        }
         Some(a) => a,
     };
 
     if is_active != 1 {
-        return Err("No se puede crear deudas en una cuenta archivada".into());
+        return Err(AppError::validation(t("debt.archived_account")).on("accountId"));
     }
 
     // Calculate due dates
-    let due_dates =
-        calculate_due_dates(&input.start_date, billing_day, input.total_installments)?;
+    let due_dates = calculate_due_dates(&input.start_date, billing_day, input.total_installments)?;
 
     let debt_id = uuid::Uuid::new_v4().to_string();
+    let monthly_payment = schedule.first().map_or(0, |row| row.amount);
 
     // Begin atomic transaction
-    let mut db_txn = pool.begin().await.map_err(|e| e.to_string())?;
+    let mut db_txn = pool.begin().await?;
 
     // Insert debt
     sqlx::query(
-        "INSERT INTO debts (id, account_id, description, original_amount, total_installments, paid_installments, monthly_payment, interest_rate, start_date, notes)
-         VALUES (?, ?, ?, ?, ?, 0, ?, ?, ?, ?)",
+        "INSERT INTO debts (id, account_id, description, original_amount, total_installments, paid_installments, monthly_payment, interest_rate, rate_type, start_date, notes)
+         VALUES (?, ?, ?, ?, ?, 0, ?, ?, ?, ?, ?)",
     )
     .bind(&debt_id)
-    .bind(&input.account_id)
+    .bind(&account_id)
     .bind(&input.description)
     .bind(input.original_amount)
     .bind(input.total_installments)
-    .bind(input.monthly_payment)
+    .bind(monthly_payment)
     .bind(input.interest_rate)
+    .bind(rate_type.as_str())
     .bind(&input.start_date)
     .bind(input.notes.as_deref())
     .execute(&mut *db_txn)
-    .await
-    .map_err(|e| e.to_string())?;
+    .await?;
 
     // Insert all installment rows
     let mut installments = Vec::new();
-    for (i, due_date) in due_dates.iter().enumerate() {
+    for (i, (due_date, row)) in due_dates.iter().zip(&schedule).enumerate() {
         let inst_id = uuid::Uuid::new_v4().to_string();
         let number = (i + 1) as i32;
 
         sqlx::query(
-            "INSERT INTO installments (id, debt_id, installment_number, due_date, amount, status)
-             VALUES (?, ?, ?, ?, ?, 'pending')",
+            "INSERT INTO installments (id, debt_id, installment_number, due_date, amount, principal, interest, balance, status)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?, 'pending')",
         )
         .bind(&inst_id)
         .bind(&debt_id)
         .bind(number)
         .bind(due_date)
-        .bind(input.monthly_payment)
+        .bind(row.amount)
+        .bind(row.principal)
+        .bind(row.interest)
+        .bind(row.balance)
         .execute(&mut *db_txn)
-        .await
-        .map_err(|e| e.to_string())?;
+        .await?;
 
         installments.push(Installment {
             id: inst_id,
             debt_id: debt_id.clone(),
             installment_number: number,
             due_date: due_date.clone(),
-            amount: input.monthly_payment,
+            amount: row.amount,
+            principal: row.principal,
+            interest: row.interest,
+            balance: row.balance,
             status: "pending".to_string(),
+            paid_amount: None,
             actual_payment_date: None,
             transaction_id: None,
             created_at: String::new(), // Will be set by DB default
         });
     }
 
-    db_txn.commit().await.map_err(|e| e.to_string())?;
+    record_status(&mut db_txn, &debt_id, None, "active", "created", None).await?;
+
+    db_txn.commit().await?;
 
     // Fetch the created debt with computed paid_installments
-    let debt = debts::get_debt_by_id(&pool, &debt_id)
-        .await
-        .map_err(|e| e.to_string())?
-        .ok_or_else(|| "Deuda creada pero no encontrada".to_string())?;
+    let debt = debts::get_debt_by_id(pool, &debt_id)
+        .await?
+        .ok_or_else(|| AppError::database(t("debt.created_missing")))?;
 
     // Fetch installments from DB to get proper created_at
-    let installments = debts::list_installments_for_debt(&pool, &debt_id)
-        .await
-        .map_err(|e| e.to_string())?;
+    let installments = debts::list_installments_for_debt(pool, &debt_id).await?;
 
-    let next_due_date = installments.first().map(|i| i.due_date.clone());
-    let remaining_amount: i64 = installments
-        .iter()
-        .filter(|i| i.status == "pending")
-        .map(|i| i.amount)
-        .sum();
+    Ok(with_installments(debt, installments, account_name))
+}
 
-    Ok(DebtWithInstallments {
-        debt,
-        installments,
-        account_name,
-        next_due_date,
-        remaining_amount,
-    })
+/// Rewrite a debt's pending installments with `schedule`, dropping any past its end, and store
+/// the resulting term and installment amount. A schedule that is empty or longer than
+/// `pending` is refused. Must be called within an active SQL transaction.
+async fn write_schedule(
+    db_txn: &mut sqlx::Transaction<'_, Sqlite>,
+    debt_id: &str,
+    pending: &[Installment],
+    schedule: &[ScheduleRow],
+) -> Result<(), AppError> {
+    // The last installment kept; any after it are no longer needed
+    let Some(last) = schedule.len().checked_sub(1).and_then(|i| pending.get(i)) else {
+        return Err(AppError::conflict(t("debt.schedule_mismatch")));
+    };
+    for (installment, row) in pending.iter().zip(schedule) {
+        debts::update_installment_schedule(
+            db_txn,
+            &installment.id,
+            row.amount,
+            row.principal,
+            row.interest,
+            row.balance,
+        )
+        .await?;
+    }
+    debts::delete_pending_installments_after(db_txn, debt_id, last.installment_number).await?;
+    debts::update_debt_schedule(db_txn, debt_id, last.installment_number, schedule[0].amount)
+        .await?;
+    Ok(())
 }
 
-/// Update an existing debt (metadata fields only).
-#[tauri::command]
+/// Update an existing debt's metadata. A new interest rate re-amortizes the pending
+/// installments over the same term, in the same SQL transaction. The debt's state only
+/// changes through `change_debt_status` and its payments.
 pub async fn update_debt(
-    pool: State<'_, SqlitePool>,
+    pool: &SqlitePool,
     id: String,
     input: UpdateDebtInput,
-) -> Result<Debt, String> {
+) -> Result<Debt, AppError> {
     // Verify debt exists
-    let existing = debts::get_debt_by_id(&pool, &id)
-        .await
-        .map_err(|e| e.to_string())?
-        .ok_or_else(|| format!("Deuda no encontrada: {id}"))?;
+    let existing = debts::get_debt_by_id(pool, &id)
+        .await?
+        .ok_or_else(|| AppError::not_found(t_with("debt.not_found", &[("id", &id)])))?;
+    if let Some(rate) = input.interest_rate {
+        validate_interest_rate(rate)?;
+    }
 
-    let final_description = input.description.as_deref().unwrap_or(&existing.description);
+    let final_description = input
+        .description
+        .as_deref()
+        .unwrap_or(&existing.description);
     let final_interest_rate = input.interest_rate.unwrap_or(existing.interest_rate);
-    let final_is_active = input
-        .is_active
-        .map(|b| if b { 1 } else { 0 })
-        .unwrap_or(existing.is_active);
     let final_notes = input.notes.as_deref().or(existing.notes.as_deref());
 
-    sqlx::query(
-        "UPDATE debts SET description = ?, interest_rate = ?, is_active = ?, notes = ? WHERE id = ?",
-    )
-    .bind(final_description)
-    .bind(final_interest_rate)
-    .bind(final_is_active)
-    .bind(final_notes)
-    .bind(&id)
-    .execute(pool.inner())
-    .await
-    .map_err(|e| e.to_string())?;
-
-    debts::get_debt_by_id(&pool, &id)
-        .await
-        .map_err(|e| e.to_string())?
-        .ok_or_else(|| "Deuda actualizada pero no encontrada".into())
+    let schedule = if (final_interest_rate - existing.interest_rate).abs() > f64::EPSILON {
+        reamortize(pool, &existing, final_interest_rate).await?
+    } else {
+        None
+    };
+
+    let mut db_txn = pool.begin().await?;
+    sqlx::query("UPDATE debts SET description = ?, interest_rate = ?, notes = ? WHERE id = ?")
+        .bind(final_description)
+        .bind(final_interest_rate)
+        .bind(final_notes)
+        .bind(&id)
+        .execute(&mut *db_txn)
+        .await?;
+    if let Some((pending, schedule)) = &schedule {
+        write_schedule(&mut db_txn, &id, pending, schedule).await?;
+    }
+    db_txn.commit().await?;
+
+    debts::get_debt_by_id(pool, &id)
+        .await?
+        .ok_or_else(|| AppError::database(t("debt.updated_missing")))
+}
+
+/// The pending installments of a debt and their schedule at a new `interest_rate`, keeping
+/// the number of installments. `None` when nothing is pending.
+async fn reamortize(
+    pool: &SqlitePool,
+    debt: &Debt,
+    interest_rate: f64,
+) -> Result<Option<(Vec<Installment>, Vec<ScheduleRow>)>, AppError> {
+    let pending: Vec<Installment> = debts::list_installments_for_debt(pool, &debt.id)
+        .await?
+        .into_iter()
+        .filter(|i| i.status == "pending")
+        .collect();
+    let Some(periods) = u32::try_from(pending.len()).ok().filter(|&n| n > 0) else {
+        return Ok(None);
+    };
+
+    let principal = pending.iter().map(|i| i.principal).sum();
+    let rate = RateType::parse(&debt.rate_type)
+        .unwrap_or(RateType::Monthly)
+        .monthly_rate(interest_rate);
+    let payment = amortization::level_payment(principal, rate, periods);
+    let schedule = amortization::schedule(principal, rate, periods, payment).ok_or_else(|| {
+        AppError::validation(t_with(
+            "debt.payment_mismatch",
+            &[("amount", &payment), ("count", &periods)],
+        ))
+        .on("interestRate")
+    })?;
+    Ok(Some((pending, schedule)))
 }
 
 /// Delete a debt. CASCADE handles installments. Auto-created transactions remain.
-#[tauri::command]
-pub async fn delete_debt(pool: State<'_, SqlitePool>, id: String) -> Result<(), String> {
-    let _existing = debts::get_debt_by_id(&pool, &id)
-        .await
-        .map_err(|e| e.to_string())?
-        .ok_or_else(|| format!("Deuda no encontrada: {id}"))?;
+pub async fn delete_debt(pool: &SqlitePool, id: String) -> Result<(), AppError> {
+    let _existing = debts::get_debt_by_id(pool, &id)
+        .await?
+        .ok_or_else(|| AppError::not_found(t_with("debt.not_found", &[("id", &id)])))?;
 
-    debts::delete_debt(&pool, &id)
-        .await
-        .map_err(|e| e.to_string())
+    debts::delete_debt(pool, &id).await.map_err(AppError::from)
 }
 
 /// List debts with optional filtering.
-#[tauri::command]
-pub async fn list_debts(
-    pool: State<'_, SqlitePool>,
-    filter: DebtFilter,
-) -> Result<Vec<Debt>, String> {
-    debts::list_debts(&pool, &filter)
+pub async fn list_debts(pool: &SqlitePool, filter: DebtFilter) -> Result<Vec<Debt>, AppError> {
+    for status in filter.statuses.iter().flatten() {
+        validate_status(status, "statuses")?;
+    }
+    debts::list_debts(pool, &filter)
         .await
-        .map_err(|e| e.to_string())
+        .map_err(AppError::from)
 }
 
-/// Get full debt detail with installments for expanded card view.
-#[tauri::command]
-pub async fn get_debt_detail(
-    pool: State<'_, SqlitePool>,
+/// Move a debt to another lifecycle state by hand: mark it defaulted or refinanced, or bring a
+/// defaulted debt back to active. The change is recorded in its history with `note`.
+pub async fn change_debt_status(
+    pool: &SqlitePool,
     id: String,
-) -> Result<DebtWithInstallments, String> {
-    let debt = debts::get_debt_by_id(&pool, &id)
-        .await
-        .map_err(|e| e.to_string())?
-        .ok_or_else(|| format!("Deuda no encontrada: {id}"))?;
+    status: String,
+    note: Option<String>,
+) -> Result<Debt, AppError> {
+    validate_status(&status, "status")?;
+    let debt = debts::get_debt_by_id(pool, &id)
+        .await?
+        .ok_or_else(|| AppError::not_found(t_with("debt.not_found", &[("id", &id)])))?;
+
+    if !MANUAL_TRANSITIONS.contains(&(debt.status.as_str(), status.as_str())) {
+        return Err(AppError::conflict(t_with(
+            "debt.status_transition_invalid",
+            &[("from", &debt.status), ("to", &status)],
+        )));
+    }
+    let note = note.map(|n| n.trim().to_string()).filter(|n| !n.is_empty());
 
-    let installments = debts::list_installments_for_debt(&pool, &id)
-        .await
-        .map_err(|e| e.to_string())?;
+    let mut db_txn = pool.begin().await?;
+    transition(&mut db_txn, &debt, &status, "manual", note).await?;
+    db_txn.commit().await?;
 
-    let account_name = debts::get_account_name_for_debt(&pool, &id)
+    debts::get_debt_by_id(pool, &id)
+        .await?
+        .ok_or_else(|| AppError::database(t("debt.updated_missing")))
+}
+
+/// List every change of a debt's state, oldest first, starting with its creation.
+pub async fn list_debt_status_history(
+    pool: &SqlitePool,
+    id: String,
+) -> Result<Vec<DebtStatusChange>, AppError> {
+    debts::get_debt_by_id(pool, &id)
+        .await?
+        .ok_or_else(|| AppError::not_found(t_with("debt.not_found", &[("id", &id)])))?;
+    debts::list_status_history(pool, &id)
         .await
-        .map_err(|e| e.to_string())?;
+        .map_err(AppError::from)
+}
 
-    let next_due_date = installments
-        .iter()
-        .find(|i| i.status == "pending")
-        .map(|i| i.due_date.clone());
+/// Get full debt detail with installments for expanded card view.
+pub async fn get_debt_detail(
+    pool: &SqlitePool,
+    id: String,
+) -> Result<DebtWithInstallments, AppError> {
+    let debt = debts::get_debt_by_id(pool, &id)
+        .await?
+        .ok_or_else(|| AppError::not_found(t_with("debt.not_found", &[("id", &id)])))?;
 
-    let remaining_amount: i64 = installments
-        .iter()
-        .filter(|i| i.status == "pending")
-        .map(|i| i.amount)
-        .sum();
+    let installments = debts::list_installments_for_debt(pool, &id).await?;
 
-    Ok(DebtWithInstallments {
-        debt,
-        installments,
-        account_name,
-        next_due_date,
-        remaining_amount,
-    })
-}
+    let account_name = debts::get_account_name_for_debt(pool, &id).await?;
 
-/// Mark an installment as paid, atomically creating an expense transaction.
-#[tauri::command]
-pub async fn mark_installment_paid(
-    pool: State<'_, SqlitePool>,
-    installment_id: String,
-    category_id: String,
-) -> Result<Installment, String> {
-    // Fetch installment
-    let installment = debts::get_installment_by_id(&pool, &installment_id)
-        .await
-        .map_err(|e| e.to_string())?
-        .ok_or_else(|| format!("Cuota no encontrada: {installment_id}"))?;
+    Ok(with_installments(debt, installments, account_name))
+}
 
-    if installment.status == "paid" {
-        return Err("Esta cuota ya fue pagada".into());
+/// The category a debt payment is recorded under: the one given, or the default
+/// debt-payment category from settings.
+async fn payment_category(
+    pool: &SqlitePool,
+    category_id: Option<String>,
+) -> Result<String, AppError> {
+    match category_id.filter(|id| !id.is_empty()) {
+        Some(id) => Ok(id),
+        None => settings::default_debt_category_id(pool)
+            .await?
+            .ok_or_else(|| {
+                AppError::validation(t("installment.category_required")).on("categoryId")
+            }),
     }
+}
 
-    // Fetch debt for account_id and description
-    let debt = debts::get_debt_by_id(&pool, &installment.debt_id)
-        .await
-        .map_err(|e| e.to_string())?
-        .ok_or_else(|| "Deuda no encontrada para esta cuota".to_string())?;
-
+/// Check that a payment of a debt can be charged to its account under `category_id`.
+async fn validate_payment(
+    pool: &SqlitePool,
+    account_id: &str,
+    category_id: &str,
+) -> Result<(), AppError> {
     // Validate account is active
-    let account: Option<(i32,)> =
-        sqlx::query_as("SELECT is_active FROM accounts WHERE id = ?")
-            .bind(&debt.account_id)
-            .fetch_optional(pool.inner())
-            .await
-            .map_err(|e| e.to_string())?;
+    let account: Option<(i32,)> = sqlx::query_as("SELECT is_active FROM accounts WHERE id = ?")
+        .bind(account_id)
+        .fetch_optional(pool)
+        .await?;
 
     match account {
-        None => return Err("Cuenta no encontrada".into()),
+        None => return Err(AppError::not_found(t("account.missing"))),
         Some((is_active,)) if is_active != 1 => {
-            return Err("No se puede registrar pagos en una cuenta archivada".into());
+            return Err(AppError::validation(t("installment.archived_account")));
         }
         _ => {}
     }
@@ -344,109 +534,423 @@ pub async fn mark_installment_paid(
     // Validate category exists, is active, and is expense type
     let category: Option<(String, i32)> =
         sqlx::query_as("SELECT type, is_active FROM categories WHERE id = ?")
-            .bind(&category_id)
-            .fetch_optional(pool.inner())
-            .await
-            .map_err(|e| e.to_string())?;
+            .bind(category_id)
+            .fetch_optional(pool)
+            .await?;
 
     match category {
-        None => return Err(format!("Categoria no encontrada: {category_id}")),
+        None => {
+            return Err(
+                AppError::not_found(t_with("category.not_found", &[("id", &category_id)]))
+                    .on("categoryId"),
+            )
+        }
         Some((_, is_active)) if is_active != 1 => {
-            return Err("No se puede usar una categoria inactiva".into());
+            return Err(AppError::validation(t("category.inactive")).on("categoryId"));
         }
         Some((cat_type, _)) if cat_type != "expense" => {
-            return Err("La categoria debe ser de tipo 'expense' para pagos de cuotas".into());
+            return Err(AppError::validation(t("installment.expense_category")).on("categoryId"));
         }
         _ => {}
     }
+    Ok(())
+}
+
+/// Account an installment payment comes out of: the debt's own account unless another one
+/// in the same currency is given, such as a bank account paying the card.
+async fn payment_account(
+    pool: &SqlitePool,
+    debt: &Debt,
+    source_account_id: Option<String>,
+) -> Result<String, AppError> {
+    let Some(source_id) = source_account_id.filter(|id| !id.is_empty() && *id != debt.account_id)
+    else {
+        return Ok(debt.account_id.clone());
+    };
+
+    let source = accounts::get_by_id(pool, &source_id)
+        .await?
+        .ok_or_else(|| {
+            AppError::not_found(t_with("account.not_found", &[("id", &source_id)]))
+                .on("sourceAccountId")
+        })?;
+    let account = accounts::get_by_id(pool, &debt.account_id)
+        .await?
+        .ok_or_else(|| AppError::not_found(t("account.missing")))?;
+    if source.currency_id != account.currency_id {
+        return Err(AppError::validation(t("installment.currency_mismatch")).on("sourceAccountId"));
+    }
+    Ok(source.id)
+}
+
+/// Date an installment was paid on: today unless given, never in the future.
+fn payment_date(date: Option<String>) -> Result<String, AppError> {
+    let today = chrono::Local::now().date_naive();
+    let date = match date.filter(|d| !d.is_empty()) {
+        Some(date) => parse_date(Some(&date), "field.payment_date")
+            .map_err(|e| AppError::validation(e).on("date"))?,
+        None => today,
+    };
+    if date > today {
+        return Err(AppError::validation(t("installment.future_date")).on("date"));
+    }
+    Ok(date.format("%Y-%m-%d").to_string())
+}
+
+/// Pay towards an installment, atomically creating an expense transaction on the paying
+/// account. Without a category the default debt-payment category from settings is used.
+/// Paying less than what is owed leaves the installment `partial` and still payable; once
+/// paid in full it is `paid`, or `paid_late` if completed after its due date, any excess
+/// being the late fee.
+pub async fn mark_installment_paid(
+    pool: &SqlitePool,
+    installment_id: String,
+    input: PayInstallmentInput,
+) -> Result<Installment, AppError> {
+    let category_id = payment_category(pool, input.category_id).await?;
+    let date = payment_date(input.date)?;
+
+    // Fetch installment
+    let installment = debts::get_installment_by_id(pool, &installment_id)
+        .await?
+        .ok_or_else(|| {
+            AppError::not_found(t_with("installment.not_found", &[("id", &installment_id)]))
+        })?;
+
+    if !matches!(installment.status.as_str(), "pending" | "partial") {
+        return Err(AppError::conflict(t("installment.already_paid")));
+    }
+
+    let owed = installment.amount - installment.paid_amount.unwrap_or(0);
+    let amount = input.amount.unwrap_or(owed);
+    if amount <= 0 {
+        return Err(AppError::validation(t("installment.amount_positive")).on("amount"));
+    }
+
+    // Fetch debt for account_id and description
+    let debt = debts::get_debt_by_id(pool, &installment.debt_id)
+        .await?
+        .ok_or_else(|| AppError::not_found(t("installment.debt_missing")))?;
+
+    let account_id = payment_account(pool, &debt, input.source_account_id).await?;
+    validate_payment(pool, &account_id, &category_id).await?;
 
     let txn_id = uuid::Uuid::new_v4().to_string();
-    let today = chrono::Local::now().format("%Y-%m-%d").to_string();
-    let txn_description = format!(
-        "Pago cuota {} - {}",
-        installment.installment_number, debt.description
+    let txn_description = t_with(
+        "installment.payment_description",
+        &[
+            ("number", &installment.installment_number),
+            ("description", &debt.description),
+        ],
     );
 
     // Begin atomic transaction
-    let mut db_txn = pool.begin().await.map_err(|e| e.to_string())?;
+    let mut db_txn = pool.begin().await?;
 
     // 1. Insert expense transaction
-    sqlx::query(
-        "INSERT INTO transactions (id, account_id, category_id, amount, type, description, date)
-         VALUES (?, ?, ?, ?, 'expense', ?, ?)",
+    debts::create_payment_transaction(
+        &mut db_txn,
+        &txn_id,
+        &account_id,
+        &category_id,
+        amount,
+        &txn_description,
+        &date,
     )
-    .bind(&txn_id)
-    .bind(&debt.account_id)
-    .bind(&category_id)
-    .bind(installment.amount)
-    .bind(&txn_description)
-    .bind(&today)
-    .execute(&mut *db_txn)
-    .await
-    .map_err(|e| e.to_string())?;
+    .await?;
 
-    // 2. Update installment status
-    sqlx::query(
-        "UPDATE installments SET status = 'paid', actual_payment_date = ?, transaction_id = ? WHERE id = ?",
-    )
-    .bind(&today)
-    .bind(&txn_id)
-    .bind(&installment_id)
-    .execute(&mut *db_txn)
-    .await
-    .map_err(|e| e.to_string())?;
+    // 2. Link the payment and recompute the installment from all its payments
+    debts::create_installment_payment(&mut db_txn, &installment_id, &txn_id).await?;
+    debts::refresh_installment(&mut db_txn, &installment_id).await?;
 
     // 3. Recalculate account balance
-    recalculate_account_balance(&mut db_txn, &debt.account_id).await?;
+    recalculate_account_balance(&mut db_txn, &account_id).await?;
 
     // 4. Update paid_installments counter on debts table
-    sqlx::query(
-        "UPDATE debts SET paid_installments = (SELECT COUNT(*) FROM installments WHERE debt_id = ? AND status = 'paid') WHERE id = ?",
-    )
-    .bind(&installment.debt_id)
-    .bind(&installment.debt_id)
-    .execute(&mut *db_txn)
-    .await
-    .map_err(|e| e.to_string())?;
+    debts::update_paid_installments(&mut db_txn, &installment.debt_id).await?;
+
+    // 5. Paying the last installment pays the debt off
+    if matches!(debt.status.as_str(), "active" | "defaulted")
+        && debts::count_unpaid_installments(&mut db_txn, &debt.id).await? == 0
+    {
+        transition(
+            &mut db_txn,
+            &debt,
+            "paid_off",
+            "last_installment_paid",
+            None,
+        )
+        .await?;
+    }
 
-    db_txn.commit().await.map_err(|e| e.to_string())?;
+    db_txn.commit().await?;
 
     // Fetch updated installment
-    debts::get_installment_by_id(&pool, &installment_id)
-        .await
-        .map_err(|e| e.to_string())?
-        .ok_or_else(|| "Cuota actualizada pero no encontrada".into())
+    debts::get_installment_by_id(pool, &installment_id)
+        .await?
+        .ok_or_else(|| AppError::database(t("installment.updated_missing")))
+}
+
+/// Undo an installment's payments: delete their expense transactions, set the installment back
+/// to pending and recount the debt, all in one SQL transaction. A debt closed by paying its last
+/// installment is reopened.
+pub async fn unmark_installment_paid(
+    pool: &SqlitePool,
+    installment_id: String,
+) -> Result<Installment, AppError> {
+    let installment = debts::get_installment_by_id(pool, &installment_id)
+        .await?
+        .ok_or_else(|| {
+            AppError::not_found(t_with("installment.not_found", &[("id", &installment_id)]))
+        })?;
+
+    if installment.status == "pending" {
+        return Err(AppError::conflict(t("installment.not_paid")));
+    }
+
+    let debt = debts::get_debt_by_id(pool, &installment.debt_id)
+        .await?
+        .ok_or_else(|| AppError::not_found(t("installment.debt_missing")))?;
+
+    // A payment may already be gone if it was deleted before this check existed
+    let mut payments = Vec::new();
+    for link in debts::list_installment_payments(pool, &installment.id).await? {
+        payments.extend(transactions::get_by_id(pool, &link.transaction_id).await?);
+    }
+
+    let mut db_txn = pool.begin().await?;
+    debts::reset_installment(&mut db_txn, &installment.id).await?;
+    for payment in &payments {
+        debts::delete_payment_transaction(&mut db_txn, &payment.id).await?;
+    }
+    debts::update_paid_installments(&mut db_txn, &debt.id).await?;
+    if debt.status == "paid_off" {
+        transition(&mut db_txn, &debt, "active", "payment_undone", None).await?;
+    }
+
+    let mut accounts = vec![debt.account_id.as_str()];
+    accounts.extend(payments.iter().map(|p| p.account_id.as_str()));
+    recalculate_account_balances(&mut db_txn, &accounts).await?;
+    db_txn.commit().await?;
+
+    debts::get_installment_by_id(pool, &installment_id)
+        .await?
+        .ok_or_else(|| AppError::database(t("installment.updated_missing")))
+}
+
+/// How a prepayment reschedules the rest of a debt.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PrepaymentMode {
+    ShorterTerm,
+    LowerPayment,
+}
+
+impl PrepaymentMode {
+    fn parse(value: &str) -> Result<Self, AppError> {
+        match value {
+            "shorter_term" => Ok(Self::ShorterTerm),
+            "lower_payment" => Ok(Self::LowerPayment),
+            _ => Err(AppError::validation(t_with(
+                "debt.prepayment_mode_invalid",
+                &[("value", &value)],
+            ))
+            .on("mode")),
+        }
+    }
+}
+
+/// A debt with its pending installments and the principal they still repay. Only active and
+/// defaulted debts are still being repaid, and a partly paid installment must be completed
+/// first.
+async fn outstanding_debt(
+    pool: &SqlitePool,
+    debt_id: &str,
+) -> Result<(Debt, Vec<Installment>, i64), AppError> {
+    let debt = debts::get_debt_by_id(pool, debt_id)
+        .await?
+        .ok_or_else(|| AppError::not_found(t_with("debt.not_found", &[("id", &debt_id)])))?;
+    if !matches!(debt.status.as_str(), "active" | "defaulted") {
+        return Err(AppError::conflict(t_with(
+            "debt.not_repaying",
+            &[("status", &debt.status)],
+        )));
+    }
+
+    let installments = debts::list_installments_for_debt(pool, debt_id).await?;
+    if let Some(partial) = installments.iter().find(|i| i.status == "partial") {
+        return Err(AppError::conflict(t_with(
+            "debt.partial_installment",
+            &[("number", &partial.installment_number)],
+        )));
+    }
+    let pending: Vec<Installment> = installments
+        .into_iter()
+        .filter(|i| i.status == "pending")
+        .collect();
+    if pending.is_empty() {
+        return Err(AppError::conflict(t("debt.nothing_pending")));
+    }
+
+    let principal = pending.iter().map(|i| i.principal).sum();
+    Ok((debt, pending, principal))
+}
+
+/// Prepay part of a debt's outstanding principal, recording the expense and rescheduling the
+/// pending installments at the debt's rate: either fewer installments of the same amount or
+/// the same installments for less. Everything is written in one SQL transaction.
+pub async fn prepay_debt(
+    pool: &SqlitePool,
+    input: PrepayDebtInput,
+) -> Result<DebtWithInstallments, AppError> {
+    if input.amount <= 0 {
+        return Err(AppError::validation(t("debt.prepayment_positive")).on("amount"));
+    }
+    let mode = PrepaymentMode::parse(&input.mode)?;
+    let category_id = payment_category(pool, input.category_id).await?;
+
+    let (debt, pending, outstanding) = outstanding_debt(pool, &input.debt_id).await?;
+    if input.amount >= outstanding {
+        return Err(AppError::validation(t_with(
+            "debt.prepayment_exceeds_balance",
+            &[("balance", &outstanding)],
+        ))
+        .on("amount"));
+    }
+    validate_payment(pool, &debt.account_id, &category_id).await?;
+
+    let rate = RateType::parse(&debt.rate_type)
+        .unwrap_or(RateType::Monthly)
+        .monthly_rate(debt.interest_rate);
+    let remaining = outstanding - input.amount;
+    let pending_count = u32::try_from(pending.len()).ok();
+    let (periods, payment) = match mode {
+        // Never more installments than are left, in case the last one was larger than the rest
+        PrepaymentMode::ShorterTerm => (
+            amortization::term_for_payment(remaining, rate, debt.monthly_payment)
+                .zip(pending_count)
+                .map(|(term, count)| term.min(count)),
+            debt.monthly_payment,
+        ),
+        PrepaymentMode::LowerPayment => (
+            pending_count,
+            pending_count.map_or(0, |n| amortization::level_payment(remaining, rate, n)),
+        ),
+    };
+    let schedule = periods
+        .and_then(|n| amortization::schedule(remaining, rate, n, payment))
+        .ok_or_else(|| {
+            AppError::validation(t_with(
+                "debt.payment_mismatch",
+                &[("amount", &payment), ("count", &periods.unwrap_or(0))],
+            ))
+        })?;
+    let txn_id = uuid::Uuid::new_v4().to_string();
+    let today = chrono::Local::now().format("%Y-%m-%d").to_string();
+    let txn_description = t_with(
+        "debt.prepayment_description",
+        &[("description", &debt.description)],
+    );
+    let prepayment = DebtPrepayment {
+        id: uuid::Uuid::new_v4().to_string(),
+        debt_id: debt.id.clone(),
+        transaction_id: txn_id.clone(),
+        mode: input.mode.clone(),
+        created_at: String::new(),
+    };
+
+    let mut db_txn = pool.begin().await?;
+    debts::create_payment_transaction(
+        &mut db_txn,
+        &txn_id,
+        &debt.account_id,
+        &category_id,
+        input.amount,
+        &txn_description,
+        &today,
+    )
+    .await?;
+    debts::create_prepayment(&mut db_txn, &prepayment).await?;
+    write_schedule(&mut db_txn, &debt.id, &pending, &schedule).await?;
+    recalculate_account_balance(&mut db_txn, &debt.account_id).await?;
+    db_txn.commit().await?;
+
+    get_debt_detail(pool, debt.id).await
+}
+
+/// Pay off the whole outstanding principal of a debt early. The first pending installment
+/// becomes the payoff, keeping its due date and paid today by the expense; the rest are
+/// dropped and the debt is closed. Everything is written in one SQL transaction.
+pub async fn pay_off_debt(
+    pool: &SqlitePool,
+    debt_id: String,
+    category_id: Option<String>,
+) -> Result<DebtWithInstallments, AppError> {
+    let category_id = payment_category(pool, category_id).await?;
+    let (debt, pending, outstanding) = outstanding_debt(pool, &debt_id).await?;
+    validate_payment(pool, &debt.account_id, &category_id).await?;
+    let payoff = &pending[0];
+
+    let txn_id = uuid::Uuid::new_v4().to_string();
+    let today = chrono::Local::now().format("%Y-%m-%d").to_string();
+    let txn_description = t_with(
+        "debt.payoff_description",
+        &[("description", &debt.description)],
+    );
+
+    let mut db_txn = pool.begin().await?;
+    debts::create_payment_transaction(
+        &mut db_txn,
+        &txn_id,
+        &debt.account_id,
+        &category_id,
+        outstanding,
+        &txn_description,
+        &today,
+    )
+    .await?;
+    debts::settle_installment(&mut db_txn, &payoff.id, outstanding).await?;
+    debts::create_installment_payment(&mut db_txn, &payoff.id, &txn_id).await?;
+    debts::refresh_installment(&mut db_txn, &payoff.id).await?;
+    debts::delete_pending_installments_after(&mut db_txn, &debt.id, payoff.installment_number)
+        .await?;
+    debts::update_debt_schedule(
+        &mut db_txn,
+        &debt.id,
+        payoff.installment_number,
+        debt.monthly_payment,
+    )
+    .await?;
+    transition(&mut db_txn, &debt, "paid_off", "early_payoff", None).await?;
+    recalculate_account_balance(&mut db_txn, &debt.account_id).await?;
+    db_txn.commit().await?;
+
+    get_debt_detail(pool, debt.id).await
 }
 
 /// Get credit utilization for all credit-card accounts.
-#[tauri::command]
-pub async fn get_credit_utilization(
-    pool: State<'_, SqlitePool>,
-) -> Result<Vec<CreditUtilization>, String> {
+pub async fn get_credit_utilization(pool: &SqlitePool) -> Result<Vec<CreditUtilization>, AppError> {
     // Fetch all active credit-card accounts with credit_limit
     let accounts: Vec<(String, String, i64, i64)> = sqlx::query_as(
         "SELECT id, name, balance, credit_limit
          FROM accounts
          WHERE type = 'credit_card' AND is_active = 1 AND credit_limit IS NOT NULL",
     )
-    .fetch_all(pool.inner())
-    .await
-    .map_err(|e| e.to_string())?;
+    .fetch_all(pool)
+    .await?;
 
     let mut utilizations = Vec::new();
 
     for (account_id, account_name, balance, credit_limit) in accounts {
         // Get remaining debt commitments for this account
         let remaining: (i64,) = sqlx::query_as(
-            "SELECT COALESCE(SUM(i.amount), 0)
+            "SELECT COALESCE(SUM(i.amount - COALESCE(i.paid_amount, 0)), 0)
              FROM installments i
              JOIN debts d ON i.debt_id = d.id
-             WHERE d.account_id = ? AND d.is_active = 1 AND i.status = 'pending'",
+             WHERE d.account_id = ? AND d.is_active = 1 AND i.status IN ('pending', 'partial')",
         )
         .bind(&account_id)
-        .fetch_one(pool.inner())
-        .await
-        .map_err(|e| e.to_string())?;
+        .fetch_one(pool)
+        .await?;
 
         let available_credit = credit_limit - balance;
 
@@ -464,25 +968,24 @@ pub async fn get_credit_utilization(
 }
 
 /// Get 6-month payment projections grouped by month and debt.
-#[tauri::command]
 pub async fn get_payment_projections(
-    pool: State<'_, SqlitePool>,
-) -> Result<Vec<MonthlyProjection>, String> {
-    // Query pending installments for active debts, due in next 6 months
+    pool: &SqlitePool,
+) -> Result<Vec<MonthlyProjection>, AppError> {
+    // Query what is still owed on installments of active debts, due in next 6 months
     let rows: Vec<(String, String, String, i64)> = sqlx::query_as(
-        "SELECT i.debt_id, d.description, strftime('%Y-%m', i.due_date) as month, SUM(i.amount) as total
+        "SELECT i.debt_id, d.description, strftime('%Y-%m', i.due_date) as month,
+                SUM(i.amount - COALESCE(i.paid_amount, 0)) as total
          FROM installments i
          JOIN debts d ON i.debt_id = d.id
-         WHERE i.status = 'pending'
+         WHERE i.status IN ('pending', 'partial')
            AND i.due_date >= date('now')
            AND i.due_date < date('now', '+6 months')
            AND d.is_active = 1
          GROUP BY i.debt_id, d.description, month
          ORDER BY month, d.description",
     )
-    .fetch_all(pool.inner())
-    .await
-    .map_err(|e| e.to_string())?;
+    .fetch_all(pool)
+    .await?;
 
     // Group by month
     let mut projections: Vec<MonthlyProjection> = Vec::new();
@@ -508,3 +1011,119 @@ pub async fn get_payment_projections(
 
     Ok(projections)
 }
+
+/// Tauri handlers. Each forwards to the function of the same name above with the current pool.
+pub mod ipc {
+    use tauri::State;
+
+    use super::{
+        AppError, CreateDebtInput, CreditUtilization, Debt, DebtFilter, DebtStatusChange,
+        DebtWithInstallments, Installment, MonthlyProjection, PayInstallmentInput, PrepayDebtInput,
+        UpdateDebtInput,
+    };
+    use crate::db::Database;
+
+    #[tauri::command]
+    pub async fn create_debt(
+        db: State<'_, Database>,
+        input: CreateDebtInput,
+    ) -> Result<DebtWithInstallments, AppError> {
+        super::create_debt(&db.pool(), input).await
+    }
+
+    #[tauri::command]
+    pub async fn update_debt(
+        db: State<'_, Database>,
+        id: String,
+        input: UpdateDebtInput,
+    ) -> Result<Debt, AppError> {
+        super::update_debt(&db.pool(), id, input).await
+    }
+
+    #[tauri::command]
+    pub async fn delete_debt(db: State<'_, Database>, id: String) -> Result<(), AppError> {
+        super::delete_debt(&db.pool(), id).await
+    }
+
+    #[tauri::command]
+    pub async fn list_debts(
+        db: State<'_, Database>,
+        filter: DebtFilter,
+    ) -> Result<Vec<Debt>, AppError> {
+        super::list_debts(&db.pool(), filter).await
+    }
+
+    #[tauri::command]
+    pub async fn change_debt_status(
+        db: State<'_, Database>,
+        id: String,
+        status: String,
+        note: Option<String>,
+    ) -> Result<Debt, AppError> {
+        super::change_debt_status(&db.pool(), id, status, note).await
+    }
+
+    #[tauri::command]
+    pub async fn list_debt_status_history(
+        db: State<'_, Database>,
+        id: String,
+    ) -> Result<Vec<DebtStatusChange>, AppError> {
+        super::list_debt_status_history(&db.pool(), id).await
+    }
+
+    #[tauri::command]
+    pub async fn get_debt_detail(
+        db: State<'_, Database>,
+        id: String,
+    ) -> Result<DebtWithInstallments, AppError> {
+        super::get_debt_detail(&db.pool(), id).await
+    }
+
+    #[tauri::command]
+    pub async fn mark_installment_paid(
+        db: State<'_, Database>,
+        installment_id: String,
+        input: PayInstallmentInput,
+    ) -> Result<Installment, AppError> {
+        super::mark_installment_paid(&db.pool(), installment_id, input).await
+    }
+
+    #[tauri::command]
+    pub async fn unmark_installment_paid(
+        db: State<'_, Database>,
+        installment_id: String,
+    ) -> Result<Installment, AppError> {
+        super::unmark_installment_paid(&db.pool(), installment_id).await
+    }
+
+    #[tauri::command]
+    pub async fn prepay_debt(
+        db: State<'_, Database>,
+        input: PrepayDebtInput,
+    ) -> Result<DebtWithInstallments, AppError> {
+        super::prepay_debt(&db.pool(), input).await
+    }
+
+    #[tauri::command]
+    pub async fn pay_off_debt(
+        db: State<'_, Database>,
+        debt_id: String,
+        category_id: Option<String>,
+    ) -> Result<DebtWithInstallments, AppError> {
+        super::pay_off_debt(&db.pool(), debt_id, category_id).await
+    }
+
+    #[tauri::command]
+    pub async fn get_credit_utilization(
+        db: State<'_, Database>,
+    ) -> Result<Vec<CreditUtilization>, AppError> {
+        super::get_credit_utilization(&db.pool()).await
+    }
+
+    #[tauri::command]
+    pub async fn get_payment_projections(
+        db: State<'_, Database>,
+    ) -> Result<Vec<MonthlyProjection>, AppError> {
+        super::get_payment_projections(&db.pool()).await
+    }
+}
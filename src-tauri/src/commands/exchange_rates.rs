@@ -0,0 +1,191 @@
+use chrono::NaiveDate;
+use sqlx::SqlitePool;
+
+use crate::db::models::{ConvertedAmount, ExchangeRate};
+use crate::db::queries::exchange_rates;
+use crate::services::i18n::{t, t_with};
+
+/// Adjust a major-unit rate so it can be applied directly to amounts stored in minor units.
+/// e.g. USD (2 decimals) -> CLP (0 decimals) at 950.0 becomes 9.5 per cent.
+pub(crate) fn scale_rate(rate: f64, from_decimal_places: i32, to_decimal_places: i32) -> f64 {
+    rate * 10f64.powi(to_decimal_places - from_decimal_places)
+}
+
+/// Apply a minor-unit rate to an amount, rounding to the nearest minor unit.
+pub(crate) fn apply_rate(amount: i64, scaled_rate: f64) -> i64 {
+    #[allow(clippy::cast_precision_loss, clippy::cast_possible_truncation)]
+    let converted = (amount as f64 * scaled_rate).round() as i64;
+    converted
+}
+
+/// Validate that both currencies exist and return their decimal places.
+async fn currency_decimal_places(
+    pool: &SqlitePool,
+    from_currency_id: &str,
+    to_currency_id: &str,
+) -> Result<(i32, i32), String> {
+    let from_places = exchange_rates::get_decimal_places(pool, from_currency_id)
+        .await
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| t_with("currency.not_found", &[("id", &from_currency_id)]))?;
+    let to_places = exchange_rates::get_decimal_places(pool, to_currency_id)
+        .await
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| t_with("currency.not_found", &[("id", &to_currency_id)]))?;
+    Ok((from_places, to_places))
+}
+
+/// Create or update the rate for a currency pair on a given date (upsert on from/to/date).
+pub async fn set_exchange_rate(
+    pool: &SqlitePool,
+    from_currency_id: String,
+    to_currency_id: String,
+    rate: f64,
+    date: String,
+) -> Result<ExchangeRate, String> {
+    if from_currency_id == to_currency_id {
+        return Err(t("exchange_rate.same_currency"));
+    }
+    if !rate.is_finite() || rate <= 0.0 {
+        return Err(t("exchange_rate.rate_positive"));
+    }
+    NaiveDate::parse_from_str(&date, "%Y-%m-%d")
+        .map_err(|e| t_with("validation.invalid_date", &[("error", &e)]))?;
+
+    currency_decimal_places(pool, &from_currency_id, &to_currency_id).await?;
+
+    let id = uuid::Uuid::new_v4().to_string();
+    exchange_rates::upsert(pool, &id, &from_currency_id, &to_currency_id, rate, &date)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Get the most recent rate for a currency pair, if any.
+pub async fn get_exchange_rate(
+    pool: &SqlitePool,
+    from_currency_id: String,
+    to_currency_id: String,
+) -> Result<Option<ExchangeRate>, String> {
+    exchange_rates::get_latest(pool, &from_currency_id, &to_currency_id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// List the rate history for a currency pair, newest first.
+pub async fn list_exchange_rates(
+    pool: &SqlitePool,
+    from_currency_id: String,
+    to_currency_id: String,
+) -> Result<Vec<ExchangeRate>, String> {
+    exchange_rates::list_for_pair(pool, &from_currency_id, &to_currency_id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Delete a single exchange rate entry.
+pub async fn delete_exchange_rate(pool: &SqlitePool, id: String) -> Result<(), String> {
+    exchange_rates::get_by_id(pool, &id)
+        .await
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| t_with("exchange_rate.not_found", &[("id", &id)]))?;
+
+    exchange_rates::delete(pool, &id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Convert an amount (minor units) between currencies using the latest available rate.
+/// Falls back to the inverse of the opposite pair. Errors if neither exists.
+pub async fn convert_amount(
+    pool: &SqlitePool,
+    amount: i64,
+    from_currency_id: String,
+    to_currency_id: String,
+) -> Result<ConvertedAmount, String> {
+    let (from_places, to_places) =
+        currency_decimal_places(pool, &from_currency_id, &to_currency_id).await?;
+
+    let (rate, rate_date) = if from_currency_id == to_currency_id {
+        (1.0, None)
+    } else if let Some(direct) =
+        exchange_rates::get_latest(pool, &from_currency_id, &to_currency_id)
+            .await
+            .map_err(|e| e.to_string())?
+    {
+        (direct.rate, Some(direct.date))
+    } else if let Some(inverse) =
+        exchange_rates::get_latest(pool, &to_currency_id, &from_currency_id)
+            .await
+            .map_err(|e| e.to_string())?
+    {
+        (1.0 / inverse.rate, Some(inverse.date))
+    } else {
+        return Err(t_with(
+            "exchange_rate.missing",
+            &[("from", &from_currency_id), ("to", &to_currency_id)],
+        ));
+    };
+
+    let converted_amount = apply_rate(amount, scale_rate(rate, from_places, to_places));
+
+    Ok(ConvertedAmount {
+        amount,
+        from_currency_id,
+        to_currency_id,
+        converted_amount,
+        rate,
+        rate_date,
+    })
+}
+
+/// Tauri handlers. Each forwards to the function of the same name above with the current pool.
+pub mod ipc {
+    use tauri::State;
+
+    use super::{ConvertedAmount, ExchangeRate};
+    use crate::db::Database;
+
+    #[tauri::command]
+    pub async fn set_exchange_rate(
+        db: State<'_, Database>,
+        from_currency_id: String,
+        to_currency_id: String,
+        rate: f64,
+        date: String,
+    ) -> Result<ExchangeRate, String> {
+        super::set_exchange_rate(&db.pool(), from_currency_id, to_currency_id, rate, date).await
+    }
+
+    #[tauri::command]
+    pub async fn get_exchange_rate(
+        db: State<'_, Database>,
+        from_currency_id: String,
+        to_currency_id: String,
+    ) -> Result<Option<ExchangeRate>, String> {
+        super::get_exchange_rate(&db.pool(), from_currency_id, to_currency_id).await
+    }
+
+    #[tauri::command]
+    pub async fn list_exchange_rates(
+        db: State<'_, Database>,
+        from_currency_id: String,
+        to_currency_id: String,
+    ) -> Result<Vec<ExchangeRate>, String> {
+        super::list_exchange_rates(&db.pool(), from_currency_id, to_currency_id).await
+    }
+
+    #[tauri::command]
+    pub async fn delete_exchange_rate(db: State<'_, Database>, id: String) -> Result<(), String> {
+        super::delete_exchange_rate(&db.pool(), id).await
+    }
+
+    #[tauri::command]
+    pub async fn convert_amount(
+        db: State<'_, Database>,
+        amount: i64,
+        from_currency_id: String,
+        to_currency_id: String,
+    ) -> Result<ConvertedAmount, String> {
+        super::convert_amount(&db.pool(), amount, from_currency_id, to_currency_id).await
+    }
+}
@@ -0,0 +1,60 @@
+use sqlx::SqlitePool;
+
+use crate::db::models::{ExportDocument, ExportSummary, ImportSummary};
+use crate::services::export;
+use crate::services::i18n::t_with;
+
+/// Export every table to a versioned JSON document at `path`.
+pub async fn export_data(pool: &SqlitePool, path: String) -> Result<ExportSummary, String> {
+    let doc = export::build_document(pool).await?;
+
+    let json = serde_json::to_string_pretty(&doc).map_err(|e| e.to_string())?;
+    std::fs::write(&path, json).map_err(|e| t_with("file.write_failed", &[("error", &e)]))?;
+
+    Ok(ExportSummary {
+        path,
+        schema_version: doc.schema_version,
+        exported_at: doc.exported_at,
+        counts: doc.counts,
+    })
+}
+
+/// Import a JSON document produced by `export_data`.
+/// `mode` is `merge` (keep existing rows, add missing IDs) or `replace` (wipe first).
+pub async fn import_data(
+    pool: &SqlitePool,
+    path: String,
+    mode: String,
+) -> Result<ImportSummary, String> {
+    let json =
+        std::fs::read_to_string(&path).map_err(|e| t_with("file.read_failed", &[("error", &e)]))?;
+    let doc: ExportDocument = serde_json::from_str(&json)
+        .map_err(|e| t_with("export.invalid_backup", &[("error", &e)]))?;
+
+    export::import_document(pool, &doc, &mode).await
+}
+
+/// Tauri handlers. Each forwards to the function of the same name above with the current pool.
+pub mod ipc {
+    use tauri::State;
+
+    use super::{ExportSummary, ImportSummary};
+    use crate::db::Database;
+
+    #[tauri::command]
+    pub async fn export_data(
+        db: State<'_, Database>,
+        path: String,
+    ) -> Result<ExportSummary, String> {
+        super::export_data(&db.pool(), path).await
+    }
+
+    #[tauri::command]
+    pub async fn import_data(
+        db: State<'_, Database>,
+        path: String,
+        mode: String,
+    ) -> Result<ImportSummary, String> {
+        super::import_data(&db.pool(), path, mode).await
+    }
+}
@@ -0,0 +1,82 @@
+use sqlx::{Sqlite, SqlitePool};
+
+use crate::commands::debts::transition;
+use crate::commands::transactions::recalculate_account_balances;
+use crate::db::models::IntegrityReport;
+use crate::db::queries::{debts, integrity};
+use crate::error::AppError;
+
+/// Collect every inconsistency in the denormalized data, inside the caller's SQL transaction
+/// so all checks see the same state.
+async fn inspect(db_txn: &mut sqlx::Transaction<'_, Sqlite>) -> Result<IntegrityReport, AppError> {
+    Ok(IntegrityReport {
+        balance_drift: integrity::balance_drift(db_txn).await?,
+        paid_installments_mismatches: integrity::paid_installments_mismatches(db_txn).await?,
+        dangling_installments: integrity::dangling_installments(db_txn).await?,
+        orphaned_tag_links: integrity::orphaned_tag_links(db_txn).await?,
+        repaired: false,
+    })
+}
+
+/// Recompute every account balance and debt counter and report whatever disagrees with the
+/// stored values, along with installments and tag links pointing at deleted records.
+/// Nothing is changed.
+pub async fn verify_integrity(pool: &SqlitePool) -> Result<IntegrityReport, AppError> {
+    let mut db_txn = pool.begin().await?;
+    let report = inspect(&mut db_txn).await?;
+    db_txn.rollback().await?;
+    Ok(report)
+}
+
+/// Fix everything `verify_integrity` reports, atomically, and return what was found.
+/// Payments whose transaction was deleted are dropped and their installments recomputed from
+/// the payments left, reopening the debts they had paid off, debt counters are recounted, orphaned tag links deleted and drifted
+/// balances recomputed.
+pub async fn repair_integrity(pool: &SqlitePool) -> Result<IntegrityReport, AppError> {
+    let mut db_txn = pool.begin().await?;
+    let mut report = inspect(&mut db_txn).await?;
+
+    if !report.dangling_installments.is_empty() {
+        integrity::delete_dangling_installment_payments(&mut db_txn).await?;
+        for dangling in &report.dangling_installments {
+            debts::refresh_installment(&mut db_txn, &dangling.installment_id).await?;
+        }
+        for debt in integrity::paid_off_debts_with_unpaid_installments(&mut db_txn).await? {
+            transition(&mut db_txn, &debt, "active", "payment_undone", None).await?;
+        }
+    }
+    // Recomputing installments changes the paid counts, so the counters are always recounted
+    integrity::recount_paid_installments(&mut db_txn).await?;
+    if !report.orphaned_tag_links.is_empty() {
+        integrity::delete_orphaned_tag_links(&mut db_txn).await?;
+    }
+
+    let drifted: Vec<&str> = report
+        .balance_drift
+        .iter()
+        .map(|d| d.account_id.as_str())
+        .collect();
+    recalculate_account_balances(&mut db_txn, &drifted).await?;
+
+    db_txn.commit().await?;
+    report.repaired = true;
+    Ok(report)
+}
+
+/// Tauri handlers. Each forwards to the function of the same name above with the current pool.
+pub mod ipc {
+    use tauri::State;
+
+    use super::{AppError, IntegrityReport};
+    use crate::db::Database;
+
+    #[tauri::command]
+    pub async fn verify_integrity(db: State<'_, Database>) -> Result<IntegrityReport, AppError> {
+        super::verify_integrity(&db.pool()).await
+    }
+
+    #[tauri::command]
+    pub async fn repair_integrity(db: State<'_, Database>) -> Result<IntegrityReport, AppError> {
+        super::repair_integrity(&db.pool()).await
+    }
+}
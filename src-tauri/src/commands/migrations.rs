@@ -0,0 +1,45 @@
+use sqlx::SqlitePool;
+
+use crate::db;
+use crate::error::AppError;
+use crate::services::i18n::t;
+
+/// Names of the migrations not yet applied, in the order they would run. Nothing is changed.
+pub async fn dry_run_migrations(pool: &SqlitePool) -> Result<Vec<String>, AppError> {
+    let pending = db::dry_run_migrations(pool)
+        .await
+        .map_err(|e| AppError::database(e.to_string()))?;
+    Ok(pending.into_iter().map(str::to_string).collect())
+}
+
+/// Undo the most recently applied migration and return its name, or `None` when there is
+/// none. Rolling back usually drops data, so only development builds allow it.
+pub async fn rollback_last_migration(pool: &SqlitePool) -> Result<Option<String>, AppError> {
+    if !cfg!(debug_assertions) {
+        return Err(AppError::conflict(t("migration.rollback_release")));
+    }
+    let reverted = db::rollback_last_migration(pool)
+        .await
+        .map_err(|e| AppError::database(e.to_string()))?;
+    Ok(reverted.map(str::to_string))
+}
+
+/// Tauri handlers. Each forwards to the function of the same name above with the current pool.
+pub mod ipc {
+    use tauri::State;
+
+    use super::AppError;
+    use crate::db::Database;
+
+    #[tauri::command]
+    pub async fn dry_run_migrations(db: State<'_, Database>) -> Result<Vec<String>, AppError> {
+        super::dry_run_migrations(&db.pool()).await
+    }
+
+    #[tauri::command]
+    pub async fn rollback_last_migration(
+        db: State<'_, Database>,
+    ) -> Result<Option<String>, AppError> {
+        super::rollback_last_migration(&db.pool()).await
+    }
+}
@@ -1,6 +1,20 @@
 // Commands module - Tauri IPC handlers
 pub mod accounts;
+pub mod backups;
+pub mod balance_history;
+pub mod budgets;
 pub mod categories;
+pub mod csv_import;
 pub mod dashboard;
 pub mod debts;
+pub mod exchange_rates;
+pub mod export;
+pub mod integrity;
+pub mod migrations;
+pub mod ofx_import;
+pub mod recurring;
+pub mod settings;
+pub mod statements;
+pub mod tags;
 pub mod transactions;
+pub mod transfers;
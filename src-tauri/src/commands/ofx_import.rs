@@ -0,0 +1,230 @@
+use std::collections::HashSet;
+
+use sqlx::SqlitePool;
+
+use crate::commands::transactions::{
+    insert_transaction, recalculate_account_balance, validate_new_transaction, NewTransaction,
+};
+use crate::db::models::{OfxImportPreview, OfxImportResult, OfxPreviewRow};
+use crate::db::queries::transactions;
+use crate::services::csv_import::{decode_content, parse_amount};
+use crate::services::i18n;
+use crate::services::ofx::{parse_ofx, OfxStatement, OfxTransaction};
+
+/// Read and parse the OFX/QFX file at `path`.
+fn read_statement(path: &str) -> Result<OfxStatement, String> {
+    let bytes =
+        std::fs::read(path).map_err(|e| i18n::t_with("file.read_failed", &[("error", &e)]))?;
+    parse_ofx(&decode_content(bytes))
+}
+
+/// Load the account's currency code and decimal places, rejecting archived accounts and
+/// statements declared in a different currency.
+async fn account_currency(
+    pool: &SqlitePool,
+    account_id: &str,
+    statement: &OfxStatement,
+) -> Result<u32, String> {
+    let row: Option<(i32, String, i32)> = sqlx::query_as(
+        "SELECT a.is_active, c.code, c.decimal_places
+         FROM accounts a
+         JOIN currencies c ON a.currency_id = c.id
+         WHERE a.id = ?",
+    )
+    .bind(account_id)
+    .fetch_optional(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    let (is_active, code, decimal_places) =
+        row.ok_or_else(|| i18n::t_with("account.not_found", &[("id", &account_id)]))?;
+    if is_active != 1 {
+        return Err(i18n::t("import.archived_account"));
+    }
+    if let Some(currency) = &statement.currency {
+        if !currency.eq_ignore_ascii_case(&code) {
+            return Err(i18n::t_with(
+                "ofx.currency_mismatch",
+                &[("statement", currency), ("account", &code)],
+            ));
+        }
+    }
+    u32::try_from(decimal_places).map_err(|e| e.to_string())
+}
+
+/// Convert a statement line to an absolute amount and a transaction type.
+fn amount_and_type(
+    transaction: &OfxTransaction,
+    decimal_places: u32,
+) -> Result<(i64, &'static str), String> {
+    let signed = parse_amount(
+        &transaction.amount,
+        transaction.decimal_separator(),
+        decimal_places,
+    )?;
+    if signed == 0 {
+        return Err(i18n::t("ofx.zero_amount"));
+    }
+    let transaction_type = if signed < 0 { "expense" } else { "income" };
+    Ok((signed.abs(), transaction_type))
+}
+
+/// Parse the OFX/QFX file at `path` and return the lines that would be imported into the
+/// account. Lines whose FITID is already on the account are flagged as duplicates.
+pub async fn preview_ofx_import(
+    pool: &SqlitePool,
+    account_id: String,
+    path: String,
+) -> Result<OfxImportPreview, String> {
+    let statement = read_statement(&path)?;
+    let decimal_places = account_currency(pool, &account_id, &statement).await?;
+
+    let existing: HashSet<String> = transactions::get_fitids(pool, &account_id)
+        .await
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .collect();
+
+    let rows: Vec<OfxPreviewRow> = statement
+        .transactions
+        .iter()
+        .map(|t| {
+            let parsed = amount_and_type(t, decimal_places);
+            OfxPreviewRow {
+                fitid: t.fitid.clone(),
+                date: t.date.clone(),
+                amount: parsed.as_ref().ok().map(|(amount, _)| *amount),
+                transaction_type: parsed.as_ref().ok().map(|(_, tt)| (*tt).to_string()),
+                description: t.description(),
+                is_duplicate: existing.contains(&t.fitid),
+                error: parsed.err(),
+            }
+        })
+        .collect();
+
+    #[allow(clippy::cast_possible_wrap)]
+    let count = |pred: fn(&OfxPreviewRow) -> bool| rows.iter().filter(|r| pred(r)).count() as i64;
+
+    Ok(OfxImportPreview {
+        account_id,
+        currency: statement.currency,
+        account_number: statement.account_number,
+        new_count: count(|r| r.error.is_none() && !r.is_duplicate),
+        duplicate_count: count(|r| r.is_duplicate),
+        error_count: count(|r| r.error.is_some()),
+        rows,
+    })
+}
+
+/// Import every line of an OFX/QFX file into the account in a single SQL transaction.
+/// Each transaction keeps its FITID, so importing the same file again skips the lines
+/// that are already present instead of duplicating them.
+pub async fn import_ofx(
+    pool: &SqlitePool,
+    account_id: String,
+    path: String,
+    expense_category_id: String,
+    income_category_id: String,
+) -> Result<OfxImportResult, String> {
+    let statement = read_statement(&path)?;
+    if statement.transactions.is_empty() {
+        return Err(i18n::t("ofx.empty"));
+    }
+    let decimal_places = account_currency(pool, &account_id, &statement).await?;
+
+    // Validate every line up front so nothing is written when one is wrong
+    let mut lines = Vec::with_capacity(statement.transactions.len());
+    for t in &statement.transactions {
+        let (amount, transaction_type) = amount_and_type(t, decimal_places).map_err(|e| {
+            i18n::t_with(
+                "ofx.transaction_error",
+                &[("fitid", &t.fitid), ("error", &e)],
+            )
+        })?;
+        let category_id = if transaction_type == "expense" {
+            &expense_category_id
+        } else {
+            &income_category_id
+        };
+        lines.push((t, amount, transaction_type, category_id, t.description()));
+    }
+
+    let mut new_transactions = Vec::with_capacity(lines.len());
+    for (t, amount, transaction_type, category_id, description) in &lines {
+        let new = NewTransaction {
+            account_id: &account_id,
+            category_id,
+            amount: *amount,
+            transaction_type,
+            description,
+            date: &t.date,
+            fitid: Some(&t.fitid),
+        };
+        validate_new_transaction(pool, &new).await?;
+        new_transactions.push(new);
+    }
+
+    let mut db_txn = pool.begin().await.map_err(|e| e.to_string())?;
+
+    let mut inserted_count: i64 = 0;
+    for new in &new_transactions {
+        if insert_transaction(&mut db_txn, new).await?.is_some() {
+            inserted_count += 1;
+        }
+    }
+
+    recalculate_account_balance(&mut db_txn, &account_id).await?;
+
+    let (balance,): (i64,) = sqlx::query_as("SELECT balance FROM accounts WHERE id = ?")
+        .bind(&account_id)
+        .fetch_one(&mut *db_txn)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    db_txn.commit().await.map_err(|e| e.to_string())?;
+
+    #[allow(clippy::cast_possible_wrap)]
+    let skipped_count = new_transactions.len() as i64 - inserted_count;
+
+    Ok(OfxImportResult {
+        account_id,
+        inserted_count,
+        skipped_count,
+        balance,
+    })
+}
+
+/// Tauri handlers. Each forwards to the function of the same name above with the current pool.
+pub mod ipc {
+    use tauri::State;
+
+    use super::{OfxImportPreview, OfxImportResult};
+    use crate::db::Database;
+
+    #[tauri::command]
+    pub async fn preview_ofx_import(
+        db: State<'_, Database>,
+        account_id: String,
+        path: String,
+    ) -> Result<OfxImportPreview, String> {
+        super::preview_ofx_import(&db.pool(), account_id, path).await
+    }
+
+    #[tauri::command]
+    pub async fn import_ofx(
+        db: State<'_, Database>,
+        account_id: String,
+        path: String,
+        expense_category_id: String,
+        income_category_id: String,
+    ) -> Result<OfxImportResult, String> {
+        super::import_ofx(
+            &db.pool(),
+            account_id,
+            path,
+            expense_category_id,
+            income_category_id,
+        )
+        .await
+    }
+}
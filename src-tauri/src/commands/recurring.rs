@@ -0,0 +1,284 @@
+use chrono::{Datelike, NaiveDate};
+use sqlx::SqlitePool;
+
+use crate::db::models::{
+    CreateRecurringInput, RecurringRunSummary, RecurringTransaction, UpdateRecurringInput,
+};
+use crate::db::queries::recurring;
+use crate::services::i18n::{t, t_with};
+use crate::services::recurring::{process_due, FREQUENCIES};
+
+/// Parse a YYYY-MM-DD date, with a field-specific error message. `field` is the catalog key
+/// of the field's name.
+fn parse_date(value: &str, field: &str) -> Result<NaiveDate, String> {
+    NaiveDate::parse_from_str(value, "%Y-%m-%d").map_err(|e| {
+        t_with(
+            "validation.field_invalid",
+            &[("field", &t(field)), ("error", &e)],
+        )
+    })
+}
+
+/// Validate that the account is active and the category is active and matches the type.
+async fn validate_account_and_category(
+    pool: &SqlitePool,
+    account_id: &str,
+    category_id: &str,
+    transaction_type: &str,
+) -> Result<(), String> {
+    let account: Option<(i32,)> = sqlx::query_as("SELECT is_active FROM accounts WHERE id = ?")
+        .bind(account_id)
+        .fetch_optional(pool)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    match account {
+        None => return Err(t_with("account.not_found", &[("id", &account_id)])),
+        Some((is_active,)) if is_active != 1 => {
+            return Err(t("account.archived"));
+        }
+        _ => {}
+    }
+
+    let category: Option<(String, i32)> =
+        sqlx::query_as("SELECT type, is_active FROM categories WHERE id = ?")
+            .bind(category_id)
+            .fetch_optional(pool)
+            .await
+            .map_err(|e| e.to_string())?;
+
+    match category {
+        None => Err(t_with("category.not_found", &[("id", &category_id)])),
+        Some((_, is_active)) if is_active != 1 => Err(t("category.inactive")),
+        Some((cat_type, _)) if cat_type != transaction_type => Err(t_with(
+            "category.type_mismatch",
+            &[
+                ("transaction_type", &transaction_type),
+                ("category_type", &cat_type),
+            ],
+        )),
+        _ => Ok(()),
+    }
+}
+
+/// List recurring rules. Inactive rules are included only when requested.
+pub async fn list_recurring(
+    pool: &SqlitePool,
+    include_inactive: Option<bool>,
+) -> Result<Vec<RecurringTransaction>, String> {
+    recurring::get_all(pool, include_inactive.unwrap_or(false))
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Create a recurring rule. The first occurrence is generated on `start_date`.
+pub async fn create_recurring(
+    pool: &SqlitePool,
+    input: CreateRecurringInput,
+) -> Result<RecurringTransaction, String> {
+    if input.amount <= 0 {
+        return Err(t("validation.amount_positive"));
+    }
+    if input.description.trim().is_empty() {
+        return Err(t("validation.description_required"));
+    }
+    if input.transaction_type != "income" && input.transaction_type != "expense" {
+        return Err(t("validation.transaction_type"));
+    }
+    if !FREQUENCIES.contains(&input.frequency.as_str()) {
+        return Err(t_with(
+            "recurring.invalid_frequency",
+            &[("frequency", &input.frequency)],
+        ));
+    }
+
+    let start = parse_date(&input.start_date, "field.start_date")?;
+    if let Some(ref end_date) = input.end_date {
+        if parse_date(end_date, "field.end_date")? < start {
+            return Err(t("validation.end_before_start"));
+        }
+    }
+
+    validate_account_and_category(
+        pool,
+        &input.account_id,
+        &input.category_id,
+        &input.transaction_type,
+    )
+    .await?;
+
+    #[allow(clippy::cast_possible_wrap)]
+    let anchor_day = start.day() as i32;
+
+    let rule = RecurringTransaction {
+        id: uuid::Uuid::new_v4().to_string(),
+        account_id: input.account_id,
+        category_id: input.category_id,
+        amount: input.amount,
+        transaction_type: input.transaction_type,
+        description: input.description.trim().to_string(),
+        frequency: input.frequency,
+        next_date: input.start_date,
+        anchor_day,
+        end_date: input.end_date,
+        is_active: 1,
+        created_at: String::new(), // Will be set by DB default
+    };
+
+    recurring::create(pool, &rule)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Update or deactivate a recurring rule. The transaction type is locked after creation.
+pub async fn update_recurring(
+    pool: &SqlitePool,
+    id: String,
+    input: UpdateRecurringInput,
+) -> Result<RecurringTransaction, String> {
+    let mut rule = recurring::get_by_id(pool, &id)
+        .await
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| t_with("recurring.not_found", &[("id", &id)]))?;
+
+    if let Some(amount) = input.amount {
+        if amount <= 0 {
+            return Err(t("validation.amount_positive"));
+        }
+        rule.amount = amount;
+    }
+    if let Some(description) = input.description {
+        if description.trim().is_empty() {
+            return Err(t("validation.description_required"));
+        }
+        rule.description = description.trim().to_string();
+    }
+    if let Some(frequency) = input.frequency {
+        if !FREQUENCIES.contains(&frequency.as_str()) {
+            return Err(t_with(
+                "recurring.invalid_frequency",
+                &[("frequency", &frequency)],
+            ));
+        }
+        rule.frequency = frequency;
+    }
+    if let Some(next_date) = input.next_date {
+        let next = parse_date(&next_date, "field.next_date")?;
+        #[allow(clippy::cast_possible_wrap)]
+        let anchor_day = next.day() as i32;
+        rule.anchor_day = anchor_day;
+        rule.next_date = next_date;
+    }
+    if let Some(end_date) = input.end_date {
+        // An empty string clears the end date
+        rule.end_date = if end_date.is_empty() {
+            None
+        } else {
+            parse_date(&end_date, "field.end_date")?;
+            Some(end_date)
+        };
+    }
+    if let Some(ref end_date) = rule.end_date {
+        if end_date.as_str() < rule.next_date.as_str() {
+            return Err(t("validation.end_before_next"));
+        }
+    }
+    if let Some(is_active) = input.is_active {
+        rule.is_active = i32::from(is_active);
+    }
+
+    let account_or_category_changed = input.account_id.is_some() || input.category_id.is_some();
+    if let Some(account_id) = input.account_id {
+        rule.account_id = account_id;
+    }
+    if let Some(category_id) = input.category_id {
+        rule.category_id = category_id;
+    }
+    if account_or_category_changed {
+        validate_account_and_category(
+            pool,
+            &rule.account_id,
+            &rule.category_id,
+            &rule.transaction_type,
+        )
+        .await?;
+    }
+
+    recurring::update(pool, &rule)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Delete a recurring rule. Transactions it already generated are kept.
+pub async fn delete_recurring(pool: &SqlitePool, id: String) -> Result<(), String> {
+    recurring::get_by_id(pool, &id)
+        .await
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| t_with("recurring.not_found", &[("id", &id)]))?;
+
+    recurring::delete(pool, &id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Generate every due recurring transaction now. Safe to call repeatedly.
+pub async fn process_recurring(pool: &SqlitePool) -> Result<RecurringRunSummary, String> {
+    process_due(pool, chrono::Local::now().date_naive()).await
+}
+
+/// Tauri handlers. Each forwards to the function of the same name above with the current pool,
+/// except `take_startup_recurring_summary`, which only reads app state.
+pub mod ipc {
+    use tauri::State;
+
+    use super::{
+        CreateRecurringInput, RecurringRunSummary, RecurringTransaction, UpdateRecurringInput,
+    };
+    use crate::db::Database;
+    use crate::services::recurring::StartupRecurringSummary;
+
+    #[tauri::command]
+    pub async fn list_recurring(
+        db: State<'_, Database>,
+        include_inactive: Option<bool>,
+    ) -> Result<Vec<RecurringTransaction>, String> {
+        super::list_recurring(&db.pool(), include_inactive).await
+    }
+
+    #[tauri::command]
+    pub async fn create_recurring(
+        db: State<'_, Database>,
+        input: CreateRecurringInput,
+    ) -> Result<RecurringTransaction, String> {
+        super::create_recurring(&db.pool(), input).await
+    }
+
+    #[tauri::command]
+    pub async fn update_recurring(
+        db: State<'_, Database>,
+        id: String,
+        input: UpdateRecurringInput,
+    ) -> Result<RecurringTransaction, String> {
+        super::update_recurring(&db.pool(), id, input).await
+    }
+
+    #[tauri::command]
+    pub async fn delete_recurring(db: State<'_, Database>, id: String) -> Result<(), String> {
+        super::delete_recurring(&db.pool(), id).await
+    }
+
+    #[tauri::command]
+    pub async fn process_recurring(db: State<'_, Database>) -> Result<RecurringRunSummary, String> {
+        super::process_recurring(&db.pool()).await
+    }
+
+    /// Return the summary of the run performed at startup, once. Later calls return `None`.
+    #[tauri::command]
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn take_startup_recurring_summary(
+        summary: State<'_, StartupRecurringSummary>,
+    ) -> Result<Option<RecurringRunSummary>, String> {
+        let mut guard = summary.inner().0.lock().map_err(|e| e.to_string())?;
+        Ok(guard.take())
+    }
+}
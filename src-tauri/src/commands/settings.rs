@@ -0,0 +1,146 @@
+use sqlx::SqlitePool;
+
+use crate::db::models::{AppSettings, UpdateSettingsInput};
+use crate::db::queries::{accounts, categories, exchange_rates, settings};
+use crate::error::AppError;
+use crate::services::i18n::{self, t, t_with, Locale};
+use crate::services::settings::{self as app_settings, parse_weekday, WEEKDAYS};
+
+/// Apply the saved locale. Called at startup before any command runs.
+pub async fn restore_locale(pool: &SqlitePool) -> Result<(), sqlx::Error> {
+    i18n::set_current(app_settings::locale(pool).await?);
+    Ok(())
+}
+
+/// Non-empty value of an optional input field. Empty strings reset a setting instead.
+fn provided(value: Option<&String>) -> Option<&str> {
+    value.map(String::as_str).filter(|v| !v.is_empty())
+}
+
+/// Validate every provided setting before anything is saved.
+async fn validate_settings(pool: &SqlitePool, input: &UpdateSettingsInput) -> Result<(), AppError> {
+    if let Some(currency_id) = provided(input.base_currency_id.as_ref()) {
+        if exchange_rates::get_decimal_places(pool, currency_id)
+            .await?
+            .is_none()
+        {
+            return Err(
+                AppError::not_found(t_with("currency.not_found", &[("id", &currency_id)]))
+                    .on("baseCurrencyId"),
+            );
+        }
+    }
+
+    if let Some(locale) = provided(input.locale.as_ref()) {
+        if Locale::from_code(locale).is_none() {
+            return Err(AppError::validation(t_with(
+                "locale.invalid",
+                &[("value", &locale), ("options", &i18n::LOCALES.join(", "))],
+            ))
+            .on("locale"));
+        }
+    }
+
+    if let Some(day) = provided(input.first_day_of_week.as_ref()) {
+        if parse_weekday(day).is_none() {
+            return Err(AppError::validation(t_with(
+                "settings.invalid_weekday",
+                &[("value", &day), ("options", &WEEKDAYS.join(", "))],
+            ))
+            .on("firstDayOfWeek"));
+        }
+    }
+
+    if let Some(account_id) = provided(input.default_account_id.as_ref()) {
+        let account = accounts::get_by_id(pool, account_id)
+            .await?
+            .ok_or_else(|| {
+                AppError::not_found(t_with("account.not_found", &[("id", &account_id)]))
+                    .on("defaultAccountId")
+            })?;
+        if account.is_active != 1 {
+            return Err(AppError::validation(t("account.archived")).on("defaultAccountId"));
+        }
+    }
+
+    if let Some(category_id) = provided(input.default_debt_category_id.as_ref()) {
+        let category = categories::get_by_id(pool, category_id)
+            .await?
+            .ok_or_else(|| {
+                AppError::not_found(t_with("category.not_found", &[("id", &category_id)]))
+                    .on("defaultDebtCategoryId")
+            })?;
+        if category.is_active != 1 {
+            return Err(AppError::validation(t("category.inactive")).on("defaultDebtCategoryId"));
+        }
+        if category.category_type != "expense" {
+            return Err(
+                AppError::validation(t("installment.expense_category")).on("defaultDebtCategoryId")
+            );
+        }
+    }
+
+    Ok(())
+}
+
+/// Get every setting, with defaults for the ones never saved.
+pub async fn get_settings(pool: &SqlitePool) -> Result<AppSettings, AppError> {
+    app_settings::load(pool).await.map_err(AppError::from)
+}
+
+/// Save the provided settings atomically and return the full set. A new locale applies to
+/// every subsequent message.
+pub async fn update_settings(
+    pool: &SqlitePool,
+    input: UpdateSettingsInput,
+) -> Result<AppSettings, AppError> {
+    validate_settings(pool, &input).await?;
+
+    let values = [
+        (settings::BASE_CURRENCY_KEY, &input.base_currency_id),
+        (settings::LOCALE_KEY, &input.locale),
+        (settings::FIRST_DAY_OF_WEEK_KEY, &input.first_day_of_week),
+        (settings::DEFAULT_ACCOUNT_KEY, &input.default_account_id),
+        (
+            settings::DEFAULT_DEBT_CATEGORY_KEY,
+            &input.default_debt_category_id,
+        ),
+    ];
+
+    let mut db_txn = pool.begin().await?;
+    for (key, value) in values {
+        match value.as_deref() {
+            None => {}
+            Some("") => settings::delete_value(&mut db_txn, key).await?,
+            Some(value) => settings::set_value(&mut db_txn, key, value).await?,
+        }
+    }
+    db_txn.commit().await?;
+
+    let saved = app_settings::load(pool).await?;
+    if let Some(locale) = Locale::from_code(&saved.locale) {
+        i18n::set_current(locale);
+    }
+    Ok(saved)
+}
+
+/// Tauri handlers. Each forwards to the function of the same name above with the current pool.
+pub mod ipc {
+    use tauri::State;
+
+    use super::{AppError, AppSettings, UpdateSettingsInput};
+    use crate::db::Database;
+
+    #[tauri::command]
+    pub async fn get_settings(db: State<'_, Database>) -> Result<AppSettings, AppError> {
+        super::get_settings(&db.pool()).await
+    }
+
+    #[tauri::command]
+    pub async fn update_settings(
+        db: State<'_, Database>,
+        input: UpdateSettingsInput,
+    ) -> Result<AppSettings, AppError> {
+        super::update_settings(&db.pool(), input).await
+    }
+}
@@ -0,0 +1,271 @@
+use chrono::NaiveDate;
+use sqlx::SqlitePool;
+
+use crate::commands::transactions::recalculate_account_balances;
+use crate::db::models::{Account, SettleStatementInput, Statement, StatementPayment, Transfer};
+use crate::db::queries::{accounts, statements, transfers};
+use crate::error::AppError;
+use crate::services::i18n::{t, t_with};
+use crate::services::periods::parse_date;
+use crate::services::statements::{CardSchedule, Cycle};
+
+/// Statements returned by `list_statements` when no count is given, and the most allowed.
+const DEFAULT_STATEMENTS: u32 = 6;
+const MAX_STATEMENTS: u32 = 24;
+
+fn format_date(date: NaiveDate) -> String {
+    date.format("%Y-%m-%d").to_string()
+}
+
+/// Load a credit card and its statement schedule, rejecting any other kind of account.
+async fn card_schedule(
+    pool: &SqlitePool,
+    account_id: &str,
+) -> Result<(Account, CardSchedule), AppError> {
+    let account = accounts::get_by_id(pool, account_id)
+        .await?
+        .ok_or_else(|| {
+            AppError::not_found(t_with("account.not_found", &[("id", &account_id)])).on("accountId")
+        })?;
+
+    let billing_day = account
+        .billing_day
+        .and_then(|day| u32::try_from(day).ok())
+        .filter(|_| account.account_type == "credit_card")
+        .ok_or_else(|| AppError::validation(t("statement.not_credit_card")).on("accountId"))?;
+    let schedule = CardSchedule {
+        billing_day,
+        payment_due_day: account
+            .payment_due_day
+            .and_then(|day| u32::try_from(day).ok()),
+    };
+
+    Ok((account, schedule))
+}
+
+/// The cycle closing on a date given by the frontend.
+fn cycle_closing_on(schedule: CardSchedule, closing_date: &str) -> Result<Cycle, AppError> {
+    let closing = parse_date(Some(closing_date), "field.closing_date")
+        .map_err(|e| AppError::validation(e).on("closingDate"))?;
+    if !schedule.is_closing_date(closing) {
+        return Err(AppError::validation(t_with(
+            "statement.invalid_closing_date",
+            &[("date", &closing_date)],
+        ))
+        .on("closingDate"));
+    }
+    schedule
+        .cycle_closing_on(closing)
+        .ok_or_else(|| AppError::validation(t("period.out_of_range")).on("closingDate"))
+}
+
+/// Assemble the statement of one cycle with its charges, installments and payments.
+async fn build_statement(
+    pool: &SqlitePool,
+    account_id: &str,
+    cycle: Cycle,
+    today: NaiveDate,
+) -> Result<Statement, AppError> {
+    let (start, closing) = (format_date(cycle.start), format_date(cycle.closing));
+
+    let charges = statements::get_charges(pool, account_id, &start, &closing).await?;
+    let installments = statements::get_installments_due(pool, account_id, &start, &closing).await?;
+    let paid_amount = statements::get_paid_amount(pool, account_id, &closing).await?;
+
+    // Refunds on the card reduce the amount billed
+    let charges_total: i64 = charges
+        .iter()
+        .map(|c| {
+            if c.transaction_type == "income" {
+                -c.amount
+            } else {
+                c.amount
+            }
+        })
+        .sum();
+    // Installments paid from another account were never charged to the card
+    let installments_total: i64 = installments
+        .iter()
+        .map(|i| (i.amount - i.paid_elsewhere).max(0))
+        .sum();
+    let total = charges_total + installments_total;
+
+    Ok(Statement {
+        account_id: account_id.to_string(),
+        period_start: start,
+        closing_date: closing,
+        due_date: format_date(cycle.due),
+        is_closed: cycle.closing < today,
+        charges,
+        installments,
+        charges_total,
+        installments_total,
+        total,
+        paid_amount,
+        outstanding: (total - paid_amount).max(0),
+    })
+}
+
+/// List a credit card's statements, newest first, starting with the open cycle that today's
+/// charges go to.
+pub async fn list_statements(
+    pool: &SqlitePool,
+    account_id: String,
+    count: Option<u32>,
+) -> Result<Vec<Statement>, AppError> {
+    let count = count.unwrap_or(DEFAULT_STATEMENTS);
+    if count == 0 || count > MAX_STATEMENTS {
+        return Err(AppError::validation(t_with(
+            "statement.count_range",
+            &[("max", &MAX_STATEMENTS)],
+        ))
+        .on("count"));
+    }
+
+    let (_, schedule) = card_schedule(pool, &account_id).await?;
+    let today = chrono::Local::now().date_naive();
+
+    let mut cycle = schedule.cycle_containing(today);
+    let mut result = Vec::new();
+    while let Some(current) = cycle.filter(|_| result.len() < count as usize) {
+        result.push(build_statement(pool, &account_id, current, today).await?);
+        cycle = schedule.previous(current);
+    }
+    Ok(result)
+}
+
+/// Get the statement of a credit card closing on `closing_date` (YYYY-MM-DD).
+pub async fn get_statement(
+    pool: &SqlitePool,
+    account_id: String,
+    closing_date: String,
+) -> Result<Statement, AppError> {
+    let (_, schedule) = card_schedule(pool, &account_id).await?;
+    let cycle = cycle_closing_on(schedule, &closing_date)?;
+    build_statement(pool, &account_id, cycle, chrono::Local::now().date_naive()).await
+}
+
+/// Pay what remains of a closed statement with a transfer from another account in the card's
+/// currency. The transfer, its link to the statement and both balances are written in one
+/// SQL transaction.
+pub async fn settle_statement(
+    pool: &SqlitePool,
+    input: SettleStatementInput,
+) -> Result<Statement, AppError> {
+    let (card, schedule) = card_schedule(pool, &input.account_id).await?;
+    let cycle = cycle_closing_on(schedule, &input.closing_date)?;
+    let today = chrono::Local::now().date_naive();
+
+    let statement = build_statement(pool, &card.id, cycle, today).await?;
+    if !statement.is_closed {
+        return Err(AppError::validation(t("statement.not_closed")).on("closingDate"));
+    }
+    if statement.outstanding == 0 {
+        return Err(AppError::conflict(t("statement.nothing_due")));
+    }
+
+    let source = accounts::get_by_id(pool, &input.from_account_id)
+        .await?
+        .ok_or_else(|| {
+            AppError::not_found(t_with(
+                "account.not_found",
+                &[("id", &input.from_account_id)],
+            ))
+            .on("fromAccountId")
+        })?;
+    if source.id == card.id {
+        return Err(AppError::validation(t("transfer.same_account")).on("fromAccountId"));
+    }
+    if source.is_active != 1 || card.is_active != 1 {
+        return Err(AppError::validation(t("transfer.archived_account")).on("fromAccountId"));
+    }
+    if source.currency_id != card.currency_id {
+        return Err(AppError::validation(t("statement.currency_mismatch")).on("fromAccountId"));
+    }
+
+    let date = match input.date.filter(|d| !d.is_empty()) {
+        Some(date) => format_date(
+            parse_date(Some(&date), "field.payment_date")
+                .map_err(|e| AppError::validation(e).on("date"))?,
+        ),
+        None => format_date(today),
+    };
+    let description = input
+        .description
+        .map(|d| d.trim().to_string())
+        .filter(|d| !d.is_empty())
+        .unwrap_or_else(|| {
+            t_with(
+                "statement.payment_description",
+                &[("date", &statement.closing_date)],
+            )
+        });
+
+    let transfer = Transfer {
+        id: uuid::Uuid::new_v4().to_string(),
+        from_transaction_id: uuid::Uuid::new_v4().to_string(),
+        to_transaction_id: uuid::Uuid::new_v4().to_string(),
+        from_account_id: source.id,
+        to_account_id: card.id,
+        from_amount: statement.outstanding,
+        to_amount: statement.outstanding,
+        rate: 1.0,
+        description,
+        date,
+        notes: None,
+        created_at: String::new(),
+    };
+    let payment = StatementPayment {
+        id: uuid::Uuid::new_v4().to_string(),
+        account_id: transfer.to_account_id.clone(),
+        closing_date: statement.closing_date,
+        transfer_id: transfer.id.clone(),
+        created_at: String::new(),
+    };
+
+    let mut db_txn = pool.begin().await?;
+    transfers::create(&mut db_txn, &transfer).await?;
+    statements::create_payment(&mut db_txn, &payment).await?;
+    recalculate_account_balances(
+        &mut db_txn,
+        &[&transfer.from_account_id, &transfer.to_account_id],
+    )
+    .await?;
+    db_txn.commit().await?;
+
+    build_statement(pool, &payment.account_id, cycle, today).await
+}
+
+/// Tauri handlers. Each forwards to the function of the same name above with the current pool.
+pub mod ipc {
+    use tauri::State;
+
+    use super::{AppError, SettleStatementInput, Statement};
+    use crate::db::Database;
+
+    #[tauri::command]
+    pub async fn list_statements(
+        db: State<'_, Database>,
+        account_id: String,
+        count: Option<u32>,
+    ) -> Result<Vec<Statement>, AppError> {
+        super::list_statements(&db.pool(), account_id, count).await
+    }
+
+    #[tauri::command]
+    pub async fn get_statement(
+        db: State<'_, Database>,
+        account_id: String,
+        closing_date: String,
+    ) -> Result<Statement, AppError> {
+        super::get_statement(&db.pool(), account_id, closing_date).await
+    }
+
+    #[tauri::command]
+    pub async fn settle_statement(
+        db: State<'_, Database>,
+        input: SettleStatementInput,
+    ) -> Result<Statement, AppError> {
+        super::settle_statement(&db.pool(), input).await
+    }
+}
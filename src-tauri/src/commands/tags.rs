@@ -0,0 +1,177 @@
+use sqlx::SqlitePool;
+
+use crate::db::models::Tag;
+use crate::db::queries::{tags, transactions};
+use crate::services::i18n::{t, t_with};
+
+/// Default tag color, matching the column default in the schema.
+const DEFAULT_TAG_COLOR: &str = "#4a5d23";
+
+/// Validate a `#rrggbb` hex color string.
+fn validate_color(color: &str) -> Result<(), String> {
+    let valid = color.len() == 7
+        && color.starts_with('#')
+        && color[1..].chars().all(|c| c.is_ascii_hexdigit());
+    if valid {
+        Ok(())
+    } else {
+        Err(t_with("tag.invalid_color", &[("color", &color)]))
+    }
+}
+
+/// List all tags ordered by name.
+pub async fn list_tags(pool: &SqlitePool) -> Result<Vec<Tag>, String> {
+    tags::get_all(pool).await.map_err(|e| e.to_string())
+}
+
+/// Create a new tag. Names are unique (case-insensitive).
+pub async fn create_tag(
+    pool: &SqlitePool,
+    name: String,
+    color: Option<String>,
+) -> Result<Tag, String> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(t("tag.name_required"));
+    }
+
+    let color = color.unwrap_or_else(|| DEFAULT_TAG_COLOR.to_string());
+    validate_color(&color)?;
+
+    let duplicate = tags::get_by_name(pool, name)
+        .await
+        .map_err(|e| e.to_string())?;
+    if duplicate.is_some() {
+        return Err(t_with("tag.duplicate", &[("name", &name)]));
+    }
+
+    let id = uuid::Uuid::new_v4().to_string();
+    tags::create(pool, &id, name, &color)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Update a tag's name and/or color.
+pub async fn update_tag(
+    pool: &SqlitePool,
+    id: String,
+    name: Option<String>,
+    color: Option<String>,
+) -> Result<Tag, String> {
+    let existing = tags::get_by_id(pool, &id)
+        .await
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| t_with("tag.not_found", &[("id", &id)]))?;
+
+    let final_name = name.as_deref().map_or(existing.name.as_str(), str::trim);
+    if final_name.is_empty() {
+        return Err(t("tag.name_required"));
+    }
+
+    let final_color = color.as_deref().unwrap_or(&existing.color);
+    validate_color(final_color)?;
+
+    if let Some(duplicate) = tags::get_by_name(pool, final_name)
+        .await
+        .map_err(|e| e.to_string())?
+    {
+        if duplicate.id != id {
+            return Err(t_with("tag.duplicate", &[("name", &final_name)]));
+        }
+    }
+
+    tags::update(pool, &id, final_name, final_color)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Delete a tag. Its links to transactions are removed; transactions are untouched.
+pub async fn delete_tag(pool: &SqlitePool, id: String) -> Result<(), String> {
+    tags::get_by_id(pool, &id)
+        .await
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| t_with("tag.not_found", &[("id", &id)]))?;
+
+    tags::delete(pool, &id).await.map_err(|e| e.to_string())
+}
+
+/// Replace all tags on a transaction with the given set and return the new tags.
+pub async fn set_transaction_tags(
+    pool: &SqlitePool,
+    transaction_id: String,
+    tag_ids: Vec<String>,
+) -> Result<Vec<Tag>, String> {
+    transactions::get_by_id(pool, &transaction_id)
+        .await
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| t_with("transaction.not_found", &[("id", &transaction_id)]))?;
+
+    let mut tag_ids = tag_ids;
+    tag_ids.sort();
+    tag_ids.dedup();
+
+    let existing = tags::count_existing(pool, &tag_ids)
+        .await
+        .map_err(|e| e.to_string())?;
+    #[allow(clippy::cast_possible_wrap)]
+    let requested = tag_ids.len() as i64;
+    if existing != requested {
+        return Err(t("tag.missing"));
+    }
+
+    let mut db_txn = pool.begin().await.map_err(|e| e.to_string())?;
+    tags::set_for_transaction(&mut db_txn, &transaction_id, &tag_ids)
+        .await
+        .map_err(|e| e.to_string())?;
+    db_txn.commit().await.map_err(|e| e.to_string())?;
+
+    tags::get_for_transaction(pool, &transaction_id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Tauri handlers. Each forwards to the function of the same name above with the current pool.
+pub mod ipc {
+    use tauri::State;
+
+    use super::Tag;
+    use crate::db::Database;
+
+    #[tauri::command]
+    pub async fn list_tags(db: State<'_, Database>) -> Result<Vec<Tag>, String> {
+        super::list_tags(&db.pool()).await
+    }
+
+    #[tauri::command]
+    pub async fn create_tag(
+        db: State<'_, Database>,
+        name: String,
+        color: Option<String>,
+    ) -> Result<Tag, String> {
+        super::create_tag(&db.pool(), name, color).await
+    }
+
+    #[tauri::command]
+    pub async fn update_tag(
+        db: State<'_, Database>,
+        id: String,
+        name: Option<String>,
+        color: Option<String>,
+    ) -> Result<Tag, String> {
+        super::update_tag(&db.pool(), id, name, color).await
+    }
+
+    #[tauri::command]
+    pub async fn delete_tag(db: State<'_, Database>, id: String) -> Result<(), String> {
+        super::delete_tag(&db.pool(), id).await
+    }
+
+    #[tauri::command]
+    pub async fn set_transaction_tags(
+        db: State<'_, Database>,
+        transaction_id: String,
+        tag_ids: Vec<String>,
+    ) -> Result<Vec<Tag>, String> {
+        super::set_transaction_tags(&db.pool(), transaction_id, tag_ids).await
+    }
+}
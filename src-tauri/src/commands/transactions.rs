@@ -1,17 +1,24 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
 use sqlx::{Sqlite, SqlitePool};
-use tauri::State;
 
+use crate::commands::exchange_rates::{apply_rate, scale_rate};
 use crate::db::models::{
-    AccountBalance, BalanceSummary, PaginatedResult, Transaction, TransactionFilter,
+    AccountBalance, BalanceSummary, DescriptionSuggestion, PaginatedResult, Tag, Transaction,
+    TransactionFilter, TransactionWithTags,
 };
-use crate::db::queries::transactions;
+use crate::db::queries::{debts, tags, transactions};
+use crate::error::AppError;
+use crate::services::i18n::{t, t_with};
+use crate::services::settings;
 
 /// Recalculate and update an account's balance based on the sum of its transactions.
 /// Must be called within an active SQL transaction.
-async fn recalculate_account_balance(
+pub(crate) async fn recalculate_account_balance(
     db_txn: &mut sqlx::Transaction<'_, Sqlite>,
     account_id: &str,
-) -> Result<(), String> {
+) -> Result<(), AppError> {
     sqlx::query(
         "UPDATE accounts SET balance = (
             SELECT COALESCE(SUM(CASE WHEN type = 'income' THEN amount ELSE -amount END), 0)
@@ -21,49 +28,111 @@ async fn recalculate_account_balance(
     .bind(account_id)
     .bind(account_id)
     .execute(&mut **db_txn)
-    .await
-    .map_err(|e| e.to_string())?;
+    .await?;
     Ok(())
 }
 
-/// Create a new transaction and atomically update the account balance.
-#[tauri::command]
-pub async fn create_transaction(
-    pool: State<'_, SqlitePool>,
-    account_id: String,
-    category_id: String,
-    amount: i64,
-    transaction_type: String,
-    description: String,
-    date: String,
-) -> Result<Transaction, String> {
+/// Recalculate several account balances (e.g. both legs of a transfer) inside the caller's
+/// SQL transaction, so either all of them are updated or none is. Duplicates are skipped.
+pub(crate) async fn recalculate_account_balances(
+    db_txn: &mut sqlx::Transaction<'_, Sqlite>,
+    account_ids: &[&str],
+) -> Result<(), AppError> {
+    let mut done: Vec<&str> = Vec::with_capacity(account_ids.len());
+    for account_id in account_ids {
+        if !done.contains(account_id) {
+            recalculate_account_balance(db_txn, account_id).await?;
+            done.push(account_id);
+        }
+    }
+    Ok(())
+}
+
+/// Shortest prefix `get_suggestions` searches for.
+const MIN_SUGGESTION_PREFIX: usize = 2;
+
+/// Suggestions returned when no limit is given, and the most allowed.
+const DEFAULT_SUGGESTIONS: u32 = 5;
+const MAX_SUGGESTIONS: u32 = 20;
+
+/// Reject direct edits to a transfer leg: both legs must change together.
+fn ensure_not_transfer_leg(transaction: &Transaction) -> Result<(), AppError> {
+    if transaction.transfer_id.is_some() {
+        return Err(AppError::conflict(t("transaction.transfer_leg")));
+    }
+    Ok(())
+}
+
+/// Reject deleting a debt payment, or changing what or when it paid, on its own: its
+/// installment or prepayment would no longer match it.
+async fn ensure_not_debt_payment(pool: &SqlitePool, id: &str) -> Result<(), AppError> {
+    if debts::get_installment_by_transaction(pool, id)
+        .await?
+        .is_some()
+    {
+        return Err(AppError::conflict(t("transaction.installment_payment")));
+    }
+    if debts::get_prepayment_by_transaction(pool, id)
+        .await?
+        .is_some()
+    {
+        return Err(AppError::conflict(t("transaction.debt_prepayment")));
+    }
+    Ok(())
+}
+
+/// Fields of a transaction about to be inserted. Shared by manual entry and file imports
+/// so every path applies the same validation and insert.
+pub(crate) struct NewTransaction<'a> {
+    pub account_id: &'a str,
+    pub category_id: &'a str,
+    pub amount: i64,
+    pub transaction_type: &'a str,
+    pub description: &'a str,
+    pub date: &'a str,
+    /// Bank-assigned transaction ID (OFX FITID), unique per account when present.
+    pub fitid: Option<&'a str>,
+}
+
+/// Validate a new transaction: positive amount, date present, valid type, active account,
+/// and an active category whose type matches.
+pub(crate) async fn validate_new_transaction(
+    pool: &SqlitePool,
+    new: &NewTransaction<'_>,
+) -> Result<(), AppError> {
     // Validate amount
-    if amount <= 0 {
-        return Err("El monto debe ser mayor a 0".into());
+    if new.amount <= 0 {
+        return Err(AppError::validation(t("validation.amount_positive")).on("amount"));
     }
 
     // Validate date is non-empty
-    if date.is_empty() {
-        return Err("La fecha es obligatoria".into());
+    if new.date.is_empty() {
+        return Err(AppError::validation(t("validation.date_required")).on("date"));
     }
 
     // Validate transaction type
+    let transaction_type = new.transaction_type;
     if transaction_type != "income" && transaction_type != "expense" {
-        return Err("El tipo de transaccion debe ser 'income' o 'expense'".into());
+        return Err(AppError::validation(t("validation.transaction_type")).on("transactionType"));
     }
 
     // Validate account exists and is active
     let account: Option<(String, i32)> =
         sqlx::query_as("SELECT id, is_active FROM accounts WHERE id = ?")
-            .bind(&account_id)
-            .fetch_optional(pool.inner())
-            .await
-            .map_err(|e| e.to_string())?;
+            .bind(new.account_id)
+            .fetch_optional(pool)
+            .await?;
 
     match account {
-        None => return Err(format!("Cuenta no encontrada: {account_id}")),
+        None => {
+            return Err(AppError::not_found(t_with(
+                "account.not_found",
+                &[("id", &new.account_id)],
+            ))
+            .on("accountId"))
+        }
         Some((_, is_active)) if is_active != 1 => {
-            return Err("No se puede agregar transacciones a una cuenta archivada".into());
+            return Err(AppError::validation(t("transaction.archived_account")).on("accountId"));
         }
         _ => {}
     }
@@ -71,58 +140,107 @@ pub async fn create_transaction(
     // Validate category exists and is active
     let category: Option<(String, String, i32)> =
         sqlx::query_as("SELECT id, type, is_active FROM categories WHERE id = ?")
-            .bind(&category_id)
-            .fetch_optional(pool.inner())
-            .await
-            .map_err(|e| e.to_string())?;
+            .bind(new.category_id)
+            .fetch_optional(pool)
+            .await?;
 
     match category {
-        None => return Err(format!("Categoria no encontrada: {category_id}")),
+        None => {
+            return Err(AppError::not_found(t_with(
+                "category.not_found",
+                &[("id", &new.category_id)],
+            ))
+            .on("categoryId"))
+        }
         Some((_, _, is_active)) if is_active != 1 => {
-            return Err("No se puede usar una categoria inactiva".into());
+            return Err(AppError::validation(t("category.inactive")).on("categoryId"));
         }
         Some((_, cat_type, _)) if cat_type != transaction_type => {
-            return Err(format!(
-                "El tipo de transaccion '{transaction_type}' no coincide con el tipo de categoria '{cat_type}'"
-            ));
+            return Err(AppError::validation(t_with(
+                "category.type_mismatch",
+                &[
+                    ("transaction_type", &transaction_type),
+                    ("category_type", &cat_type),
+                ],
+            ))
+            .on("categoryId"));
         }
         _ => {}
     }
 
-    let id = uuid::Uuid::new_v4().to_string();
+    Ok(())
+}
 
-    // Atomic: insert transaction + recalculate balance
-    let mut db_txn = pool.begin().await.map_err(|e| e.to_string())?;
+/// Insert a validated transaction. Returns the new ID, or `None` when a transaction with
+/// the same FITID already exists on the account (re-imports are skipped, not duplicated).
+/// Must be called within an active SQL transaction; the caller recalculates the balance.
+pub(crate) async fn insert_transaction(
+    db_txn: &mut sqlx::Transaction<'_, Sqlite>,
+    new: &NewTransaction<'_>,
+) -> Result<Option<String>, AppError> {
+    let id = uuid::Uuid::new_v4().to_string();
 
-    sqlx::query(
-        "INSERT INTO transactions (id, account_id, category_id, amount, type, description, date)
-         VALUES (?, ?, ?, ?, ?, ?, ?)",
+    let result = sqlx::query(
+        "INSERT INTO transactions
+            (id, account_id, category_id, amount, type, description, date, fitid)
+         VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+         ON CONFLICT (account_id, fitid) WHERE fitid IS NOT NULL DO NOTHING",
     )
     .bind(&id)
-    .bind(&account_id)
-    .bind(&category_id)
-    .bind(amount)
-    .bind(&transaction_type)
-    .bind(&description)
-    .bind(&date)
-    .execute(&mut *db_txn)
-    .await
-    .map_err(|e| e.to_string())?;
+    .bind(new.account_id)
+    .bind(new.category_id)
+    .bind(new.amount)
+    .bind(new.transaction_type)
+    .bind(new.description)
+    .bind(new.date)
+    .bind(new.fitid)
+    .execute(&mut **db_txn)
+    .await?;
+
+    Ok((result.rows_affected() > 0).then_some(id))
+}
+
+/// Create a new transaction and atomically update the account balance.
+pub async fn create_transaction(
+    pool: &SqlitePool,
+    account_id: String,
+    category_id: String,
+    amount: i64,
+    transaction_type: String,
+    description: String,
+    date: String,
+) -> Result<Transaction, AppError> {
+    let new = NewTransaction {
+        account_id: &account_id,
+        category_id: &category_id,
+        amount,
+        transaction_type: &transaction_type,
+        description: &description,
+        date: &date,
+        fitid: None,
+    };
+    validate_new_transaction(pool, &new).await?;
+
+    // Atomic: insert transaction + recalculate balance
+    let mut db_txn = pool.begin().await?;
+
+    let id = insert_transaction(&mut db_txn, &new)
+        .await?
+        .ok_or_else(|| AppError::database(t("transaction.not_created")))?;
 
     recalculate_account_balance(&mut db_txn, &account_id).await?;
 
-    db_txn.commit().await.map_err(|e| e.to_string())?;
+    db_txn.commit().await?;
 
-    transactions::get_by_id(&pool, &id)
-        .await
-        .map_err(|e| e.to_string())?
-        .ok_or_else(|| "Transaccion creada pero no encontrada".into())
+    transactions::get_by_id(pool, &id)
+        .await?
+        .ok_or_else(|| AppError::database(t("transaction.created_missing")))
 }
 
-/// Update an existing transaction and atomically recalculate affected account balances.
-#[tauri::command]
+/// Update an existing transaction and atomically recalculate affected account balances. The
+/// account, amount, type and date of a debt payment cannot change without its debt.
 pub async fn update_transaction(
-    pool: State<'_, SqlitePool>,
+    pool: &SqlitePool,
     id: String,
     account_id: Option<String>,
     category_id: Option<String>,
@@ -130,12 +248,12 @@ pub async fn update_transaction(
     transaction_type: Option<String>,
     description: Option<String>,
     date: Option<String>,
-) -> Result<Transaction, String> {
+) -> Result<Transaction, AppError> {
     // Fetch existing transaction
-    let existing = transactions::get_by_id(&pool, &id)
-        .await
-        .map_err(|e| e.to_string())?
-        .ok_or_else(|| format!("Transaccion no encontrada: {id}"))?;
+    let existing = transactions::get_by_id(pool, &id)
+        .await?
+        .ok_or_else(|| AppError::not_found(t_with("transaction.not_found", &[("id", &id)])))?;
+    ensure_not_transfer_leg(&existing)?;
 
     let final_account_id = account_id.as_deref().unwrap_or(&existing.account_id);
     let final_category_id = category_id.as_deref().unwrap_or(&existing.category_id);
@@ -146,21 +264,33 @@ pub async fn update_transaction(
     let final_description = description.as_deref().unwrap_or(&existing.description);
     let final_date = date.as_deref().unwrap_or(&existing.date);
 
+    // A debt payment keeps its category and description editable, nothing else
+    if (final_account_id, final_amount, final_type, final_date)
+        != (
+            existing.account_id.as_str(),
+            existing.amount,
+            existing.transaction_type.as_str(),
+            existing.date.as_str(),
+        )
+    {
+        ensure_not_debt_payment(pool, &id).await?;
+    }
+
     // Validate amount if provided
     if final_amount <= 0 {
-        return Err("El monto debe ser mayor a 0".into());
+        return Err(AppError::validation(t("validation.amount_positive")).on("amount"));
     }
 
     // Validate type if provided
     if final_type != "income" && final_type != "expense" {
-        return Err("El tipo de transaccion debe ser 'income' o 'expense'".into());
+        return Err(AppError::validation(t("validation.transaction_type")).on("transactionType"));
     }
 
     let old_account_id = existing.account_id.clone();
     let account_changed = final_account_id != old_account_id;
 
     // Atomic: update transaction + recalculate balance(s)
-    let mut db_txn = pool.begin().await.map_err(|e| e.to_string())?;
+    let mut db_txn = pool.begin().await?;
 
     sqlx::query(
         "UPDATE transactions
@@ -175,8 +305,7 @@ pub async fn update_transaction(
     .bind(final_date)
     .bind(&id)
     .execute(&mut *db_txn)
-    .await
-    .map_err(|e| e.to_string())?;
+    .await?;
 
     // Recalculate old account balance
     recalculate_account_balance(&mut db_txn, &old_account_id).await?;
@@ -186,55 +315,71 @@ pub async fn update_transaction(
         recalculate_account_balance(&mut db_txn, final_account_id).await?;
     }
 
-    db_txn.commit().await.map_err(|e| e.to_string())?;
+    db_txn.commit().await?;
 
-    transactions::get_by_id(&pool, &id)
-        .await
-        .map_err(|e| e.to_string())?
-        .ok_or_else(|| "Transaccion actualizada pero no encontrada".into())
+    transactions::get_by_id(pool, &id)
+        .await?
+        .ok_or_else(|| AppError::database(t("transaction.updated_missing")))
 }
 
-/// Delete a transaction and atomically recalculate the account balance.
-#[tauri::command]
-pub async fn delete_transaction(pool: State<'_, SqlitePool>, id: String) -> Result<(), String> {
+/// Delete a transaction and atomically recalculate the account balance. Debt payments are
+/// refused: `unmark_installment_paid` deletes installment payments along with resetting the
+/// installment.
+pub async fn delete_transaction(pool: &SqlitePool, id: String) -> Result<(), AppError> {
     // Fetch existing to get account_id
-    let existing = transactions::get_by_id(&pool, &id)
-        .await
-        .map_err(|e| e.to_string())?
-        .ok_or_else(|| format!("Transaccion no encontrada: {id}"))?;
+    let existing = transactions::get_by_id(pool, &id)
+        .await?
+        .ok_or_else(|| AppError::not_found(t_with("transaction.not_found", &[("id", &id)])))?;
+    ensure_not_transfer_leg(&existing)?;
+    ensure_not_debt_payment(pool, &id).await?;
 
     let account_id = existing.account_id;
 
     // Atomic: delete transaction + recalculate balance
-    let mut db_txn = pool.begin().await.map_err(|e| e.to_string())?;
+    let mut db_txn = pool.begin().await?;
 
     sqlx::query("DELETE FROM transactions WHERE id = ?")
         .bind(&id)
         .execute(&mut *db_txn)
-        .await
-        .map_err(|e| e.to_string())?;
+        .await?;
 
     recalculate_account_balance(&mut db_txn, &account_id).await?;
 
-    db_txn.commit().await.map_err(|e| e.to_string())?;
+    db_txn.commit().await?;
     Ok(())
 }
 
 /// List transactions with dynamic filtering, sorting, and pagination.
-#[tauri::command]
+/// Each transaction is returned together with its tags.
 pub async fn list_transactions(
-    pool: State<'_, SqlitePool>,
+    pool: &SqlitePool,
     filter: TransactionFilter,
-) -> Result<PaginatedResult<Transaction>, String> {
+) -> Result<PaginatedResult<TransactionWithTags>, AppError> {
+    if !matches!(filter.tag_match.as_deref(), None | Some("any" | "all")) {
+        return Err(AppError::validation(t("validation.tag_match")).on("tagMatch"));
+    }
     let page = filter.page.unwrap_or(1);
     let page_size = filter.page_size.unwrap_or(20);
 
-    let total = transactions::count_filtered(&pool, &filter)
-        .await
-        .map_err(|e| e.to_string())?;
-    let data = transactions::list_filtered(&pool, &filter)
-        .await
-        .map_err(|e| e.to_string())?;
+    let total = transactions::count_filtered(pool, &filter).await?;
+    let rows = transactions::list_filtered(pool, &filter).await?;
+
+    // Fetch tags for the whole page in one query and group them per transaction
+    let ids: Vec<String> = rows.iter().map(|t| t.id.clone()).collect();
+    let tag_rows = tags::get_for_transactions(pool, &ids).await?;
+
+    let mut tags_by_txn: HashMap<String, Vec<Tag>> = HashMap::new();
+    for (transaction_id, tag) in tag_rows {
+        tags_by_txn.entry(transaction_id).or_default().push(tag);
+    }
+
+    let data: Vec<TransactionWithTags> = rows
+        .into_iter()
+        .map(|transaction| {
+            let tags = tags_by_txn.remove(&transaction.id).unwrap_or_default();
+            TransactionWithTags { transaction, tags }
+        })
+        .collect();
 
     let total_pages = if total == 0 {
         0
@@ -252,11 +397,10 @@ pub async fn list_transactions(
 }
 
 /// Get a balance summary for all active accounts with optional currency consolidation.
-#[tauri::command]
 pub async fn get_balance_summary(
-    pool: State<'_, SqlitePool>,
+    pool: &SqlitePool,
     base_currency_id: Option<String>,
-) -> Result<BalanceSummary, String> {
+) -> Result<BalanceSummary, AppError> {
     // Fetch all active accounts with their currency code
     let accounts: Vec<(String, String, i64, String)> = sqlx::query_as(
         "SELECT a.id, a.name, a.balance, c.code
@@ -264,9 +408,8 @@ pub async fn get_balance_summary(
          JOIN currencies c ON a.currency_id = c.id
          WHERE a.is_active = 1",
     )
-    .fetch_all(pool.inner())
-    .await
-    .map_err(|e| e.to_string())?;
+    .fetch_all(pool)
+    .await?;
 
     let account_balances: Vec<AccountBalance> = accounts
         .iter()
@@ -278,16 +421,18 @@ pub async fn get_balance_summary(
         })
         .collect();
 
-    // Determine base currency
-    let base_cur_id = base_currency_id.unwrap_or_else(|| "cur_clp".to_string());
+    // Determine base currency, defaulting to the one in settings
+    let base_cur_id = match base_currency_id {
+        Some(id) => id,
+        None => settings::base_currency_id(pool).await?,
+    };
 
     // Get base currency code
     let base_currency_code: String =
         sqlx::query_as::<_, (String,)>("SELECT code FROM currencies WHERE id = ?")
             .bind(&base_cur_id)
-            .fetch_optional(pool.inner())
-            .await
-            .map_err(|e| e.to_string())?
+            .fetch_optional(pool)
+            .await?
             .map(|(code,)| code)
             .unwrap_or_else(|| "CLP".to_string());
 
@@ -301,22 +446,23 @@ pub async fn get_balance_summary(
             has_any = true;
         } else {
             // Look up exchange rate from account currency to base currency
-            let rate: Option<(f64,)> = sqlx::query_as(
-                "SELECT rate FROM exchange_rates
-                 WHERE from_currency_id = (SELECT id FROM currencies WHERE code = ?)
-                   AND to_currency_id = ?
-                 ORDER BY date DESC LIMIT 1",
+            let rate: Option<(f64, i32, i32)> = sqlx::query_as(
+                "SELECT er.rate, c.decimal_places, base.decimal_places
+                 FROM exchange_rates er
+                 JOIN currencies c ON er.from_currency_id = c.id
+                 JOIN currencies base ON er.to_currency_id = base.id
+                 WHERE c.code = ?
+                   AND er.to_currency_id = ?
+                 ORDER BY er.date DESC LIMIT 1",
             )
             .bind(currency_code)
             .bind(&base_cur_id)
-            .fetch_optional(pool.inner())
-            .await
-            .map_err(|e| e.to_string())?;
-
-            if let Some((rate_val,)) = rate {
-                #[allow(clippy::cast_precision_loss)]
-                let converted = (*balance as f64 * rate_val).round() as i64;
-                consolidated += converted;
+            .fetch_optional(pool)
+            .await?;
+
+            if let Some((rate_val, from_places, base_places)) = rate {
+                consolidated +=
+                    apply_rate(*balance, scale_rate(rate_val, from_places, base_places));
                 has_any = true;
             }
             // If no rate found, skip this account in consolidation
@@ -331,3 +477,175 @@ pub async fn get_balance_summary(
         base_currency_code,
     })
 }
+
+/// Most frequent value, with ties going to the one seen first.
+fn most_common<T: Eq + Hash + Clone>(values: impl Iterator<Item = T>) -> Option<T> {
+    let mut counts: HashMap<T, usize> = HashMap::new();
+    let mut order: Vec<T> = Vec::new();
+
+    for value in values {
+        let count = counts.entry(value.clone()).or_insert(0);
+        if *count == 0 {
+            order.push(value);
+        }
+        *count += 1;
+    }
+
+    let mut best: Option<(T, usize)> = None;
+    for value in order {
+        let count = counts.get(&value).copied().unwrap_or(0);
+        if best
+            .as_ref()
+            .map_or(true, |(_, best_count)| count > *best_count)
+        {
+            best = Some((value, count));
+        }
+    }
+    best.map(|(value, _)| value)
+}
+
+/// Autocomplete transaction descriptions from history. Returns up to `limit` (default 5)
+/// distinct descriptions starting with `prefix`, ranked by frequency and recency, each with
+/// its most common category, account, amount and type. Ties go to the most recent value.
+pub async fn get_suggestions(
+    pool: &SqlitePool,
+    prefix: String,
+    limit: Option<u32>,
+) -> Result<Vec<DescriptionSuggestion>, AppError> {
+    let prefix = prefix.trim();
+    if prefix.chars().count() < MIN_SUGGESTION_PREFIX {
+        return Err(AppError::validation(t_with(
+            "transaction.suggestion_prefix",
+            &[("min", &MIN_SUGGESTION_PREFIX)],
+        ))
+        .on("prefix"));
+    }
+    let limit = limit
+        .unwrap_or(DEFAULT_SUGGESTIONS)
+        .clamp(1, MAX_SUGGESTIONS);
+
+    let today = chrono::Local::now().format("%Y-%m-%d").to_string();
+    let ranked = transactions::rank_descriptions(pool, prefix, &today, i64::from(limit)).await?;
+    if ranked.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let descriptions: Vec<String> = ranked.iter().map(|(d, _, _)| d.clone()).collect();
+    let rows = transactions::get_by_descriptions(pool, &descriptions).await?;
+
+    let mut suggestions = Vec::with_capacity(ranked.len());
+    for (description, use_count, last_used) in ranked {
+        let matching: Vec<_> = rows
+            .iter()
+            .filter(|row| row.0.eq_ignore_ascii_case(&description))
+            .collect();
+        let (Some(category_id), Some(account_id), Some(amount), Some(transaction_type)) = (
+            most_common(matching.iter().map(|row| &row.1)),
+            most_common(matching.iter().map(|row| &row.2)),
+            most_common(matching.iter().map(|row| row.3)),
+            most_common(matching.iter().map(|row| &row.4)),
+        ) else {
+            continue;
+        };
+
+        suggestions.push(DescriptionSuggestion {
+            category_id: category_id.clone(),
+            account_id: account_id.clone(),
+            amount,
+            transaction_type: transaction_type.clone(),
+            description,
+            use_count,
+            last_used,
+        });
+    }
+
+    Ok(suggestions)
+}
+
+/// Tauri handlers. Each forwards to the function of the same name above with the current pool.
+pub mod ipc {
+    use tauri::State;
+
+    use super::{
+        AppError, BalanceSummary, DescriptionSuggestion, PaginatedResult, Transaction,
+        TransactionFilter, TransactionWithTags,
+    };
+    use crate::db::Database;
+
+    #[tauri::command]
+    pub async fn create_transaction(
+        db: State<'_, Database>,
+        account_id: String,
+        category_id: String,
+        amount: i64,
+        transaction_type: String,
+        description: String,
+        date: String,
+    ) -> Result<Transaction, AppError> {
+        super::create_transaction(
+            &db.pool(),
+            account_id,
+            category_id,
+            amount,
+            transaction_type,
+            description,
+            date,
+        )
+        .await
+    }
+
+    #[tauri::command]
+    #[allow(clippy::too_many_arguments)]
+    pub async fn update_transaction(
+        db: State<'_, Database>,
+        id: String,
+        account_id: Option<String>,
+        category_id: Option<String>,
+        amount: Option<i64>,
+        transaction_type: Option<String>,
+        description: Option<String>,
+        date: Option<String>,
+    ) -> Result<Transaction, AppError> {
+        super::update_transaction(
+            &db.pool(),
+            id,
+            account_id,
+            category_id,
+            amount,
+            transaction_type,
+            description,
+            date,
+        )
+        .await
+    }
+
+    #[tauri::command]
+    pub async fn delete_transaction(db: State<'_, Database>, id: String) -> Result<(), AppError> {
+        super::delete_transaction(&db.pool(), id).await
+    }
+
+    #[tauri::command]
+    pub async fn list_transactions(
+        db: State<'_, Database>,
+        filter: TransactionFilter,
+    ) -> Result<PaginatedResult<TransactionWithTags>, AppError> {
+        super::list_transactions(&db.pool(), filter).await
+    }
+
+    #[tauri::command]
+    pub async fn get_balance_summary(
+        db: State<'_, Database>,
+        base_currency_id: Option<String>,
+    ) -> Result<BalanceSummary, AppError> {
+        super::get_balance_summary(&db.pool(), base_currency_id).await
+    }
+
+    #[tauri::command]
+    pub async fn get_suggestions(
+        db: State<'_, Database>,
+        prefix: String,
+        limit: Option<u32>,
+    ) -> Result<Vec<DescriptionSuggestion>, AppError> {
+        super::get_suggestions(&db.pool(), prefix, limit).await
+    }
+}
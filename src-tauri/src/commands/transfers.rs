@@ -0,0 +1,279 @@
+use sqlx::SqlitePool;
+
+use crate::commands::exchange_rates::{apply_rate, scale_rate};
+use crate::commands::transactions::recalculate_account_balances;
+use crate::db::models::{CreateTransferInput, Transfer, UpdateTransferInput};
+use crate::db::queries::transfers;
+use crate::services::i18n::{t, t_with};
+
+/// Currency details of one side of a transfer.
+struct AccountCurrency {
+    currency_id: String,
+    decimal_places: i32,
+}
+
+/// Load an account's currency, rejecting unknown or archived accounts.
+async fn account_currency(pool: &SqlitePool, account_id: &str) -> Result<AccountCurrency, String> {
+    let row: Option<(i32, String, i32)> = sqlx::query_as(
+        "SELECT a.is_active, a.currency_id, c.decimal_places
+         FROM accounts a
+         JOIN currencies c ON a.currency_id = c.id
+         WHERE a.id = ?",
+    )
+    .bind(account_id)
+    .fetch_optional(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    match row {
+        None => Err(t_with("account.not_found", &[("id", &account_id)])),
+        Some((is_active, _, _)) if is_active != 1 => Err(t("transfer.archived_account")),
+        Some((_, currency_id, decimal_places)) => Ok(AccountCurrency {
+            currency_id,
+            decimal_places,
+        }),
+    }
+}
+
+/// Compute the amount credited to the destination account and the rate to store.
+/// Same-currency transfers always use a rate of 1; cross-currency ones need an explicit rate.
+fn resolve_to_amount(
+    from: &AccountCurrency,
+    to: &AccountCurrency,
+    amount: i64,
+    rate: Option<f64>,
+) -> Result<(i64, f64), String> {
+    if from.currency_id == to.currency_id {
+        if rate.is_some_and(|r| (r - 1.0).abs() > f64::EPSILON) {
+            return Err(t("transfer.same_currency_rate"));
+        }
+        return Ok((amount, 1.0));
+    }
+
+    let rate = rate.ok_or_else(|| t("transfer.rate_required"))?;
+    if !rate.is_finite() || rate <= 0.0 {
+        return Err(t("transfer.rate_positive"));
+    }
+
+    let to_amount = apply_rate(
+        amount,
+        scale_rate(rate, from.decimal_places, to.decimal_places),
+    );
+    if to_amount <= 0 {
+        return Err(t("transfer.converted_too_small"));
+    }
+    Ok((to_amount, rate))
+}
+
+/// Normalize an optional description, falling back to the default label.
+fn description_or_default(description: Option<&str>) -> String {
+    description
+        .map(str::trim)
+        .filter(|d| !d.is_empty())
+        .map_or_else(|| t("transfer.default_description"), str::to_string)
+}
+
+/// List transfers, optionally only those touching the given account.
+pub async fn list_transfers(
+    pool: &SqlitePool,
+    account_id: Option<String>,
+) -> Result<Vec<Transfer>, String> {
+    transfers::get_all(pool, account_id.as_deref())
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Create a transfer: an expense leg on the source account and an income leg on the
+/// destination, written together with both balance recalculations in one SQL transaction.
+pub async fn create_transfer(
+    pool: &SqlitePool,
+    input: CreateTransferInput,
+) -> Result<Transfer, String> {
+    if input.amount <= 0 {
+        return Err(t("validation.amount_positive"));
+    }
+    if input.date.is_empty() {
+        return Err(t("validation.date_required"));
+    }
+    if input.from_account_id == input.to_account_id {
+        return Err(t("transfer.same_account"));
+    }
+
+    let from = account_currency(pool, &input.from_account_id).await?;
+    let to = account_currency(pool, &input.to_account_id).await?;
+    let (to_amount, rate) = resolve_to_amount(&from, &to, input.amount, input.rate)?;
+
+    let transfer = Transfer {
+        id: uuid::Uuid::new_v4().to_string(),
+        from_transaction_id: uuid::Uuid::new_v4().to_string(),
+        to_transaction_id: uuid::Uuid::new_v4().to_string(),
+        from_account_id: input.from_account_id,
+        to_account_id: input.to_account_id,
+        from_amount: input.amount,
+        to_amount,
+        rate,
+        description: description_or_default(input.description.as_deref()),
+        date: input.date,
+        notes: input.notes.filter(|n| !n.is_empty()),
+        created_at: String::new(),
+    };
+
+    let mut db_txn = pool.begin().await.map_err(|e| e.to_string())?;
+    transfers::create(&mut db_txn, &transfer)
+        .await
+        .map_err(|e| e.to_string())?;
+    recalculate_account_balances(
+        &mut db_txn,
+        &[&transfer.from_account_id, &transfer.to_account_id],
+    )
+    .await?;
+    db_txn.commit().await.map_err(|e| e.to_string())?;
+
+    transfers::get_by_id(pool, &transfer.id)
+        .await
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| t("transfer.created_missing"))
+}
+
+/// Update a transfer. Both legs are rewritten and every affected account balance
+/// (old and new source/destination) is recalculated in the same SQL transaction.
+/// An empty `notes` clears them.
+pub async fn update_transfer(
+    pool: &SqlitePool,
+    id: String,
+    input: UpdateTransferInput,
+) -> Result<Transfer, String> {
+    let existing = transfers::get_by_id(pool, &id)
+        .await
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| t_with("transfer.not_found", &[("id", &id)]))?;
+
+    let from_account_id = input
+        .from_account_id
+        .unwrap_or_else(|| existing.from_account_id.clone());
+    let to_account_id = input
+        .to_account_id
+        .unwrap_or_else(|| existing.to_account_id.clone());
+    let amount = input.amount.unwrap_or(existing.from_amount);
+    let date = input.date.unwrap_or_else(|| existing.date.clone());
+
+    if amount <= 0 {
+        return Err(t("validation.amount_positive"));
+    }
+    if date.is_empty() {
+        return Err(t("validation.date_required"));
+    }
+    if from_account_id == to_account_id {
+        return Err(t("transfer.same_account"));
+    }
+
+    let from = account_currency(pool, &from_account_id).await?;
+    let to = account_currency(pool, &to_account_id).await?;
+
+    // Keep the stored rate only while both accounts stay the same
+    let same_accounts =
+        from_account_id == existing.from_account_id && to_account_id == existing.to_account_id;
+    let requested_rate = input.rate.or(same_accounts.then_some(existing.rate));
+    let (to_amount, rate) = resolve_to_amount(&from, &to, amount, requested_rate)?;
+
+    let description = match input.description {
+        Some(d) => description_or_default(Some(&d)),
+        None => existing.description.clone(),
+    };
+    let notes = match input.notes {
+        Some(n) if n.is_empty() => None,
+        Some(n) => Some(n),
+        None => existing.notes.clone(),
+    };
+
+    let transfer = Transfer {
+        from_account_id,
+        to_account_id,
+        from_amount: amount,
+        to_amount,
+        rate,
+        description,
+        date,
+        notes,
+        ..existing.clone()
+    };
+
+    let mut db_txn = pool.begin().await.map_err(|e| e.to_string())?;
+    transfers::update(&mut db_txn, &transfer)
+        .await
+        .map_err(|e| e.to_string())?;
+    recalculate_account_balances(
+        &mut db_txn,
+        &[
+            &existing.from_account_id,
+            &existing.to_account_id,
+            &transfer.from_account_id,
+            &transfer.to_account_id,
+        ],
+    )
+    .await?;
+    db_txn.commit().await.map_err(|e| e.to_string())?;
+
+    transfers::get_by_id(pool, &id)
+        .await
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| t("transfer.updated_missing"))
+}
+
+/// Delete a transfer and both legs, recalculating both account balances atomically.
+pub async fn delete_transfer(pool: &SqlitePool, id: String) -> Result<(), String> {
+    let existing = transfers::get_by_id(pool, &id)
+        .await
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| t_with("transfer.not_found", &[("id", &id)]))?;
+
+    let mut db_txn = pool.begin().await.map_err(|e| e.to_string())?;
+    transfers::delete(&mut db_txn, &id)
+        .await
+        .map_err(|e| e.to_string())?;
+    recalculate_account_balances(
+        &mut db_txn,
+        &[&existing.from_account_id, &existing.to_account_id],
+    )
+    .await?;
+    db_txn.commit().await.map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+/// Tauri handlers. Each forwards to the function of the same name above with the current pool.
+pub mod ipc {
+    use tauri::State;
+
+    use super::{CreateTransferInput, Transfer, UpdateTransferInput};
+    use crate::db::Database;
+
+    #[tauri::command]
+    pub async fn list_transfers(
+        db: State<'_, Database>,
+        account_id: Option<String>,
+    ) -> Result<Vec<Transfer>, String> {
+        super::list_transfers(&db.pool(), account_id).await
+    }
+
+    #[tauri::command]
+    pub async fn create_transfer(
+        db: State<'_, Database>,
+        input: CreateTransferInput,
+    ) -> Result<Transfer, String> {
+        super::create_transfer(&db.pool(), input).await
+    }
+
+    #[tauri::command]
+    pub async fn update_transfer(
+        db: State<'_, Database>,
+        id: String,
+        input: UpdateTransferInput,
+    ) -> Result<Transfer, String> {
+        super::update_transfer(&db.pool(), id, input).await
+    }
+
+    #[tauri::command]
+    pub async fn delete_transfer(db: State<'_, Database>, id: String) -> Result<(), String> {
+        super::delete_transfer(&db.pool(), id).await
+    }
+}
@@ -0,0 +1,248 @@
+use std::fmt::Write as _;
+
+use sha2::{Digest, Sha256};
+use sqlx::SqlitePool;
+
+use crate::services::i18n::t_with;
+
+/// A schema migration embedded at compile time. `down` undoes `up`; it is only meant for
+/// rolling back while a migration is being developed.
+pub struct Migration {
+    pub name: &'static str,
+    pub up: &'static str,
+    pub down: Option<&'static str>,
+}
+
+/// Embed `migrations/<name>.sql`, plus `migrations/<name>.down.sql` when marked `reversible`.
+macro_rules! migration {
+    ($name:literal) => {
+        Migration {
+            name: $name,
+            up: include_str!(concat!("migrations/", $name, ".sql")),
+            down: None,
+        }
+    };
+    ($name:literal, reversible) => {
+        Migration {
+            name: $name,
+            up: include_str!(concat!("migrations/", $name, ".sql")),
+            down: Some(include_str!(concat!("migrations/", $name, ".down.sql"))),
+        }
+    };
+}
+
+/// Every migration, in the order they are applied.
+pub const MIGRATIONS: &[Migration] = &[
+    migration!("001_initial_schema"),
+    migration!("002_seed_currencies"),
+    migration!("003_seed_categories"),
+    migration!("004_migrate_category_icons"),
+    migration!("005_create_installments"),
+    migration!("006_create_recurring_transactions"),
+    migration!("007_create_transfers"),
+    migration!("008_create_csv_import_profiles"),
+    migration!("009_add_transaction_fitid"),
+    migration!("010_create_budgets", reversible),
+    migration!("011_description_index_nocase", reversible),
+    migration!("012_create_settings", reversible),
+    migration!("013_credit_card_statements", reversible),
+    migration!("014_debt_amortization", reversible),
+    migration!("015_debt_lifecycle", reversible),
+    migration!("016_installment_payments", reversible),
+    migration!("017_debt_prepayments", reversible),
+    migration!("018_installment_partial_payments", reversible),
+];
+
+/// SHA-256 of a migration script as hex. Carriage returns are skipped so a checkout with
+/// Windows line endings gives the same checksum.
+pub fn checksum(sql: &str) -> String {
+    let mut hasher = Sha256::new();
+    for part in sql.split('\r') {
+        hasher.update(part.as_bytes());
+    }
+
+    hasher
+        .finalize()
+        .iter()
+        .fold(String::with_capacity(64), |mut hex, byte| {
+            let _ = write!(hex, "{byte:02x}");
+            hex
+        })
+}
+
+/// Applied migrations in the order they ran, with their stored checksum. Empty when the
+/// database has never been migrated. Databases migrated before checksums were recorded have
+/// no checksum column yet.
+async fn applied_migrations(
+    pool: &SqlitePool,
+) -> Result<Vec<(String, Option<String>)>, sqlx::Error> {
+    let columns: Vec<(String,)> =
+        sqlx::query_as("SELECT name FROM pragma_table_info('_migrations')")
+            .fetch_all(pool)
+            .await?;
+    if columns.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let query = if columns.iter().any(|(name,)| name == "checksum") {
+        "SELECT name, checksum FROM _migrations ORDER BY id"
+    } else {
+        "SELECT name, NULL FROM _migrations ORDER BY id"
+    };
+    sqlx::query_as(query).fetch_all(pool).await
+}
+
+/// Check the applied migrations against the embedded scripts and return the pending ones.
+/// Fails when an applied migration is unknown to this build or its script has changed since
+/// it ran.
+fn plan(applied: &[(String, Option<String>)]) -> Result<Vec<&'static Migration>, String> {
+    for (name, stored) in applied {
+        let migration = MIGRATIONS
+            .iter()
+            .find(|m| m.name == name)
+            .ok_or_else(|| t_with("migration.unknown", &[("name", name)]))?;
+
+        if stored
+            .as_ref()
+            .is_some_and(|stored| *stored != checksum(migration.up))
+        {
+            return Err(t_with("migration.modified", &[("name", name)]));
+        }
+    }
+
+    Ok(MIGRATIONS
+        .iter()
+        .filter(|m| !applied.iter().any(|(name, _)| name == m.name))
+        .collect())
+}
+
+/// Names of the migrations `run_migrations` would apply, without touching the database.
+///
+/// # Errors
+///
+/// Fails the same way `run_migrations` would when an applied migration was modified or is
+/// unknown to this build.
+pub async fn dry_run_migrations(
+    pool: &SqlitePool,
+) -> Result<Vec<&'static str>, Box<dyn std::error::Error>> {
+    let applied = applied_migrations(pool).await?;
+    Ok(plan(&applied)?.iter().map(|m| m.name).collect())
+}
+
+/// Run all pending migrations in order and return their names.
+/// Creates the `_migrations` tracking table if it doesn't exist and verifies the checksum of
+/// every migration applied before. Each migration runs in one transaction together with its
+/// `_migrations` row, so a failing migration leaves no trace. The script is split into
+/// statements by `SQLite` itself, so trigger bodies and string literals may contain `;`.
+///
+/// # Errors
+///
+/// Returns an error when an applied migration was modified or is unknown to this build, or
+/// when a migration fails. Migrations before the failing one stay applied.
+pub async fn run_migrations(
+    pool: &SqlitePool,
+) -> Result<Vec<&'static str>, Box<dyn std::error::Error>> {
+    // Create migrations tracking table
+    sqlx::query(
+        "CREATE TABLE IF NOT EXISTS _migrations (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            name TEXT NOT NULL UNIQUE,
+            applied_at TEXT NOT NULL DEFAULT (datetime('now')),
+            checksum TEXT
+        )",
+    )
+    .execute(pool)
+    .await?;
+
+    let applied = applied_migrations(pool).await?;
+    let pending = plan(&applied)?;
+
+    // Migrations applied before checksums were recorded are trusted as they are now
+    let mut db_txn = pool.begin().await?;
+    let has_checksum: (i64,) = sqlx::query_as(
+        "SELECT COUNT(*) FROM pragma_table_info('_migrations') WHERE name = 'checksum'",
+    )
+    .fetch_one(&mut *db_txn)
+    .await?;
+    if has_checksum.0 == 0 {
+        sqlx::query("ALTER TABLE _migrations ADD COLUMN checksum TEXT")
+            .execute(&mut *db_txn)
+            .await?;
+    }
+    for (name, stored) in &applied {
+        if stored.is_none() {
+            if let Some(migration) = MIGRATIONS.iter().find(|m| m.name == name) {
+                sqlx::query("UPDATE _migrations SET checksum = ? WHERE name = ?")
+                    .bind(checksum(migration.up))
+                    .bind(name)
+                    .execute(&mut *db_txn)
+                    .await?;
+            }
+        }
+    }
+    db_txn.commit().await?;
+
+    for migration in &pending {
+        let mut db_txn = pool.begin().await?;
+
+        sqlx::raw_sql(migration.up)
+            .execute(&mut *db_txn)
+            .await
+            .map_err(|e| {
+                t_with(
+                    "migration.failed",
+                    &[("name", &migration.name), ("error", &e)],
+                )
+            })?;
+
+        // Record migration as applied
+        sqlx::query("INSERT INTO _migrations (name, checksum) VALUES (?, ?)")
+            .bind(migration.name)
+            .bind(checksum(migration.up))
+            .execute(&mut *db_txn)
+            .await?;
+
+        db_txn.commit().await?;
+        println!("Applied migration: {}", migration.name);
+    }
+
+    Ok(pending.iter().map(|m| m.name).collect())
+}
+
+/// Undo the most recently applied migration with its down script, in one transaction, and
+/// return its name. Meant for development: rolling back usually drops data. Returns `None`
+/// when no migration has been applied.
+///
+/// # Errors
+///
+/// Returns an error when the last migration has no down script or the script fails.
+pub async fn rollback_last_migration(
+    pool: &SqlitePool,
+) -> Result<Option<&'static str>, Box<dyn std::error::Error>> {
+    let applied = applied_migrations(pool).await?;
+    let Some((name, _)) = applied.last() else {
+        return Ok(None);
+    };
+
+    let migration = MIGRATIONS
+        .iter()
+        .find(|m| m.name == name)
+        .ok_or_else(|| t_with("migration.unknown", &[("name", name)]))?;
+    let down = migration
+        .down
+        .ok_or_else(|| t_with("migration.no_down", &[("name", name)]))?;
+
+    let mut db_txn = pool.begin().await?;
+    sqlx::raw_sql(down)
+        .execute(&mut *db_txn)
+        .await
+        .map_err(|e| t_with("migration.failed", &[("name", name), ("error", &e)]))?;
+    sqlx::query("DELETE FROM _migrations WHERE name = ?")
+        .bind(migration.name)
+        .execute(&mut *db_txn)
+        .await?;
+    db_txn.commit().await?;
+
+    println!("Rolled back migration: {}", migration.name);
+    Ok(Some(migration.name))
+}
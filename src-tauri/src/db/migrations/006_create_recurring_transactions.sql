@@ -0,0 +1,23 @@
+-- Recurring transaction rules, materialized into transactions at startup
+CREATE TABLE recurring_transactions (
+    id TEXT PRIMARY KEY,
+    account_id TEXT NOT NULL REFERENCES accounts(id),
+    category_id TEXT NOT NULL REFERENCES categories(id),
+    amount INTEGER NOT NULL,
+    type TEXT NOT NULL CHECK (type IN ('income', 'expense')),
+    description TEXT NOT NULL,
+    frequency TEXT NOT NULL CHECK (frequency IN ('weekly', 'biweekly', 'monthly', 'yearly')),
+    next_date TEXT NOT NULL,
+    anchor_day INTEGER NOT NULL CHECK (anchor_day BETWEEN 1 AND 31),
+    end_date TEXT,
+    is_active INTEGER NOT NULL DEFAULT 1,
+    created_at TEXT NOT NULL DEFAULT (datetime('now'))
+);
+
+CREATE INDEX idx_recurring_active_date ON recurring_transactions(is_active, next_date);
+
+-- Link generated transactions back to their rule (one transaction per rule and date)
+ALTER TABLE transactions ADD COLUMN recurring_id TEXT REFERENCES recurring_transactions(id) ON DELETE SET NULL;
+
+CREATE UNIQUE INDEX idx_transactions_recurring_date ON transactions(recurring_id, date)
+    WHERE recurring_id IS NOT NULL;
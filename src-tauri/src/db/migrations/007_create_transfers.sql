@@ -0,0 +1,16 @@
+-- Transfers between accounts: each transfer owns an outgoing (expense) and an incoming (income) leg
+CREATE TABLE transfers (
+    id TEXT PRIMARY KEY,
+    rate REAL NOT NULL DEFAULT 1.0,
+    created_at TEXT NOT NULL DEFAULT (datetime('now'))
+);
+
+ALTER TABLE transactions ADD COLUMN transfer_id TEXT REFERENCES transfers(id) ON DELETE CASCADE;
+
+CREATE INDEX idx_transactions_transfer ON transactions(transfer_id);
+
+-- Categories assigned to transfer legs, kept out of income/expense reports
+INSERT OR IGNORE INTO categories (id, name, type, icon, parent_id) VALUES
+    ('cat_transferencia_salida', 'Transferencia', 'expense', 'arrow-left-right', NULL);
+INSERT OR IGNORE INTO categories (id, name, type, icon, parent_id) VALUES
+    ('cat_transferencia_entrada', 'Transferencia', 'income', 'arrow-left-right', NULL);
@@ -0,0 +1,18 @@
+-- Saved column mappings for importing bank statement CSV files
+CREATE TABLE csv_import_profiles (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL UNIQUE,
+    delimiter TEXT NOT NULL DEFAULT ',',
+    skip_rows INTEGER NOT NULL DEFAULT 1 CHECK (skip_rows >= 0),
+    date_column INTEGER NOT NULL CHECK (date_column >= 0),
+    date_format TEXT NOT NULL DEFAULT '%d/%m/%Y',
+    description_column INTEGER NOT NULL CHECK (description_column >= 0),
+    sign_convention TEXT NOT NULL CHECK (sign_convention IN ('negative_is_expense', 'positive_is_expense', 'debit_credit_columns')),
+    amount_column INTEGER CHECK (amount_column >= 0),
+    debit_column INTEGER CHECK (debit_column >= 0),
+    credit_column INTEGER CHECK (credit_column >= 0),
+    decimal_separator TEXT NOT NULL DEFAULT ',' CHECK (decimal_separator IN (',', '.')),
+    default_expense_category_id TEXT REFERENCES categories(id) ON DELETE SET NULL,
+    default_income_category_id TEXT REFERENCES categories(id) ON DELETE SET NULL,
+    created_at TEXT NOT NULL DEFAULT (datetime('now'))
+);
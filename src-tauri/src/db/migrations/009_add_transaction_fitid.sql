@@ -0,0 +1,5 @@
+-- Bank-assigned transaction IDs (OFX FITID) so re-importing a statement never duplicates rows
+ALTER TABLE transactions ADD COLUMN fitid TEXT;
+
+CREATE UNIQUE INDEX idx_transactions_account_fitid ON transactions(account_id, fitid)
+    WHERE fitid IS NOT NULL;
@@ -0,0 +1,2 @@
+-- Undo 010_create_budgets
+DROP TABLE budgets;
@@ -0,0 +1,9 @@
+-- Monthly spending limits per expense category (parent or subcategory), in base currency
+CREATE TABLE budgets (
+    id TEXT PRIMARY KEY,
+    category_id TEXT NOT NULL UNIQUE REFERENCES categories(id) ON DELETE CASCADE,
+    amount INTEGER NOT NULL CHECK (amount > 0),
+    rollover INTEGER NOT NULL DEFAULT 0,
+    start_month TEXT NOT NULL,
+    created_at TEXT NOT NULL DEFAULT (datetime('now'))
+);
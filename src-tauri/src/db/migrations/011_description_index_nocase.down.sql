@@ -0,0 +1,4 @@
+-- Undo 011_description_index_nocase: back to the case-sensitive index
+DROP INDEX IF EXISTS idx_transactions_description;
+
+CREATE INDEX idx_transactions_description ON transactions(description);
@@ -0,0 +1,4 @@
+-- Case-insensitive description index so prefix searches (LIKE 'abc%') for autocomplete can use it
+DROP INDEX IF EXISTS idx_transactions_description;
+
+CREATE INDEX idx_transactions_description ON transactions(description COLLATE NOCASE);
@@ -0,0 +1,2 @@
+-- Undo 012_create_settings
+DROP TABLE settings;
@@ -0,0 +1,3 @@
+-- Undo 013_credit_card_statements
+DROP TABLE statement_payments;
+ALTER TABLE accounts DROP COLUMN payment_due_day;
@@ -0,0 +1,12 @@
+-- Credit-card statements: the day each statement is due, and the transfers that paid them
+ALTER TABLE accounts ADD COLUMN payment_due_day INTEGER CHECK (payment_due_day BETWEEN 1 AND 31);
+
+CREATE TABLE statement_payments (
+    id TEXT PRIMARY KEY,
+    account_id TEXT NOT NULL REFERENCES accounts(id),
+    closing_date TEXT NOT NULL,               -- YYYY-MM-DD, identifies the statement
+    transfer_id TEXT NOT NULL UNIQUE REFERENCES transfers(id) ON DELETE CASCADE,
+    created_at TEXT NOT NULL DEFAULT (datetime('now'))
+);
+
+CREATE INDEX idx_statement_payments_account ON statement_payments(account_id, closing_date);
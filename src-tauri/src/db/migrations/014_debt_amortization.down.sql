@@ -0,0 +1,5 @@
+-- Undo 014_debt_amortization
+ALTER TABLE installments DROP COLUMN balance;
+ALTER TABLE installments DROP COLUMN interest;
+ALTER TABLE installments DROP COLUMN principal;
+ALTER TABLE debts DROP COLUMN rate_type;
@@ -0,0 +1,18 @@
+-- Amortization: how a debt's interest rate is quoted, and the principal/interest split and
+-- remaining balance of each installment
+ALTER TABLE debts ADD COLUMN rate_type TEXT NOT NULL DEFAULT 'monthly'
+    CHECK (rate_type IN ('monthly', 'annual', 'cae'));
+
+ALTER TABLE installments ADD COLUMN principal INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE installments ADD COLUMN interest INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE installments ADD COLUMN balance INTEGER NOT NULL DEFAULT 0;
+
+-- Existing installments never modelled interest: each one repays its whole amount
+UPDATE installments SET
+    principal = amount,
+    balance = (
+        SELECT COALESCE(SUM(later.amount), 0)
+        FROM installments later
+        WHERE later.debt_id = installments.debt_id
+          AND later.installment_number > installments.installment_number
+    );
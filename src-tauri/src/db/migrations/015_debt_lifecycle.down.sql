@@ -0,0 +1,3 @@
+-- Undo 015_debt_lifecycle
+DROP TABLE debt_status_history;
+ALTER TABLE debts DROP COLUMN status;
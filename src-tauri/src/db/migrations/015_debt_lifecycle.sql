@@ -0,0 +1,20 @@
+-- Debt lifecycle: the state of each debt and when and why it changed
+ALTER TABLE debts ADD COLUMN status TEXT NOT NULL DEFAULT 'active'
+    CHECK (status IN ('active', 'paid_off', 'defaulted', 'refinanced'));
+
+CREATE TABLE debt_status_history (
+    id TEXT PRIMARY KEY,
+    debt_id TEXT NOT NULL REFERENCES debts(id) ON DELETE CASCADE,
+    from_status TEXT,                         -- NULL for the entry recording the debt's creation
+    to_status TEXT NOT NULL,
+    reason TEXT NOT NULL CHECK (reason IN ('created', 'last_installment_paid', 'early_payoff', 'payment_undone', 'manual')),
+    note TEXT,
+    changed_at TEXT NOT NULL DEFAULT (datetime('now'))
+);
+
+CREATE INDEX idx_debt_status_history_debt ON debt_status_history(debt_id, changed_at);
+
+-- Debts whose installments are all paid are already paid off. Their history starts here
+UPDATE debts SET status = 'paid_off', is_active = 0
+WHERE EXISTS (SELECT 1 FROM installments i WHERE i.debt_id = debts.id)
+  AND NOT EXISTS (SELECT 1 FROM installments i WHERE i.debt_id = debts.id AND i.status <> 'paid');
@@ -0,0 +1,30 @@
+-- Undo 016_installment_payments: late and partial payments count as plain paid ones
+CREATE TABLE installments_old (
+    id TEXT PRIMARY KEY,
+    debt_id TEXT NOT NULL REFERENCES debts(id) ON DELETE CASCADE,
+    installment_number INTEGER NOT NULL,
+    due_date TEXT NOT NULL,
+    amount INTEGER NOT NULL,
+    status TEXT NOT NULL DEFAULT 'pending' CHECK (status IN ('pending', 'paid')),
+    actual_payment_date TEXT,
+    transaction_id TEXT REFERENCES transactions(id),
+    created_at TEXT NOT NULL DEFAULT (datetime('now')),
+    principal INTEGER NOT NULL DEFAULT 0,
+    interest INTEGER NOT NULL DEFAULT 0,
+    balance INTEGER NOT NULL DEFAULT 0,
+    UNIQUE(debt_id, installment_number)
+);
+
+INSERT INTO installments_old (id, debt_id, installment_number, due_date, amount, status,
+    actual_payment_date, transaction_id, created_at, principal, interest, balance)
+SELECT id, debt_id, installment_number, due_date, amount,
+    CASE WHEN status = 'pending' THEN 'pending' ELSE 'paid' END,
+    actual_payment_date, transaction_id, created_at, principal, interest, balance
+FROM installments;
+
+DROP TABLE installments;
+ALTER TABLE installments_old RENAME TO installments;
+
+CREATE INDEX idx_installments_debt ON installments(debt_id);
+CREATE INDEX idx_installments_status ON installments(status);
+CREATE INDEX idx_installments_due_date ON installments(due_date);
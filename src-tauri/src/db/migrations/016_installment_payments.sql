@@ -0,0 +1,35 @@
+-- Installment payments: what was actually paid. Paying more than the installment (a late fee)
+-- or less (a partial payment) gets its own status. SQLite cannot change a CHECK constraint in
+-- place, so the table is rebuilt
+CREATE TABLE installments_new (
+    id TEXT PRIMARY KEY,
+    debt_id TEXT NOT NULL REFERENCES debts(id) ON DELETE CASCADE,
+    installment_number INTEGER NOT NULL,
+    due_date TEXT NOT NULL,
+    amount INTEGER NOT NULL,
+    status TEXT NOT NULL DEFAULT 'pending'
+        CHECK (status IN ('pending', 'paid', 'paid_late', 'partial')),
+    actual_payment_date TEXT,
+    transaction_id TEXT REFERENCES transactions(id),
+    created_at TEXT NOT NULL DEFAULT (datetime('now')),
+    principal INTEGER NOT NULL DEFAULT 0,
+    interest INTEGER NOT NULL DEFAULT 0,
+    balance INTEGER NOT NULL DEFAULT 0,
+    paid_amount INTEGER,                      -- NULL while pending
+    UNIQUE(debt_id, installment_number)
+);
+
+-- Installments paid so far were always paid in full
+INSERT INTO installments_new (id, debt_id, installment_number, due_date, amount, status,
+    actual_payment_date, transaction_id, created_at, principal, interest, balance, paid_amount)
+SELECT id, debt_id, installment_number, due_date, amount, status,
+    actual_payment_date, transaction_id, created_at, principal, interest, balance,
+    CASE WHEN status = 'paid' THEN amount END
+FROM installments;
+
+DROP TABLE installments;
+ALTER TABLE installments_new RENAME TO installments;
+
+CREATE INDEX idx_installments_debt ON installments(debt_id);
+CREATE INDEX idx_installments_status ON installments(status);
+CREATE INDEX idx_installments_due_date ON installments(due_date);
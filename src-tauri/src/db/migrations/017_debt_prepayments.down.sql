@@ -0,0 +1,2 @@
+-- Undo 017_debt_prepayments
+DROP TABLE debt_prepayments;
@@ -0,0 +1,11 @@
+-- Debt prepayments: the expense transaction of each prepayment, so it is not billed as a
+-- purchase and cannot be deleted without its debt
+CREATE TABLE debt_prepayments (
+    id TEXT PRIMARY KEY,
+    debt_id TEXT NOT NULL REFERENCES debts(id) ON DELETE CASCADE,
+    transaction_id TEXT NOT NULL UNIQUE REFERENCES transactions(id),
+    mode TEXT NOT NULL CHECK (mode IN ('shorter_term', 'lower_payment')),
+    created_at TEXT NOT NULL DEFAULT (datetime('now'))
+);
+
+CREATE INDEX idx_debt_prepayments_debt ON debt_prepayments(debt_id);
@@ -0,0 +1,11 @@
+-- Undo 018_installment_partial_payments: an installment keeps only its latest payment, and
+-- paying more or less than it is what makes it late or partial again
+DROP TABLE installment_payments;
+
+UPDATE installments
+SET status = CASE
+        WHEN paid_amount > amount THEN 'paid_late'
+        WHEN paid_amount < amount THEN 'partial'
+        ELSE 'paid'
+    END
+WHERE status <> 'pending';
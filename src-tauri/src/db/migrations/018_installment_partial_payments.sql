@@ -0,0 +1,24 @@
+-- Installment payments: every transaction paying towards an installment. A partially paid
+-- installment stays payable until its payments add up to its amount, and it is late when
+-- completed after its due date rather than when paid with a surcharge
+CREATE TABLE installment_payments (
+    transaction_id TEXT PRIMARY KEY REFERENCES transactions(id),
+    installment_id TEXT NOT NULL REFERENCES installments(id) ON DELETE CASCADE,
+    created_at TEXT NOT NULL DEFAULT (datetime('now'))
+);
+
+CREATE INDEX idx_installment_payments_installment ON installment_payments(installment_id);
+
+-- Installments paid so far were paid by a single transaction
+INSERT INTO installment_payments (transaction_id, installment_id)
+SELECT i.transaction_id, i.id
+FROM installments i
+JOIN transactions t ON t.id = i.transaction_id;
+
+UPDATE installments
+SET status = CASE
+        WHEN paid_amount < amount THEN 'partial'
+        WHEN actual_payment_date > due_date THEN 'paid_late'
+        ELSE 'paid'
+    END
+WHERE status <> 'pending';
@@ -1,15 +1,58 @@
+mod migrate;
 pub mod models;
 pub mod queries;
 
+pub use migrate::{dry_run_migrations, rollback_last_migration, run_migrations, MIGRATIONS};
+
 use sqlx::sqlite::{SqlitePool, SqlitePoolOptions};
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::{PoisonError, RwLock};
+
+/// File name of the database inside the app data dir.
+pub const DATABASE_FILE: &str = "necronomics.db";
+
+/// Connection pool managed by Tauri. Commands take a handle with `pool()` on every call, so
+/// restoring a backup can close the pool and swap in one opened on the restored file.
+pub struct Database {
+    path: PathBuf,
+    pool: RwLock<SqlitePool>,
+}
+
+impl Database {
+    pub fn new(path: PathBuf, pool: SqlitePool) -> Self {
+        Self {
+            path,
+            pool: RwLock::new(pool),
+        }
+    }
+
+    /// Path of the database file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Handle to the current pool. Cheap: clones share the same connections.
+    pub fn pool(&self) -> SqlitePool {
+        self.pool
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
+    /// Swap in a new pool, returning the previous one.
+    pub fn replace(&self, pool: SqlitePool) -> SqlitePool {
+        let mut current = self.pool.write().unwrap_or_else(PoisonError::into_inner);
+        std::mem::replace(&mut *current, pool)
+    }
+}
 
-/// Initialize the SQLite database connection pool.
-/// Creates the database file if it doesn't exist.
-pub async fn create_pool(app_data_dir: &PathBuf) -> Result<SqlitePool, sqlx::Error> {
-    fs::create_dir_all(app_data_dir).ok();
-    let db_path = app_data_dir.join("necronomics.db");
+/// Open the connection pool on the database file at `db_path`.
+/// Creates the file and its folder if they don't exist.
+pub async fn create_pool(db_path: &Path) -> Result<SqlitePool, sqlx::Error> {
+    if let Some(dir) = db_path.parent() {
+        fs::create_dir_all(dir).ok();
+    }
     let db_url = format!("sqlite:{}?mode=rwc", db_path.display());
 
     let pool = SqlitePoolOptions::new()
@@ -29,73 +72,3 @@ pub async fn create_pool(app_data_dir: &PathBuf) -> Result<SqlitePool, sqlx::Err
 
     Ok(pool)
 }
-
-/// Run all pending migrations in order.
-/// Creates the `_migrations` tracking table if it doesn't exist.
-pub async fn run_migrations(pool: &SqlitePool) -> Result<(), Box<dyn std::error::Error>> {
-    // Create migrations tracking table
-    sqlx::query(
-        "CREATE TABLE IF NOT EXISTS _migrations (
-            id INTEGER PRIMARY KEY AUTOINCREMENT,
-            name TEXT NOT NULL UNIQUE,
-            applied_at TEXT NOT NULL DEFAULT (datetime('now'))
-        )",
-    )
-    .execute(pool)
-    .await?;
-
-    // Migration files embedded at compile time
-    let migrations: Vec<(&str, &str)> = vec![
-        (
-            "001_initial_schema",
-            include_str!("migrations/001_initial_schema.sql"),
-        ),
-        (
-            "002_seed_currencies",
-            include_str!("migrations/002_seed_currencies.sql"),
-        ),
-        (
-            "003_seed_categories",
-            include_str!("migrations/003_seed_categories.sql"),
-        ),
-        (
-            "004_migrate_category_icons",
-            include_str!("migrations/004_migrate_category_icons.sql"),
-        ),
-        (
-            "005_create_installments",
-            include_str!("migrations/005_create_installments.sql"),
-        ),
-    ];
-
-    for (name, sql) in migrations {
-        // Check if migration has already been applied
-        let applied: Option<(i64,)> = sqlx::query_as("SELECT id FROM _migrations WHERE name = ?")
-            .bind(name)
-            .fetch_optional(pool)
-            .await?;
-
-        if applied.is_none() {
-            // Execute migration SQL (may contain multiple statements)
-            let statements: Vec<&str> = sql
-                .split(';')
-                .map(|s| s.trim())
-                .filter(|s| !s.is_empty())
-                .collect();
-
-            for statement in statements {
-                sqlx::query(statement).execute(pool).await?;
-            }
-
-            // Record migration as applied
-            sqlx::query("INSERT INTO _migrations (name) VALUES (?)")
-                .bind(name)
-                .execute(pool)
-                .await?;
-
-            println!("Applied migration: {name}");
-        }
-    }
-
-    Ok(())
-}
@@ -15,6 +15,9 @@ pub struct Account {
     pub balance: i64,
     pub credit_limit: Option<i64>,
     pub billing_day: Option<i32>,
+    /// Day of the month credit-card statements are due. Unset means a fixed number of days
+    /// after closing.
+    pub payment_due_day: Option<i32>,
     pub is_active: i32,
     pub created_at: String,
 }
@@ -47,6 +50,31 @@ pub struct Currency {
     pub created_at: String,
 }
 
+/// Represents an exchange rate entity from the `exchange_rates` table.
+/// `rate` expresses how many units of the target currency one unit of the source buys.
+#[derive(Debug, FromRow, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ExchangeRate {
+    pub id: String,
+    pub from_currency_id: String,
+    pub to_currency_id: String,
+    pub rate: f64,
+    pub date: String,
+    pub created_at: String,
+}
+
+/// Result of converting an amount between two currencies.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConvertedAmount {
+    pub amount: i64,
+    pub from_currency_id: String,
+    pub to_currency_id: String,
+    pub converted_amount: i64,
+    pub rate: f64,
+    pub rate_date: Option<String>,
+}
+
 /// Represents a transaction entity from the `transactions` table.
 #[derive(Debug, FromRow, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
@@ -62,9 +90,58 @@ pub struct Transaction {
     pub description: String,
     pub date: String,
     pub notes: Option<String>,
+    /// Set when the transaction was generated by a recurring rule.
+    pub recurring_id: Option<String>,
+    /// Set when the transaction is one leg of a transfer between accounts.
+    pub transfer_id: Option<String>,
+    /// Bank-assigned ID of an imported statement line (OFX FITID).
+    pub fitid: Option<String>,
     pub created_at: String,
 }
 
+/// Represents a tag entity from the `tags` table.
+#[derive(Debug, FromRow, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Tag {
+    pub id: String,
+    pub name: String,
+    pub color: String,
+    pub created_at: String,
+}
+
+/// A transaction together with the tags assigned to it.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionWithTags {
+    #[serde(flatten)]
+    pub transaction: Transaction,
+    pub tags: Vec<Tag>,
+}
+
+/// A past description matching an autocomplete prefix, with the values most often used
+/// with it so the transaction form can be prefilled.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DescriptionSuggestion {
+    pub description: String,
+    pub category_id: String,
+    pub account_id: String,
+    /// Most frequent amount, in the account's minor units.
+    pub amount: i64,
+    #[serde(rename = "type")]
+    pub transaction_type: String,
+    pub use_count: i64,
+    pub last_used: String,
+}
+
+/// Represents a row of the `transaction_tags` join table.
+#[derive(Debug, FromRow, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionTag {
+    pub transaction_id: String,
+    pub tag_id: String,
+}
+
 /// Filter parameters for listing transactions with dynamic conditions.
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -77,6 +154,10 @@ pub struct TransactionFilter {
     pub amount_min: Option<i64>,
     pub amount_max: Option<i64>,
     pub search: Option<String>,
+    /// Only include transactions carrying these tags.
+    pub tag_ids: Option<Vec<String>>,
+    /// How `tag_ids` is matched: `"any"` (default) or `"all"`; anything else is rejected.
+    pub tag_match: Option<String>,
     pub sort_by: Option<String>,
     pub sort_dir: Option<String>,
     pub page: Option<i64>,
@@ -116,6 +197,16 @@ pub struct BalanceSummary {
     pub base_currency_code: String,
 }
 
+/// Rate type of debts exported before rates could be quoted annually.
+fn default_rate_type() -> String {
+    "monthly".to_string()
+}
+
+/// Lifecycle state of debts exported before debts had one.
+fn default_debt_status() -> String {
+    "active".to_string()
+}
+
 /// Represents a debt entity from the `debts` table.
 #[derive(Debug, FromRow, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
@@ -128,12 +219,57 @@ pub struct Debt {
     pub paid_installments: i32,
     pub monthly_payment: i64,
     pub interest_rate: f64,
+    /// How `interest_rate` is quoted: "monthly", "annual" or "cae".
+    #[serde(default = "default_rate_type")]
+    pub rate_type: String,
     pub start_date: String,
     pub is_active: i32,
+    /// Lifecycle state: `active`, `paid_off`, `defaulted` or `refinanced`. Debts leaving the
+    /// active and defaulted states get `is_active = 0`.
+    #[serde(default = "default_debt_status")]
+    pub status: String,
     pub notes: Option<String>,
     pub created_at: String,
 }
 
+/// Represents a row of the `debt_prepayments` table: the expense that prepaid part of a debt.
+#[derive(Debug, FromRow, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct DebtPrepayment {
+    pub id: String,
+    pub debt_id: String,
+    pub transaction_id: String,
+    /// `shorter_term` or `lower_payment`.
+    pub mode: String,
+    pub created_at: String,
+}
+
+/// Represents a row of the `installment_payments` table: a transaction paying towards an
+/// installment.
+#[derive(Debug, FromRow, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct InstallmentPayment {
+    pub transaction_id: String,
+    pub installment_id: String,
+    pub created_at: String,
+}
+
+/// Represents a row of the `debt_status_history` table: one change of a debt's state.
+#[derive(Debug, FromRow, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct DebtStatusChange {
+    pub id: String,
+    pub debt_id: String,
+    /// `None` for the entry recording the debt's creation.
+    pub from_status: Option<String>,
+    pub to_status: String,
+    /// Why it changed: `created`, `last_installment_paid`, `early_payoff`, `payment_undone`
+    /// or `manual`.
+    pub reason: String,
+    pub note: Option<String>,
+    pub changed_at: String,
+}
+
 /// Represents an installment entity from the `installments` table.
 #[derive(Debug, FromRow, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
@@ -143,8 +279,22 @@ pub struct Installment {
     pub installment_number: i32,
     pub due_date: String,
     pub amount: i64,
+    #[serde(default)]
+    pub principal: i64,
+    #[serde(default)]
+    pub interest: i64,
+    /// Principal still owed once this installment is paid.
+    #[serde(default)]
+    pub balance: i64,
+    /// `pending`, `partial` (paid short, still payable), `paid` or `paid_late` (completed
+    /// after the due date).
     pub status: String,
+    /// What has been paid so far, any excess over `amount` being a late fee; `None` while
+    /// pending.
+    pub paid_amount: Option<i64>,
+    /// Date of the latest payment.
     pub actual_payment_date: Option<String>,
+    /// The latest payment; every payment is in `installment_payments`.
     pub transaction_id: Option<String>,
     pub created_at: String,
 }
@@ -155,6 +305,8 @@ pub struct Installment {
 pub struct DebtFilter {
     pub account_id: Option<String>,
     pub is_active: Option<bool>,
+    /// Lifecycle states to include; all when absent or empty.
+    pub statuses: Option<Vec<String>>,
     pub search: Option<String>,
 }
 
@@ -167,6 +319,8 @@ pub struct DebtWithInstallments {
     pub account_name: String,
     pub next_due_date: Option<String>,
     pub remaining_amount: i64,
+    pub interest_paid: i64,
+    pub interest_remaining: i64,
 }
 
 /// Credit utilization for a credit-card account.
@@ -203,26 +357,392 @@ pub struct MonthlyProjection {
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct CreateDebtInput {
-    pub account_id: String,
+    /// Defaults to the account set in settings.
+    pub account_id: Option<String>,
     pub description: String,
     pub original_amount: i64,
     pub total_installments: i32,
-    pub monthly_payment: i64,
+    /// Installment amount quoted by the lender. Computed from the rate and term when absent.
+    pub monthly_payment: Option<i64>,
     pub interest_rate: f64,
+    /// How `interest_rate` is quoted: "monthly" (default), "annual" or "cae".
+    pub rate_type: Option<String>,
     pub start_date: String,
     pub notes: Option<String>,
 }
 
+/// How an installment was paid. Every field is optional.
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PayInstallmentInput {
+    /// Defaults to the debt-payment category from settings.
+    pub category_id: Option<String>,
+    /// Date of the payment (YYYY-MM-DD); today when absent. Payments cannot be dated ahead.
+    pub date: Option<String>,
+    /// Amount actually paid; what is still owed on the installment when absent. Paying less
+    /// leaves the rest owed, paying more records the excess as a late fee.
+    pub amount: Option<i64>,
+    /// Account the payment comes out of, in the debt's currency; the debt's own account when
+    /// absent.
+    pub source_account_id: Option<String>,
+}
+
+/// Input for prepaying part of a debt's outstanding principal.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PrepayDebtInput {
+    pub debt_id: String,
+    pub amount: i64,
+    /// `shorter_term` keeps the installment amount and drops installments from the end;
+    /// `lower_payment` keeps the remaining installments and lowers their amount.
+    pub mode: String,
+    /// Defaults to the debt-payment category from settings.
+    pub category_id: Option<String>,
+}
+
 /// Input for updating an existing debt (only metadata fields).
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct UpdateDebtInput {
     pub description: Option<String>,
     pub interest_rate: Option<f64>,
+    pub notes: Option<String>,
+}
+
+/// Represents a recurring transaction rule from the `recurring_transactions` table.
+#[derive(Debug, FromRow, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RecurringTransaction {
+    pub id: String,
+    pub account_id: String,
+    pub category_id: String,
+    pub amount: i64,
+    /// Mapped from the `type` column (Rust keyword). Use sqlx rename + serde rename.
+    #[sqlx(rename = "type")]
+    #[serde(rename = "type")]
+    pub transaction_type: String,
+    pub description: String,
+    /// One of `weekly`, `biweekly`, `monthly`, `yearly`.
+    pub frequency: String,
+    pub next_date: String,
+    /// Day of month used by monthly/yearly rules, clamped to shorter months.
+    pub anchor_day: i32,
+    pub end_date: Option<String>,
+    pub is_active: i32,
+    pub created_at: String,
+}
+
+/// Input for creating a new recurring transaction rule.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateRecurringInput {
+    pub account_id: String,
+    pub category_id: String,
+    pub amount: i64,
+    pub transaction_type: String,
+    pub description: String,
+    pub frequency: String,
+    pub start_date: String,
+    pub end_date: Option<String>,
+}
+
+/// Input for updating a recurring transaction rule.
+/// Changing `next_date` also moves the monthly/yearly anchor day to that date.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateRecurringInput {
+    pub account_id: Option<String>,
+    pub category_id: Option<String>,
+    pub amount: Option<i64>,
+    pub description: Option<String>,
+    pub frequency: Option<String>,
+    pub next_date: Option<String>,
+    pub end_date: Option<String>,
     pub is_active: Option<bool>,
+}
+
+/// Transactions materialized from a single recurring rule during one processing run.
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RecurringGenerated {
+    pub recurring_id: String,
+    pub description: String,
+    pub account_id: String,
+    pub amount: i64,
+    pub transaction_type: String,
+    pub dates: Vec<String>,
+}
+
+/// Summary of a recurring processing run, shown to the user after startup.
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RecurringRunSummary {
+    pub run_date: String,
+    pub generated_count: i64,
+    pub rules: Vec<RecurringGenerated>,
+}
+
+/// Represents a row of the `transfers` table. The legs live in `transactions`.
+#[derive(Debug, FromRow, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct TransferRecord {
+    pub id: String,
+    pub rate: f64,
+    pub created_at: String,
+}
+
+/// A transfer between two accounts, assembled from its outgoing and incoming legs.
+#[derive(Debug, FromRow, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Transfer {
+    pub id: String,
+    pub from_transaction_id: String,
+    pub to_transaction_id: String,
+    pub from_account_id: String,
+    pub to_account_id: String,
+    /// Amount leaving the source account, in its currency's minor units.
+    pub from_amount: i64,
+    /// Amount arriving at the destination account, in its currency's minor units.
+    pub to_amount: i64,
+    /// Destination units per source unit. Always 1.0 for same-currency transfers.
+    pub rate: f64,
+    pub description: String,
+    pub date: String,
+    pub notes: Option<String>,
+    pub created_at: String,
+}
+
+/// Input for creating a transfer. `rate` is required when the accounts use different currencies.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateTransferInput {
+    pub from_account_id: String,
+    pub to_account_id: String,
+    pub amount: i64,
+    pub rate: Option<f64>,
+    pub description: Option<String>,
+    pub date: String,
+    pub notes: Option<String>,
+}
+
+/// Input for updating a transfer. Both legs are rewritten from the resulting values.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateTransferInput {
+    pub from_account_id: Option<String>,
+    pub to_account_id: Option<String>,
+    pub amount: Option<i64>,
+    pub rate: Option<f64>,
+    pub description: Option<String>,
+    pub date: Option<String>,
     pub notes: Option<String>,
 }
 
+/// Represents a saved CSV column mapping from the `csv_import_profiles` table.
+/// Column indexes are zero-based.
+#[derive(Debug, FromRow, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CsvImportProfile {
+    pub id: String,
+    pub name: String,
+    pub delimiter: String,
+    /// Header/preamble rows skipped before the first data row.
+    pub skip_rows: i32,
+    pub date_column: i32,
+    /// chrono format string, e.g. `%d/%m/%Y`.
+    pub date_format: String,
+    pub description_column: i32,
+    /// One of `negative_is_expense`, `positive_is_expense`, `debit_credit_columns`.
+    pub sign_convention: String,
+    /// Signed amount column, used unless the convention is `debit_credit_columns`.
+    pub amount_column: Option<i32>,
+    pub debit_column: Option<i32>,
+    pub credit_column: Option<i32>,
+    /// Either `,` or `.`; the other character is treated as a thousands separator.
+    pub decimal_separator: String,
+    pub default_expense_category_id: Option<String>,
+    pub default_income_category_id: Option<String>,
+    pub created_at: String,
+}
+
+/// Input for creating or updating (full replacement) a CSV import profile.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CsvImportProfileInput {
+    pub name: String,
+    pub delimiter: String,
+    pub skip_rows: i32,
+    pub date_column: i32,
+    pub date_format: String,
+    pub description_column: i32,
+    pub sign_convention: String,
+    pub amount_column: Option<i32>,
+    pub debit_column: Option<i32>,
+    pub credit_column: Option<i32>,
+    pub decimal_separator: String,
+    pub default_expense_category_id: Option<String>,
+    pub default_income_category_id: Option<String>,
+}
+
+/// A parsed CSV line as shown in the import preview.
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CsvPreviewRow {
+    /// 1-based line number in the source file.
+    pub line_number: i64,
+    pub date: Option<String>,
+    /// Absolute amount in the account currency's minor units.
+    pub amount: Option<i64>,
+    pub transaction_type: Option<String>,
+    pub description: String,
+    /// Suggested category from the profile defaults.
+    pub category_id: Option<String>,
+    /// An existing transaction on the account has the same date, amount and description.
+    pub is_duplicate: bool,
+    /// Set when the line could not be parsed; such rows cannot be imported.
+    pub error: Option<String>,
+}
+
+/// Result of previewing a CSV file against a profile and an account.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CsvImportPreview {
+    pub account_id: String,
+    pub profile_id: String,
+    pub rows: Vec<CsvPreviewRow>,
+    pub valid_count: i64,
+    pub duplicate_count: i64,
+    pub error_count: i64,
+}
+
+/// A reviewed row sent back by the UI to be imported.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CsvImportRow {
+    pub date: String,
+    pub amount: i64,
+    pub transaction_type: String,
+    pub description: String,
+    pub category_id: String,
+}
+
+/// Result of committing a CSV import.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CsvImportResult {
+    pub account_id: String,
+    pub inserted_count: i64,
+    pub balance: i64,
+}
+
+/// A parsed OFX/QFX statement line as shown in the import preview.
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct OfxPreviewRow {
+    pub fitid: String,
+    pub date: String,
+    /// Absolute amount in the account currency's minor units.
+    pub amount: Option<i64>,
+    pub transaction_type: Option<String>,
+    pub description: String,
+    /// A transaction with the same FITID was already imported into the account.
+    pub is_duplicate: bool,
+    /// Set when the amount could not be parsed; such rows are not imported.
+    pub error: Option<String>,
+}
+
+/// Result of previewing an OFX/QFX file against an account.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OfxImportPreview {
+    pub account_id: String,
+    /// Currency declared by the statement (`CURDEF`).
+    pub currency: Option<String>,
+    /// Account number declared by the statement (`ACCTID`).
+    pub account_number: Option<String>,
+    pub rows: Vec<OfxPreviewRow>,
+    pub new_count: i64,
+    pub duplicate_count: i64,
+    pub error_count: i64,
+}
+
+/// Result of importing an OFX/QFX file.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OfxImportResult {
+    pub account_id: String,
+    pub inserted_count: i64,
+    /// Lines whose FITID was already present on the account.
+    pub skipped_count: i64,
+    pub balance: i64,
+}
+
+/// Represents a row in the `budgets` table: a monthly limit for one expense category.
+/// A budget on a parent category covers spending in all of its subcategories.
+#[derive(Debug, FromRow, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Budget {
+    pub id: String,
+    pub category_id: String,
+    /// Monthly limit in base currency minor units.
+    pub amount: i64,
+    /// When 1, the unspent part of each month is added to the next month's limit.
+    pub rollover: i32,
+    /// First month (YYYY-MM) the budget applies to, and where rollover starts accumulating.
+    pub start_month: String,
+    pub created_at: String,
+}
+
+/// Input for creating a budget. `start_month` defaults to the current month.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateBudgetInput {
+    pub category_id: String,
+    pub amount: i64,
+    pub rollover: Option<bool>,
+    pub start_month: Option<String>,
+}
+
+/// Input for updating a budget. The category is locked after creation.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateBudgetInput {
+    pub amount: Option<i64>,
+    pub rollover: Option<bool>,
+    pub start_month: Option<String>,
+}
+
+/// Spending against one budget in a given month, in base currency.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BudgetStatus {
+    pub budget_id: String,
+    pub category_id: String,
+    pub category_name: String,
+    /// Monthly limit as configured.
+    pub amount: i64,
+    /// Unspent amount carried over from previous months (0 without rollover).
+    pub rollover_amount: i64,
+    /// `amount + rollover_amount`.
+    pub available: i64,
+    pub spent: i64,
+    /// Negative when the budget is overspent.
+    pub remaining: i64,
+    /// Spent as a percentage of `available`.
+    pub percentage: f64,
+    pub is_overspent: bool,
+}
+
+/// Status of every budget for one month, returned by the `get_budget_status` command.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BudgetMonthStatus {
+    /// YYYY-MM
+    pub month: String,
+    pub base_currency_code: String,
+    pub budgets: Vec<BudgetStatus>,
+}
+
 /// Dashboard aggregate data returned by the get_dashboard_data command.
 #[derive(Debug, Serialize)]
 #[serde(rename_all = "camelCase")]
@@ -233,17 +753,49 @@ pub struct DashboardData {
     pub recent_transactions: Vec<RecentTransaction>,
 }
 
-/// Income and expense totals for the current month, converted to base currency.
+/// Selection of the period the dashboard aggregates over.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DashboardPeriod {
+    /// One of `this_month`, `last_month`, `last_n_months`, `year_to_date`, `custom`.
+    pub period_type: String,
+    /// Number of months for `last_n_months`, counting the current one.
+    pub months: Option<u32>,
+    /// Inclusive bounds (YYYY-MM-DD) for `custom`.
+    pub date_from: Option<String>,
+    pub date_to: Option<String>,
+}
+
+/// A resolved dashboard period.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PeriodInfo {
+    pub period_type: String,
+    /// First day of the period (YYYY-MM-DD).
+    pub date_from: String,
+    /// Last day of the period, inclusive (YYYY-MM-DD).
+    pub date_to: String,
+    /// Human-readable description, e.g. "Marzo 2025".
+    pub label: String,
+}
+
+/// Income and expense totals for the selected period, converted to base currency,
+/// compared against the previous equivalent period.
 #[derive(Debug, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct MonthlyIncomeExpense {
     pub income: i64,
     pub expense: i64,
-    pub month_name: String,
-    pub year: i32,
+    pub previous_income: i64,
+    pub previous_expense: i64,
+    /// Percentage change against the previous period; `None` when that period had none.
+    pub income_change: Option<f64>,
+    pub expense_change: Option<f64>,
+    pub period: PeriodInfo,
+    pub previous_period: PeriodInfo,
 }
 
-/// A category's spending amount and proportion of total spending for the month.
+/// A category's spending amount and proportion of total spending for the period.
 #[derive(Debug, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct CategorySpending {
@@ -251,6 +803,10 @@ pub struct CategorySpending {
     pub category_name: String,
     pub amount: i64,
     pub percentage: f64,
+    /// Spending in the same category during the previous period.
+    pub previous_amount: i64,
+    /// Percentage change against the previous period; `None` when that period had none.
+    pub change: Option<f64>,
 }
 
 /// A recent transaction with joined account and category names for display.
@@ -266,3 +822,333 @@ pub struct RecentTransaction {
     pub date: String,
     pub currency_code: String,
 }
+
+/// Optional filters for the monthly income/expense comparison.
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MonthlyComparisonFilter {
+    pub account_id: Option<String>,
+    /// Matches the category itself and its subcategories.
+    pub category_id: Option<String>,
+}
+
+/// Income, expense and net of one month, in base currency.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MonthlyComparisonPoint {
+    /// YYYY-MM
+    pub month: String,
+    pub label: String,
+    pub income: i64,
+    pub expense: i64,
+    pub net: i64,
+}
+
+/// Month-by-month income vs expense series, oldest month first.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MonthlyComparison {
+    pub base_currency_code: String,
+    pub months: Vec<MonthlyComparisonPoint>,
+}
+
+/// An account or consolidated balance at the end of a sampled day.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BalancePoint {
+    pub date: String,
+    pub balance: i64,
+}
+
+/// Balance series of one account, in its own currency.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountBalanceHistory {
+    pub account_id: String,
+    pub account_name: String,
+    pub currency_code: String,
+    pub points: Vec<BalancePoint>,
+}
+
+/// Reconstructed balances per account plus a consolidated line in base currency.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BalanceHistory {
+    /// One of `daily`, `weekly`, `monthly`.
+    pub granularity: String,
+    pub base_currency_code: String,
+    pub accounts: Vec<AccountBalanceHistory>,
+    /// Sum of every account converted with the rate in effect on each date.
+    /// Accounts whose currency has no rate at all are left out.
+    pub consolidated: Vec<BalancePoint>,
+}
+
+/// Row counts per table, used by export metadata and import summaries.
+#[derive(Debug, Serialize, Deserialize, Default, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct TableCounts {
+    pub currencies: i64,
+    pub accounts: i64,
+    pub categories: i64,
+    pub transactions: i64,
+    pub tags: i64,
+    pub transaction_tags: i64,
+    pub recurring_transactions: i64,
+    #[serde(default)]
+    pub transfers: i64,
+    #[serde(default)]
+    pub statement_payments: i64,
+    #[serde(default)]
+    pub csv_import_profiles: i64,
+    #[serde(default)]
+    pub budgets: i64,
+    pub debts: i64,
+    pub installments: i64,
+    #[serde(default)]
+    pub installment_payments: i64,
+    #[serde(default)]
+    pub debt_status_history: i64,
+    #[serde(default)]
+    pub debt_prepayments: i64,
+    pub exchange_rates: i64,
+    #[serde(default)]
+    pub settings: i64,
+}
+
+/// Every exported table, one vector of rows each.
+#[derive(Debug, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportData {
+    pub currencies: Vec<Currency>,
+    pub accounts: Vec<Account>,
+    pub categories: Vec<Category>,
+    pub transactions: Vec<Transaction>,
+    pub tags: Vec<Tag>,
+    pub transaction_tags: Vec<TransactionTag>,
+    #[serde(default)]
+    pub recurring_transactions: Vec<RecurringTransaction>,
+    #[serde(default)]
+    pub transfers: Vec<TransferRecord>,
+    #[serde(default)]
+    pub statement_payments: Vec<StatementPayment>,
+    #[serde(default)]
+    pub csv_import_profiles: Vec<CsvImportProfile>,
+    #[serde(default)]
+    pub budgets: Vec<Budget>,
+    pub debts: Vec<Debt>,
+    pub installments: Vec<Installment>,
+    #[serde(default)]
+    pub installment_payments: Vec<InstallmentPayment>,
+    #[serde(default)]
+    pub debt_status_history: Vec<DebtStatusChange>,
+    #[serde(default)]
+    pub debt_prepayments: Vec<DebtPrepayment>,
+    pub exchange_rates: Vec<ExchangeRate>,
+    #[serde(default)]
+    pub settings: Vec<Setting>,
+}
+
+/// Versioned JSON backup document produced by `export_data` and read by `import_data`.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportDocument {
+    /// Version of the document layout itself.
+    pub format_version: u32,
+    pub app_version: String,
+    /// Name of the latest migration applied to the exporting database.
+    pub schema_version: String,
+    pub exported_at: String,
+    pub counts: TableCounts,
+    pub data: ExportData,
+}
+
+/// Result of writing an export file.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportSummary {
+    pub path: String,
+    pub schema_version: String,
+    pub exported_at: String,
+    pub counts: TableCounts,
+}
+
+/// Result of an import: rows written and rows skipped because their ID already existed.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportSummary {
+    pub mode: String,
+    pub schema_version: String,
+    pub inserted: TableCounts,
+    pub skipped: TableCounts,
+}
+
+/// A database snapshot in the backups folder.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BackupSnapshot {
+    pub file_name: String,
+    /// Local time the snapshot was taken, `YYYY-MM-DD HH:MM:SS`.
+    pub created_at: String,
+    /// `startup`, `pre_migration` or `pre_restore`.
+    pub reason: String,
+    pub size_bytes: u64,
+}
+
+/// A stored preference from the `settings` table, as exported.
+#[derive(Debug, FromRow, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Setting {
+    pub key: String,
+    pub value: String,
+    pub updated_at: String,
+}
+
+/// Application preferences, with defaults filled in for anything never saved.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AppSettings {
+    /// Currency dashboards, budgets and balance summaries are converted to.
+    pub base_currency_id: String,
+    /// Language of backend messages: `es` or `en`.
+    pub locale: String,
+    /// Day weeks start on, as a lowercase English weekday name (e.g. `monday`).
+    pub first_day_of_week: String,
+    /// Account preselected for new debts.
+    pub default_account_id: Option<String>,
+    /// Expense category used for installment payments when none is given.
+    pub default_debt_category_id: Option<String>,
+}
+
+/// Input for `update_settings`. Omitted fields keep their value; an empty string resets a
+/// field to its default (no default account or category).
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateSettingsInput {
+    pub base_currency_id: Option<String>,
+    pub locale: Option<String>,
+    pub first_day_of_week: Option<String>,
+    pub default_account_id: Option<String>,
+    pub default_debt_category_id: Option<String>,
+}
+
+/// An account whose stored balance differs from the sum of its transactions.
+#[derive(Debug, Clone, FromRow, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BalanceDrift {
+    pub account_id: String,
+    pub account_name: String,
+    /// Balance stored in `accounts.balance`.
+    pub stored_balance: i64,
+    /// Balance recomputed from the account's transactions.
+    pub computed_balance: i64,
+}
+
+/// A debt whose `paid_installments` counter disagrees with its paid installments.
+#[derive(Debug, Clone, FromRow, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PaidInstallmentsMismatch {
+    pub debt_id: String,
+    pub description: String,
+    pub stored_count: i32,
+    pub actual_count: i32,
+}
+
+/// An installment linked to a transaction that no longer exists.
+#[derive(Debug, Clone, FromRow, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DanglingInstallment {
+    pub installment_id: String,
+    pub debt_id: String,
+    pub installment_number: i32,
+    pub transaction_id: String,
+}
+
+/// A `transaction_tags` row whose transaction or tag no longer exists.
+#[derive(Debug, Clone, FromRow, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OrphanedTagLink {
+    pub transaction_id: String,
+    pub tag_id: String,
+}
+
+/// Result of `verify_integrity` / `repair_integrity`: every inconsistency found in the
+/// denormalized data. `repaired` tells whether they have been fixed.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IntegrityReport {
+    pub balance_drift: Vec<BalanceDrift>,
+    pub paid_installments_mismatches: Vec<PaidInstallmentsMismatch>,
+    pub dangling_installments: Vec<DanglingInstallment>,
+    pub orphaned_tag_links: Vec<OrphanedTagLink>,
+    pub repaired: bool,
+}
+
+/// Represents a row of the `statement_payments` table: a transfer that paid a credit-card
+/// statement.
+#[derive(Debug, FromRow, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct StatementPayment {
+    pub id: String,
+    pub account_id: String,
+    /// Closing date (YYYY-MM-DD) of the statement paid.
+    pub closing_date: String,
+    pub transfer_id: String,
+    pub created_at: String,
+}
+
+/// An installment billed on a credit-card statement.
+#[derive(Debug, Clone, FromRow, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StatementInstallment {
+    pub installment_id: String,
+    pub debt_id: String,
+    pub debt_description: String,
+    pub installment_number: i32,
+    pub total_installments: i32,
+    pub amount: i64,
+    pub due_date: String,
+    pub status: String,
+    /// Paid from another account than the card, so not billed on it.
+    pub paid_elsewhere: i64,
+}
+
+/// One statement cycle of a credit card: the charges made between the day after the previous
+/// closing and this closing (inclusive), plus the installments due in that span.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Statement {
+    pub account_id: String,
+    /// First day of the cycle (YYYY-MM-DD).
+    pub period_start: String,
+    /// Last day of the cycle, inclusive (YYYY-MM-DD). Identifies the statement.
+    pub closing_date: String,
+    pub due_date: String,
+    /// Whether the closing date has passed, so no more charges can land on it.
+    pub is_closed: bool,
+    /// Purchases and refunds, excluding transfers and installment payments.
+    pub charges: Vec<Transaction>,
+    pub installments: Vec<StatementInstallment>,
+    /// Purchases minus refunds.
+    pub charges_total: i64,
+    /// Installments due, less what was paid on them from other accounts.
+    pub installments_total: i64,
+    /// Amount billed: `charges_total + installments_total`.
+    pub total: i64,
+    /// Sum of the settlements recorded against this statement.
+    pub paid_amount: i64,
+    /// What remains to pay, never negative.
+    pub outstanding: i64,
+}
+
+/// Input for `settle_statement`: pay what remains of a closed statement from another account.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SettleStatementInput {
+    pub account_id: String,
+    pub closing_date: String,
+    /// Account the payment comes from; must use the same currency as the card.
+    pub from_account_id: String,
+    /// Payment date (YYYY-MM-DD); defaults to today.
+    pub date: Option<String>,
+    pub description: Option<String>,
+}
@@ -5,7 +5,8 @@ use crate::db::models::Account;
 /// Get all active accounts, ordered by type then name.
 pub async fn get_all(pool: &SqlitePool) -> Result<Vec<Account>, sqlx::Error> {
     sqlx::query_as::<_, Account>(
-        "SELECT id, name, type, currency_id, balance, credit_limit, billing_day, is_active, created_at
+        "SELECT id, name, type, currency_id, balance, credit_limit, billing_day, payment_due_day,
+                is_active, created_at
          FROM accounts
          WHERE is_active = 1
          ORDER BY type, name",
@@ -17,7 +18,8 @@ pub async fn get_all(pool: &SqlitePool) -> Result<Vec<Account>, sqlx::Error> {
 /// Get a single account by ID (active or not).
 pub async fn get_by_id(pool: &SqlitePool, id: &str) -> Result<Option<Account>, sqlx::Error> {
     sqlx::query_as::<_, Account>(
-        "SELECT id, name, type, currency_id, balance, credit_limit, billing_day, is_active, created_at
+        "SELECT id, name, type, currency_id, balance, credit_limit, billing_day, payment_due_day,
+                is_active, created_at
          FROM accounts
          WHERE id = ?",
     )
@@ -27,6 +29,7 @@ pub async fn get_by_id(pool: &SqlitePool, id: &str) -> Result<Option<Account>, s
 }
 
 /// Create a new account and return it.
+#[allow(clippy::too_many_arguments)]
 pub async fn create(
     pool: &SqlitePool,
     id: &str,
@@ -35,10 +38,11 @@ pub async fn create(
     currency_id: &str,
     credit_limit: Option<i64>,
     billing_day: Option<i32>,
+    payment_due_day: Option<i32>,
 ) -> Result<Account, sqlx::Error> {
     sqlx::query(
-        "INSERT INTO accounts (id, name, type, currency_id, credit_limit, billing_day)
-         VALUES (?, ?, ?, ?, ?, ?)",
+        "INSERT INTO accounts (id, name, type, currency_id, credit_limit, billing_day, payment_due_day)
+         VALUES (?, ?, ?, ?, ?, ?, ?)",
     )
     .bind(id)
     .bind(name)
@@ -46,6 +50,7 @@ pub async fn create(
     .bind(currency_id)
     .bind(credit_limit)
     .bind(billing_day)
+    .bind(payment_due_day)
     .execute(pool)
     .await?;
 
@@ -64,6 +69,7 @@ pub async fn update(
     currency_id: Option<&str>,
     credit_limit: Option<i64>,
     billing_day: Option<i32>,
+    payment_due_day: Option<i32>,
 ) -> Result<Account, sqlx::Error> {
     // Fetch the existing account to preserve unchanged fields
     let existing = get_by_id(pool, id)
@@ -82,16 +88,22 @@ pub async fn update(
     } else {
         existing.billing_day
     };
+    let final_payment_due_day = if payment_due_day.is_some() {
+        payment_due_day
+    } else {
+        existing.payment_due_day
+    };
 
     sqlx::query(
         "UPDATE accounts
-         SET name = ?, currency_id = ?, credit_limit = ?, billing_day = ?
+         SET name = ?, currency_id = ?, credit_limit = ?, billing_day = ?, payment_due_day = ?
          WHERE id = ?",
     )
     .bind(final_name)
     .bind(final_currency)
     .bind(final_credit_limit)
     .bind(final_billing_day)
+    .bind(final_payment_due_day)
     .bind(id)
     .execute(pool)
     .await?;
@@ -0,0 +1,82 @@
+use sqlx::SqlitePool;
+
+use crate::db::models::Budget;
+
+/// Get all budgets ordered by their category name.
+pub async fn get_all(pool: &SqlitePool) -> Result<Vec<Budget>, sqlx::Error> {
+    sqlx::query_as::<_, Budget>(
+        "SELECT b.id, b.category_id, b.amount, b.rollover, b.start_month, b.created_at
+         FROM budgets b
+         JOIN categories c ON b.category_id = c.id
+         ORDER BY c.name",
+    )
+    .fetch_all(pool)
+    .await
+}
+
+/// Get a single budget by ID.
+pub async fn get_by_id(pool: &SqlitePool, id: &str) -> Result<Option<Budget>, sqlx::Error> {
+    sqlx::query_as::<_, Budget>(
+        "SELECT id, category_id, amount, rollover, start_month, created_at
+         FROM budgets
+         WHERE id = ?",
+    )
+    .bind(id)
+    .fetch_optional(pool)
+    .await
+}
+
+/// Check whether a category already has a budget.
+pub async fn exists_for_category(
+    pool: &SqlitePool,
+    category_id: &str,
+) -> Result<bool, sqlx::Error> {
+    let count: (i64,) = sqlx::query_as("SELECT COUNT(*) FROM budgets WHERE category_id = ?")
+        .bind(category_id)
+        .fetch_one(pool)
+        .await?;
+    Ok(count.0 > 0)
+}
+
+/// Create a new budget from a fully-populated struct and return the stored row.
+pub async fn create(pool: &SqlitePool, budget: &Budget) -> Result<Budget, sqlx::Error> {
+    sqlx::query(
+        "INSERT INTO budgets (id, category_id, amount, rollover, start_month)
+         VALUES (?, ?, ?, ?, ?)",
+    )
+    .bind(&budget.id)
+    .bind(&budget.category_id)
+    .bind(budget.amount)
+    .bind(budget.rollover)
+    .bind(&budget.start_month)
+    .execute(pool)
+    .await?;
+
+    get_by_id(pool, &budget.id)
+        .await?
+        .ok_or(sqlx::Error::RowNotFound)
+}
+
+/// Update the limit, rollover flag and start month of a budget and return it.
+pub async fn update(pool: &SqlitePool, budget: &Budget) -> Result<Budget, sqlx::Error> {
+    sqlx::query("UPDATE budgets SET amount = ?, rollover = ?, start_month = ? WHERE id = ?")
+        .bind(budget.amount)
+        .bind(budget.rollover)
+        .bind(&budget.start_month)
+        .bind(&budget.id)
+        .execute(pool)
+        .await?;
+
+    get_by_id(pool, &budget.id)
+        .await?
+        .ok_or(sqlx::Error::RowNotFound)
+}
+
+/// Delete a budget.
+pub async fn delete(pool: &SqlitePool, id: &str) -> Result<(), sqlx::Error> {
+    sqlx::query("DELETE FROM budgets WHERE id = ?")
+        .bind(id)
+        .execute(pool)
+        .await?;
+    Ok(())
+}
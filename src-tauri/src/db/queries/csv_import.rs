@@ -0,0 +1,139 @@
+use sqlx::SqlitePool;
+
+use crate::db::models::{CsvImportProfile, CsvImportProfileInput};
+
+/// Get all CSV import profiles, ordered by name.
+pub async fn get_all(pool: &SqlitePool) -> Result<Vec<CsvImportProfile>, sqlx::Error> {
+    sqlx::query_as::<_, CsvImportProfile>(
+        "SELECT id, name, delimiter, skip_rows, date_column, date_format, description_column,
+                sign_convention, amount_column, debit_column, credit_column, decimal_separator,
+                default_expense_category_id, default_income_category_id, created_at
+         FROM csv_import_profiles
+         ORDER BY name",
+    )
+    .fetch_all(pool)
+    .await
+}
+
+/// Get a single CSV import profile by ID.
+pub async fn get_by_id(
+    pool: &SqlitePool,
+    id: &str,
+) -> Result<Option<CsvImportProfile>, sqlx::Error> {
+    sqlx::query_as::<_, CsvImportProfile>(
+        "SELECT id, name, delimiter, skip_rows, date_column, date_format, description_column,
+                sign_convention, amount_column, debit_column, credit_column, decimal_separator,
+                default_expense_category_id, default_income_category_id, created_at
+         FROM csv_import_profiles
+         WHERE id = ?",
+    )
+    .bind(id)
+    .fetch_optional(pool)
+    .await
+}
+
+/// Get the ID of the profile with the given name, case-insensitively.
+pub async fn get_id_by_name(pool: &SqlitePool, name: &str) -> Result<Option<String>, sqlx::Error> {
+    let row: Option<(String,)> =
+        sqlx::query_as("SELECT id FROM csv_import_profiles WHERE name = ? COLLATE NOCASE")
+            .bind(name)
+            .fetch_optional(pool)
+            .await?;
+    Ok(row.map(|(id,)| id))
+}
+
+/// Create a new CSV import profile and return it.
+pub async fn create(
+    pool: &SqlitePool,
+    id: &str,
+    input: &CsvImportProfileInput,
+) -> Result<CsvImportProfile, sqlx::Error> {
+    sqlx::query(
+        "INSERT INTO csv_import_profiles
+            (id, name, delimiter, skip_rows, date_column, date_format, description_column,
+             sign_convention, amount_column, debit_column, credit_column, decimal_separator,
+             default_expense_category_id, default_income_category_id)
+         VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+    )
+    .bind(id)
+    .bind(&input.name)
+    .bind(&input.delimiter)
+    .bind(input.skip_rows)
+    .bind(input.date_column)
+    .bind(&input.date_format)
+    .bind(input.description_column)
+    .bind(&input.sign_convention)
+    .bind(input.amount_column)
+    .bind(input.debit_column)
+    .bind(input.credit_column)
+    .bind(&input.decimal_separator)
+    .bind(&input.default_expense_category_id)
+    .bind(&input.default_income_category_id)
+    .execute(pool)
+    .await?;
+
+    get_by_id(pool, id).await?.ok_or(sqlx::Error::RowNotFound)
+}
+
+/// Replace every field of a CSV import profile and return it.
+pub async fn update(
+    pool: &SqlitePool,
+    id: &str,
+    input: &CsvImportProfileInput,
+) -> Result<CsvImportProfile, sqlx::Error> {
+    sqlx::query(
+        "UPDATE csv_import_profiles
+         SET name = ?, delimiter = ?, skip_rows = ?, date_column = ?, date_format = ?,
+             description_column = ?, sign_convention = ?, amount_column = ?, debit_column = ?,
+             credit_column = ?, decimal_separator = ?, default_expense_category_id = ?,
+             default_income_category_id = ?
+         WHERE id = ?",
+    )
+    .bind(&input.name)
+    .bind(&input.delimiter)
+    .bind(input.skip_rows)
+    .bind(input.date_column)
+    .bind(&input.date_format)
+    .bind(input.description_column)
+    .bind(&input.sign_convention)
+    .bind(input.amount_column)
+    .bind(input.debit_column)
+    .bind(input.credit_column)
+    .bind(&input.decimal_separator)
+    .bind(&input.default_expense_category_id)
+    .bind(&input.default_income_category_id)
+    .bind(id)
+    .execute(pool)
+    .await?;
+
+    get_by_id(pool, id).await?.ok_or(sqlx::Error::RowNotFound)
+}
+
+/// Delete a CSV import profile.
+pub async fn delete(pool: &SqlitePool, id: &str) -> Result<(), sqlx::Error> {
+    sqlx::query("DELETE FROM csv_import_profiles WHERE id = ?")
+        .bind(id)
+        .execute(pool)
+        .await?;
+    Ok(())
+}
+
+/// Get (date, amount, type, description) of an account's transactions within a date range,
+/// used to flag likely duplicates in an import preview.
+pub async fn get_existing_keys(
+    pool: &SqlitePool,
+    account_id: &str,
+    date_from: &str,
+    date_to: &str,
+) -> Result<Vec<(String, i64, String, String)>, sqlx::Error> {
+    sqlx::query_as::<_, (String, i64, String, String)>(
+        "SELECT date, amount, type, description
+         FROM transactions
+         WHERE account_id = ? AND date >= ? AND date <= ?",
+    )
+    .bind(account_id)
+    .bind(date_from)
+    .bind(date_to)
+    .fetch_all(pool)
+    .await
+}
@@ -1,32 +1,145 @@
-use sqlx::SqlitePool;
+use sqlx::sqlite::Sqlite;
+use sqlx::{QueryBuilder, SqlitePool};
 
-/// Fetch all transactions for the current month with their currency code.
-/// Returns (amount, type, currency_code) tuples.
+use crate::db::models::MonthlyComparisonFilter;
+
+/// Fetch all transactions dated in `[date_from, date_to)` with their currency code.
+/// Transfer legs are excluded: they move money between accounts, not in or out.
+/// Returns `(amount, type, currency_code)` tuples.
 pub async fn get_monthly_transactions(
     pool: &SqlitePool,
+    date_from: &str,
+    date_to: &str,
 ) -> Result<Vec<(i64, String, String)>, sqlx::Error> {
     sqlx::query_as::<_, (i64, String, String)>(
         "SELECT t.amount, t.type, cur.code as currency_code
          FROM transactions t
          JOIN accounts a ON t.account_id = a.id
          JOIN currencies cur ON a.currency_id = cur.id
-         WHERE t.date >= date('now', 'start of month')
-           AND t.date < date('now', 'start of month', '+1 month')",
+         WHERE t.transfer_id IS NULL
+           AND t.date >= ?
+           AND t.date < ?",
+    )
+    .bind(date_from)
+    .bind(date_to)
+    .fetch_all(pool)
+    .await
+}
+
+/// Sum non-transfer transactions dated in `[date_from, date_to)` per month, type and
+/// currency, using the same joins as `get_monthly_transactions`.
+/// Returns `(month, type, amount, currency_code)` tuples, where `month` is YYYY-MM.
+pub async fn get_monthly_totals(
+    pool: &SqlitePool,
+    date_from: &str,
+    date_to: &str,
+    filter: &MonthlyComparisonFilter,
+) -> Result<Vec<(String, String, i64, String)>, sqlx::Error> {
+    let mut builder: QueryBuilder<'_, Sqlite> = QueryBuilder::new(
+        "SELECT strftime('%Y-%m', t.date) as month, t.type, SUM(t.amount), cur.code
+         FROM transactions t
+         JOIN accounts a ON t.account_id = a.id
+         JOIN currencies cur ON a.currency_id = cur.id
+         JOIN categories c ON t.category_id = c.id
+         WHERE t.transfer_id IS NULL
+           AND t.date >= ",
+    );
+    builder.push_bind(date_from.to_string());
+    builder
+        .push(" AND t.date < ")
+        .push_bind(date_to.to_string());
+
+    if let Some(ref account_id) = filter.account_id {
+        builder
+            .push(" AND t.account_id = ")
+            .push_bind(account_id.clone());
+    }
+    if let Some(ref category_id) = filter.category_id {
+        builder
+            .push(" AND (c.id = ")
+            .push_bind(category_id.clone())
+            .push(" OR c.parent_id = ")
+            .push_bind(category_id.clone())
+            .push(")");
+    }
+
+    builder.push(" GROUP BY month, t.type, cur.code ORDER BY month");
+
+    builder
+        .build_query_as::<(String, String, i64, String)>()
+        .fetch_all(pool)
+        .await
+}
+
+/// Fetch the full rate history into `base_cur_id`, oldest first.
+/// Returns `(from_currency_code, date, rate, from_decimal_places, base_decimal_places)`.
+pub async fn get_rate_history(
+    pool: &SqlitePool,
+    base_cur_id: &str,
+) -> Result<Vec<(String, String, f64, i32, i32)>, sqlx::Error> {
+    sqlx::query_as::<_, (String, String, f64, i32, i32)>(
+        "SELECT c.code, er.date, er.rate, c.decimal_places, base.decimal_places
+         FROM exchange_rates er
+         JOIN currencies c ON er.from_currency_id = c.id
+         JOIN currencies base ON er.to_currency_id = base.id
+         WHERE er.to_currency_id = ?
+         ORDER BY er.date",
+    )
+    .bind(base_cur_id)
+    .fetch_all(pool)
+    .await
+}
+
+/// Fetch all accounts, active or not, with their currency code, ordered by name.
+/// Returns `(account_id, account_name, currency_code)` tuples.
+pub async fn get_accounts_with_currency(
+    pool: &SqlitePool,
+) -> Result<Vec<(String, String, String)>, sqlx::Error> {
+    sqlx::query_as::<_, (String, String, String)>(
+        "SELECT a.id, a.name, cur.code
+         FROM accounts a
+         JOIN currencies cur ON a.currency_id = cur.id
+         ORDER BY a.name",
+    )
+    .fetch_all(pool)
+    .await
+}
+
+/// Net balance change per account and day up to `date_to` (inclusive), oldest first,
+/// signed like `recalculate_account_balance`: income adds, everything else subtracts.
+/// Returns `(account_id, date, change)` tuples.
+pub async fn get_daily_balance_changes(
+    pool: &SqlitePool,
+    date_to: &str,
+) -> Result<Vec<(String, String, i64)>, sqlx::Error> {
+    sqlx::query_as::<_, (String, String, i64)>(
+        "SELECT account_id, date,
+                SUM(CASE WHEN type = 'income' THEN amount ELSE -amount END)
+         FROM transactions
+         WHERE date <= ?
+         GROUP BY account_id, date
+         ORDER BY date",
     )
+    .bind(date_to)
     .fetch_all(pool)
     .await
 }
 
-/// Fetch expense transactions for the current month with parent category rollup.
-/// Returns (category_id, category_name, amount, currency_code) — one row per transaction.
+/// Fetch expense transactions dated in `[date_from, date_to)` with parent category rollup.
+/// Returns `(category_id, category_name, subcategory_id, amount, currency_code)` — one row
+/// per transaction, where `category_id` is the parent when the transaction's category has
+/// one and `subcategory_id` is always the transaction's own category.
 /// Grouping and currency conversion happen in the command layer.
 pub async fn get_category_spending(
     pool: &SqlitePool,
-) -> Result<Vec<(String, String, i64, String)>, sqlx::Error> {
-    sqlx::query_as::<_, (String, String, i64, String)>(
+    date_from: &str,
+    date_to: &str,
+) -> Result<Vec<(String, String, String, i64, String)>, sqlx::Error> {
+    sqlx::query_as::<_, (String, String, String, i64, String)>(
         "SELECT
            COALESCE(parent.id, c.id) as category_id,
            COALESCE(parent.name, c.name) as category_name,
+           c.id as subcategory_id,
            t.amount,
            cur.code as currency_code
          FROM transactions t
@@ -35,17 +148,22 @@ pub async fn get_category_spending(
          JOIN accounts a ON t.account_id = a.id
          JOIN currencies cur ON a.currency_id = cur.id
          WHERE t.type = 'expense'
-           AND t.date >= date('now', 'start of month')
-           AND t.date < date('now', 'start of month', '+1 month')",
+           AND t.transfer_id IS NULL
+           AND t.date >= ?
+           AND t.date < ?",
     )
+    .bind(date_from)
+    .bind(date_to)
     .fetch_all(pool)
     .await
 }
 
-/// Fetch the 10 most recent transactions with joined account, category, and currency info.
+/// Fetch the 10 most recent transactions dated before `date_to`, with joined account,
+/// category, and currency info.
 /// Returns tuples to avoid needing FromRow on the display struct.
 pub async fn get_recent_transactions(
     pool: &SqlitePool,
+    date_to: &str,
 ) -> Result<Vec<(String, String, String, i64, String, String, String, String)>, sqlx::Error> {
     sqlx::query_as::<_, (String, String, String, i64, String, String, String, String)>(
         "SELECT
@@ -54,18 +172,11 @@ pub async fn get_recent_transactions(
          JOIN accounts a ON t.account_id = a.id
          JOIN categories c ON t.category_id = c.id
          JOIN currencies cur ON a.currency_id = cur.id
+         WHERE t.date < ?
          ORDER BY t.date DESC, t.created_at DESC
          LIMIT 10",
     )
+    .bind(date_to)
     .fetch_all(pool)
     .await
 }
-
-/// Get the current month number (1-12) and year.
-pub async fn get_current_month_info(pool: &SqlitePool) -> Result<(i32, i32), sqlx::Error> {
-    sqlx::query_as::<_, (i32, i32)>(
-        "SELECT CAST(strftime('%m', 'now') AS INTEGER), CAST(strftime('%Y', 'now') AS INTEGER)",
-    )
-    .fetch_one(pool)
-    .await
-}
@@ -1,14 +1,17 @@
 use sqlx::sqlite::Sqlite;
 use sqlx::{QueryBuilder, SqlitePool};
 
-use crate::db::models::{Debt, DebtFilter, Installment};
+use crate::db::models::{
+    Debt, DebtFilter, DebtPrepayment, DebtStatusChange, Installment, InstallmentPayment,
+};
 
 /// Get a single debt by ID with computed paid_installments from installments table.
 pub async fn get_debt_by_id(pool: &SqlitePool, id: &str) -> Result<Option<Debt>, sqlx::Error> {
     sqlx::query_as::<_, Debt>(
         "SELECT d.id, d.account_id, d.description, d.original_amount, d.total_installments,
-                (SELECT COUNT(*) FROM installments WHERE debt_id = d.id AND status = 'paid') as paid_installments,
-                d.monthly_payment, d.interest_rate, d.start_date, d.is_active, d.notes, d.created_at
+                (SELECT COUNT(*) FROM installments WHERE debt_id = d.id AND status IN ('paid', 'paid_late')) as paid_installments,
+                d.monthly_payment, d.interest_rate, d.rate_type, d.start_date, d.is_active, d.status, d.notes,
+                d.created_at
          FROM debts d
          WHERE d.id = ?",
     )
@@ -21,8 +24,9 @@ pub async fn get_debt_by_id(pool: &SqlitePool, id: &str) -> Result<Option<Debt>,
 pub async fn list_debts(pool: &SqlitePool, filter: &DebtFilter) -> Result<Vec<Debt>, sqlx::Error> {
     let mut builder: QueryBuilder<Sqlite> = QueryBuilder::new(
         "SELECT d.id, d.account_id, d.description, d.original_amount, d.total_installments,
-                (SELECT COUNT(*) FROM installments WHERE debt_id = d.id AND status = 'paid') as paid_installments,
-                d.monthly_payment, d.interest_rate, d.start_date, d.is_active, d.notes, d.created_at
+                (SELECT COUNT(*) FROM installments WHERE debt_id = d.id AND status IN ('paid', 'paid_late')) as paid_installments,
+                d.monthly_payment, d.interest_rate, d.rate_type, d.start_date, d.is_active, d.status, d.notes,
+                d.created_at
          FROM debts d WHERE 1=1",
     );
 
@@ -36,6 +40,16 @@ pub async fn list_debts(pool: &SqlitePool, filter: &DebtFilter) -> Result<Vec<De
             .push(" AND d.is_active = ")
             .push_bind(if is_active { 1 } else { 0 });
     }
+    if let Some(ref statuses) = filter.statuses {
+        if !statuses.is_empty() {
+            builder.push(" AND d.status IN (");
+            let mut separated = builder.separated(", ");
+            for status in statuses {
+                separated.push_bind(status.clone());
+            }
+            separated.push_unseparated(")");
+        }
+    }
     if let Some(ref search) = filter.search {
         let pattern = format!("%{search}%");
         builder.push(" AND d.description LIKE ").push_bind(pattern);
@@ -95,7 +109,8 @@ pub async fn list_installments_for_debt(
     debt_id: &str,
 ) -> Result<Vec<Installment>, sqlx::Error> {
     sqlx::query_as::<_, Installment>(
-        "SELECT id, debt_id, installment_number, due_date, amount, status, actual_payment_date, transaction_id, created_at
+        "SELECT id, debt_id, installment_number, due_date, amount, principal, interest,
+                balance, status, paid_amount, actual_payment_date, transaction_id, created_at
          FROM installments
          WHERE debt_id = ?
          ORDER BY installment_number ASC",
@@ -111,7 +126,8 @@ pub async fn get_installment_by_id(
     id: &str,
 ) -> Result<Option<Installment>, sqlx::Error> {
     sqlx::query_as::<_, Installment>(
-        "SELECT id, debt_id, installment_number, due_date, amount, status, actual_payment_date, transaction_id, created_at
+        "SELECT id, debt_id, installment_number, due_date, amount, principal, interest,
+                balance, status, paid_amount, actual_payment_date, transaction_id, created_at
          FROM installments
          WHERE id = ?",
     )
@@ -133,3 +149,352 @@ pub async fn get_account_name_for_debt(
     .await?;
     Ok(row.0)
 }
+
+/// Insert the expense transaction recording a payment towards a debt.
+/// Must be called within an active SQL transaction.
+pub async fn create_payment_transaction(
+    db_txn: &mut sqlx::Transaction<'_, Sqlite>,
+    id: &str,
+    account_id: &str,
+    category_id: &str,
+    amount: i64,
+    description: &str,
+    date: &str,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "INSERT INTO transactions (id, account_id, category_id, amount, type, description, date)
+         VALUES (?, ?, ?, ?, 'expense', ?, ?)",
+    )
+    .bind(id)
+    .bind(account_id)
+    .bind(category_id)
+    .bind(amount)
+    .bind(description)
+    .bind(date)
+    .execute(&mut **db_txn)
+    .await?;
+    Ok(())
+}
+
+/// Rewrite the amount and principal/interest split of a pending installment after its debt
+/// is rescheduled. Must be called within an active SQL transaction.
+pub async fn update_installment_schedule(
+    db_txn: &mut sqlx::Transaction<'_, Sqlite>,
+    id: &str,
+    amount: i64,
+    principal: i64,
+    interest: i64,
+    balance: i64,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "UPDATE installments SET amount = ?, principal = ?, interest = ?, balance = ?
+         WHERE id = ? AND status = 'pending'",
+    )
+    .bind(amount)
+    .bind(principal)
+    .bind(interest)
+    .bind(balance)
+    .bind(id)
+    .execute(&mut **db_txn)
+    .await?;
+    Ok(())
+}
+
+/// Delete the pending installments of a debt numbered after `installment_number`.
+/// Must be called within an active SQL transaction.
+pub async fn delete_pending_installments_after(
+    db_txn: &mut sqlx::Transaction<'_, Sqlite>,
+    debt_id: &str,
+    installment_number: i32,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "DELETE FROM installments
+         WHERE debt_id = ? AND installment_number > ? AND status = 'pending'",
+    )
+    .bind(debt_id)
+    .bind(installment_number)
+    .execute(&mut **db_txn)
+    .await?;
+    Ok(())
+}
+
+/// Update a debt's term and installment amount, and recount its paid installments.
+/// Must be called within an active SQL transaction.
+pub async fn update_debt_schedule(
+    db_txn: &mut sqlx::Transaction<'_, Sqlite>,
+    id: &str,
+    total_installments: i32,
+    monthly_payment: i64,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "UPDATE debts SET total_installments = ?, monthly_payment = ?,
+                paid_installments = (SELECT COUNT(*) FROM installments WHERE debt_id = ? AND status IN ('paid', 'paid_late'))
+         WHERE id = ?",
+    )
+    .bind(total_installments)
+    .bind(monthly_payment)
+    .bind(id)
+    .bind(id)
+    .execute(&mut **db_txn)
+    .await?;
+    Ok(())
+}
+
+/// Turn a pending installment into the one that repays the rest of its debt: `amount` of
+/// principal and no interest, keeping its due date. The payment itself is recorded in
+/// `installment_payments`. Must be called within an active SQL transaction.
+pub async fn settle_installment(
+    db_txn: &mut sqlx::Transaction<'_, Sqlite>,
+    id: &str,
+    amount: i64,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "UPDATE installments SET amount = ?, principal = ?, interest = 0, balance = 0
+         WHERE id = ?",
+    )
+    .bind(amount)
+    .bind(amount)
+    .bind(id)
+    .execute(&mut **db_txn)
+    .await?;
+    Ok(())
+}
+
+/// Link a payment transaction to the installment it pays towards.
+/// Must be called within an active SQL transaction.
+pub async fn create_installment_payment(
+    db_txn: &mut sqlx::Transaction<'_, Sqlite>,
+    installment_id: &str,
+    transaction_id: &str,
+) -> Result<(), sqlx::Error> {
+    sqlx::query("INSERT INTO installment_payments (transaction_id, installment_id) VALUES (?, ?)")
+        .bind(transaction_id)
+        .bind(installment_id)
+        .execute(&mut **db_txn)
+        .await?;
+    Ok(())
+}
+
+/// Every payment made towards an installment.
+pub async fn list_installment_payments(
+    pool: &SqlitePool,
+    installment_id: &str,
+) -> Result<Vec<InstallmentPayment>, sqlx::Error> {
+    sqlx::query_as::<_, InstallmentPayment>(
+        "SELECT transaction_id, installment_id, created_at
+         FROM installment_payments
+         WHERE installment_id = ?
+         ORDER BY rowid",
+    )
+    .bind(installment_id)
+    .fetch_all(pool)
+    .await
+}
+
+/// Recompute what has been paid on an installment, when, and its status from its payment
+/// transactions: `partial` until they add up to its amount, then `paid_late` if the last one
+/// came after the due date. Must be called within an active SQL transaction.
+pub async fn refresh_installment(
+    db_txn: &mut sqlx::Transaction<'_, Sqlite>,
+    id: &str,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "UPDATE installments
+         SET paid_amount = (SELECT SUM(t.amount) FROM installment_payments p
+                            JOIN transactions t ON t.id = p.transaction_id
+                            WHERE p.installment_id = installments.id),
+             actual_payment_date = (SELECT MAX(t.date) FROM installment_payments p
+                                    JOIN transactions t ON t.id = p.transaction_id
+                                    WHERE p.installment_id = installments.id),
+             transaction_id = (SELECT p.transaction_id FROM installment_payments p
+                               JOIN transactions t ON t.id = p.transaction_id
+                               WHERE p.installment_id = installments.id
+                               ORDER BY t.date DESC, p.rowid DESC LIMIT 1)
+         WHERE id = ?",
+    )
+    .bind(id)
+    .execute(&mut **db_txn)
+    .await?;
+    sqlx::query(
+        "UPDATE installments
+         SET status = CASE
+                 WHEN paid_amount IS NULL THEN 'pending'
+                 WHEN paid_amount < amount THEN 'partial'
+                 WHEN actual_payment_date > due_date THEN 'paid_late'
+                 ELSE 'paid'
+             END
+         WHERE id = ?",
+    )
+    .bind(id)
+    .execute(&mut **db_txn)
+    .await?;
+    Ok(())
+}
+
+/// Move a debt to another lifecycle state, keeping `is_active` in line with it.
+/// Must be called within an active SQL transaction.
+pub async fn set_debt_status(
+    db_txn: &mut sqlx::Transaction<'_, Sqlite>,
+    id: &str,
+    status: &str,
+    is_active: bool,
+) -> Result<(), sqlx::Error> {
+    sqlx::query("UPDATE debts SET status = ?, is_active = ? WHERE id = ?")
+        .bind(status)
+        .bind(i32::from(is_active))
+        .bind(id)
+        .execute(&mut **db_txn)
+        .await?;
+    Ok(())
+}
+
+/// Record a change of a debt's state. Must be called within an active SQL transaction.
+pub async fn create_status_change(
+    db_txn: &mut sqlx::Transaction<'_, Sqlite>,
+    change: &DebtStatusChange,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "INSERT INTO debt_status_history (id, debt_id, from_status, to_status, reason, note)
+         VALUES (?, ?, ?, ?, ?, ?)",
+    )
+    .bind(&change.id)
+    .bind(&change.debt_id)
+    .bind(&change.from_status)
+    .bind(&change.to_status)
+    .bind(&change.reason)
+    .bind(&change.note)
+    .execute(&mut **db_txn)
+    .await?;
+    Ok(())
+}
+
+/// Every change of a debt's state, oldest first.
+pub async fn list_status_history(
+    pool: &SqlitePool,
+    debt_id: &str,
+) -> Result<Vec<DebtStatusChange>, sqlx::Error> {
+    sqlx::query_as::<_, DebtStatusChange>(
+        "SELECT id, debt_id, from_status, to_status, reason, note, changed_at
+         FROM debt_status_history
+         WHERE debt_id = ?
+         ORDER BY changed_at, rowid",
+    )
+    .bind(debt_id)
+    .fetch_all(pool)
+    .await
+}
+
+/// Link a prepayment's expense transaction to its debt.
+/// Must be called within an active SQL transaction.
+pub async fn create_prepayment(
+    db_txn: &mut sqlx::Transaction<'_, Sqlite>,
+    prepayment: &DebtPrepayment,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "INSERT INTO debt_prepayments (id, debt_id, transaction_id, mode) VALUES (?, ?, ?, ?)",
+    )
+    .bind(&prepayment.id)
+    .bind(&prepayment.debt_id)
+    .bind(&prepayment.transaction_id)
+    .bind(&prepayment.mode)
+    .execute(&mut **db_txn)
+    .await?;
+    Ok(())
+}
+
+/// The prepayment recorded by a transaction, if any.
+pub async fn get_prepayment_by_transaction(
+    pool: &SqlitePool,
+    transaction_id: &str,
+) -> Result<Option<DebtPrepayment>, sqlx::Error> {
+    sqlx::query_as::<_, DebtPrepayment>(
+        "SELECT id, debt_id, transaction_id, mode, created_at
+         FROM debt_prepayments
+         WHERE transaction_id = ?",
+    )
+    .bind(transaction_id)
+    .fetch_optional(pool)
+    .await
+}
+
+/// The installment a transaction paid towards, if any.
+pub async fn get_installment_by_transaction(
+    pool: &SqlitePool,
+    transaction_id: &str,
+) -> Result<Option<Installment>, sqlx::Error> {
+    sqlx::query_as::<_, Installment>(
+        "SELECT i.id, i.debt_id, i.installment_number, i.due_date, i.amount, i.principal,
+                i.interest, i.balance, i.status, i.paid_amount, i.actual_payment_date,
+                i.transaction_id, i.created_at
+         FROM installments i
+         JOIN installment_payments p ON p.installment_id = i.id
+         WHERE p.transaction_id = ?",
+    )
+    .bind(transaction_id)
+    .fetch_optional(pool)
+    .await
+}
+
+/// Set a paid installment back to pending, unlinking its payment transactions.
+/// Must be called within an active SQL transaction.
+pub async fn reset_installment(
+    db_txn: &mut sqlx::Transaction<'_, Sqlite>,
+    id: &str,
+) -> Result<(), sqlx::Error> {
+    sqlx::query("DELETE FROM installment_payments WHERE installment_id = ?")
+        .bind(id)
+        .execute(&mut **db_txn)
+        .await?;
+    sqlx::query(
+        "UPDATE installments
+         SET status = 'pending', paid_amount = NULL, actual_payment_date = NULL, transaction_id = NULL
+         WHERE id = ?",
+    )
+    .bind(id)
+    .execute(&mut **db_txn)
+    .await?;
+    Ok(())
+}
+
+/// Delete a transaction that recorded a debt payment. Its installment must already be
+/// unlinked. Must be called within an active SQL transaction.
+pub async fn delete_payment_transaction(
+    db_txn: &mut sqlx::Transaction<'_, Sqlite>,
+    id: &str,
+) -> Result<(), sqlx::Error> {
+    sqlx::query("DELETE FROM transactions WHERE id = ?")
+        .bind(id)
+        .execute(&mut **db_txn)
+        .await?;
+    Ok(())
+}
+
+/// Recount a debt's paid installments. Must be called within an active SQL transaction.
+pub async fn update_paid_installments(
+    db_txn: &mut sqlx::Transaction<'_, Sqlite>,
+    debt_id: &str,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "UPDATE debts SET paid_installments = (SELECT COUNT(*) FROM installments WHERE debt_id = ? AND status IN ('paid', 'paid_late')) WHERE id = ?",
+    )
+    .bind(debt_id)
+    .bind(debt_id)
+    .execute(&mut **db_txn)
+    .await?;
+    Ok(())
+}
+
+/// Number of installments of a debt not yet paid in full.
+/// Must be called within an active SQL transaction.
+pub async fn count_unpaid_installments(
+    db_txn: &mut sqlx::Transaction<'_, Sqlite>,
+    debt_id: &str,
+) -> Result<i64, sqlx::Error> {
+    let (count,): (i64,) = sqlx::query_as(
+        "SELECT COUNT(*) FROM installments WHERE debt_id = ? AND status IN ('pending', 'partial')",
+    )
+    .bind(debt_id)
+    .fetch_one(&mut **db_txn)
+    .await?;
+    Ok(count)
+}
@@ -0,0 +1,120 @@
+use sqlx::SqlitePool;
+
+use crate::db::models::ExchangeRate;
+
+/// Get a single exchange rate by ID.
+pub async fn get_by_id(pool: &SqlitePool, id: &str) -> Result<Option<ExchangeRate>, sqlx::Error> {
+    sqlx::query_as::<_, ExchangeRate>(
+        "SELECT id, from_currency_id, to_currency_id, rate, date, created_at
+         FROM exchange_rates
+         WHERE id = ?",
+    )
+    .bind(id)
+    .fetch_optional(pool)
+    .await
+}
+
+/// Get the rate recorded for a currency pair on a specific date.
+pub async fn get_by_pair_and_date(
+    pool: &SqlitePool,
+    from_currency_id: &str,
+    to_currency_id: &str,
+    date: &str,
+) -> Result<Option<ExchangeRate>, sqlx::Error> {
+    sqlx::query_as::<_, ExchangeRate>(
+        "SELECT id, from_currency_id, to_currency_id, rate, date, created_at
+         FROM exchange_rates
+         WHERE from_currency_id = ? AND to_currency_id = ? AND date = ?",
+    )
+    .bind(from_currency_id)
+    .bind(to_currency_id)
+    .bind(date)
+    .fetch_optional(pool)
+    .await
+}
+
+/// Get the most recent rate for a currency pair.
+pub async fn get_latest(
+    pool: &SqlitePool,
+    from_currency_id: &str,
+    to_currency_id: &str,
+) -> Result<Option<ExchangeRate>, sqlx::Error> {
+    sqlx::query_as::<_, ExchangeRate>(
+        "SELECT id, from_currency_id, to_currency_id, rate, date, created_at
+         FROM exchange_rates
+         WHERE from_currency_id = ? AND to_currency_id = ?
+         ORDER BY date DESC
+         LIMIT 1",
+    )
+    .bind(from_currency_id)
+    .bind(to_currency_id)
+    .fetch_optional(pool)
+    .await
+}
+
+/// List the full rate history for a currency pair, newest first.
+pub async fn list_for_pair(
+    pool: &SqlitePool,
+    from_currency_id: &str,
+    to_currency_id: &str,
+) -> Result<Vec<ExchangeRate>, sqlx::Error> {
+    sqlx::query_as::<_, ExchangeRate>(
+        "SELECT id, from_currency_id, to_currency_id, rate, date, created_at
+         FROM exchange_rates
+         WHERE from_currency_id = ? AND to_currency_id = ?
+         ORDER BY date DESC",
+    )
+    .bind(from_currency_id)
+    .bind(to_currency_id)
+    .fetch_all(pool)
+    .await
+}
+
+/// Insert a rate, or overwrite it if the pair already has one for that date.
+/// Returns the stored row (keeping the original ID on update).
+pub async fn upsert(
+    pool: &SqlitePool,
+    id: &str,
+    from_currency_id: &str,
+    to_currency_id: &str,
+    rate: f64,
+    date: &str,
+) -> Result<ExchangeRate, sqlx::Error> {
+    sqlx::query(
+        "INSERT INTO exchange_rates (id, from_currency_id, to_currency_id, rate, date)
+         VALUES (?, ?, ?, ?, ?)
+         ON CONFLICT(from_currency_id, to_currency_id, date) DO UPDATE SET rate = excluded.rate",
+    )
+    .bind(id)
+    .bind(from_currency_id)
+    .bind(to_currency_id)
+    .bind(rate)
+    .bind(date)
+    .execute(pool)
+    .await?;
+
+    get_by_pair_and_date(pool, from_currency_id, to_currency_id, date)
+        .await?
+        .ok_or(sqlx::Error::RowNotFound)
+}
+
+/// Delete an exchange rate by ID.
+pub async fn delete(pool: &SqlitePool, id: &str) -> Result<(), sqlx::Error> {
+    sqlx::query("DELETE FROM exchange_rates WHERE id = ?")
+        .bind(id)
+        .execute(pool)
+        .await?;
+    Ok(())
+}
+
+/// Get the number of decimal places of a currency, or `None` if it doesn't exist.
+pub async fn get_decimal_places(
+    pool: &SqlitePool,
+    currency_id: &str,
+) -> Result<Option<i32>, sqlx::Error> {
+    let row: Option<(i32,)> = sqlx::query_as("SELECT decimal_places FROM currencies WHERE id = ?")
+        .bind(currency_id)
+        .fetch_optional(pool)
+        .await?;
+    Ok(row.map(|(places,)| places))
+}
@@ -0,0 +1,628 @@
+use sqlx::sqlite::Sqlite;
+use sqlx::SqlitePool;
+
+use crate::db::models::{
+    Account, Budget, Category, CsvImportProfile, Currency, Debt, DebtPrepayment, DebtStatusChange,
+    ExchangeRate, ExportData, Installment, InstallmentPayment, RecurringTransaction, Setting,
+    StatementPayment, Tag, Transaction, TransactionTag, TransferRecord,
+};
+
+/// Get the name of the latest applied migration, if any.
+pub async fn get_schema_version(pool: &SqlitePool) -> Result<Option<String>, sqlx::Error> {
+    let row: Option<(String,)> =
+        sqlx::query_as("SELECT name FROM _migrations ORDER BY name DESC LIMIT 1")
+            .fetch_optional(pool)
+            .await?;
+    Ok(row.map(|(name,)| name))
+}
+
+/// Check whether a migration with the given name has been applied to this database.
+pub async fn is_migration_applied(pool: &SqlitePool, name: &str) -> Result<bool, sqlx::Error> {
+    let count: (i64,) = sqlx::query_as("SELECT COUNT(*) FROM _migrations WHERE name = ?")
+        .bind(name)
+        .fetch_one(pool)
+        .await?;
+    Ok(count.0 > 0)
+}
+
+/// Read every exported table in full, including archived and inactive rows.
+#[allow(clippy::too_many_lines)]
+pub async fn fetch_all(pool: &SqlitePool) -> Result<ExportData, sqlx::Error> {
+    let currencies = sqlx::query_as::<_, Currency>(
+        "SELECT id, code, name, symbol, decimal_places, created_at FROM currencies ORDER BY id",
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let accounts = sqlx::query_as::<_, Account>(
+        "SELECT id, name, type, currency_id, balance, credit_limit, billing_day, payment_due_day,
+                is_active, created_at
+         FROM accounts ORDER BY created_at, id",
+    )
+    .fetch_all(pool)
+    .await?;
+
+    // Parents first so a plain sequential insert never references a missing parent
+    let categories = sqlx::query_as::<_, Category>(
+        "SELECT id, name, type, icon, parent_id, is_active, created_at
+         FROM categories ORDER BY parent_id IS NOT NULL, id",
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let transactions = sqlx::query_as::<_, Transaction>(
+        "SELECT id, account_id, category_id, amount, type, description, date, notes, recurring_id,
+                transfer_id, fitid, created_at
+         FROM transactions ORDER BY date, created_at, id",
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let tags = sqlx::query_as::<_, Tag>("SELECT id, name, color, created_at FROM tags ORDER BY id")
+        .fetch_all(pool)
+        .await?;
+
+    let transaction_tags = sqlx::query_as::<_, TransactionTag>(
+        "SELECT transaction_id, tag_id FROM transaction_tags ORDER BY transaction_id, tag_id",
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let recurring_transactions = sqlx::query_as::<_, RecurringTransaction>(
+        "SELECT id, account_id, category_id, amount, type, description, frequency, next_date,
+                anchor_day, end_date, is_active, created_at
+         FROM recurring_transactions ORDER BY created_at, id",
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let transfers = sqlx::query_as::<_, TransferRecord>(
+        "SELECT id, rate, created_at FROM transfers ORDER BY created_at, id",
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let debts = sqlx::query_as::<_, Debt>(
+        "SELECT id, account_id, description, original_amount, total_installments, paid_installments,
+                monthly_payment, interest_rate, rate_type, start_date, is_active, status, notes,
+                created_at
+         FROM debts ORDER BY created_at, id",
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let installments = sqlx::query_as::<_, Installment>(
+        "SELECT id, debt_id, installment_number, due_date, amount, principal, interest, balance,
+                status, paid_amount, actual_payment_date, transaction_id, created_at
+         FROM installments ORDER BY debt_id, installment_number",
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let exchange_rates = sqlx::query_as::<_, ExchangeRate>(
+        "SELECT id, from_currency_id, to_currency_id, rate, date, created_at
+         FROM exchange_rates ORDER BY date, id",
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let csv_import_profiles = sqlx::query_as::<_, CsvImportProfile>(
+        "SELECT id, name, delimiter, skip_rows, date_column, date_format, description_column,
+                sign_convention, amount_column, debit_column, credit_column, decimal_separator,
+                default_expense_category_id, default_income_category_id, created_at
+         FROM csv_import_profiles ORDER BY name",
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let budgets = sqlx::query_as::<_, Budget>(
+        "SELECT id, category_id, amount, rollover, start_month, created_at
+         FROM budgets ORDER BY created_at, id",
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let statement_payments = sqlx::query_as::<_, StatementPayment>(
+        "SELECT id, account_id, closing_date, transfer_id, created_at
+         FROM statement_payments ORDER BY created_at, id",
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let installment_payments = sqlx::query_as::<_, InstallmentPayment>(
+        "SELECT transaction_id, installment_id, created_at
+         FROM installment_payments ORDER BY installment_id, rowid",
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let debt_status_history = sqlx::query_as::<_, DebtStatusChange>(
+        "SELECT id, debt_id, from_status, to_status, reason, note, changed_at
+         FROM debt_status_history ORDER BY changed_at, rowid",
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let debt_prepayments = sqlx::query_as::<_, DebtPrepayment>(
+        "SELECT id, debt_id, transaction_id, mode, created_at
+         FROM debt_prepayments ORDER BY created_at, id",
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let settings =
+        sqlx::query_as::<_, Setting>("SELECT key, value, updated_at FROM settings ORDER BY key")
+            .fetch_all(pool)
+            .await?;
+
+    Ok(ExportData {
+        currencies,
+        accounts,
+        categories,
+        transactions,
+        tags,
+        transaction_tags,
+        recurring_transactions,
+        transfers,
+        statement_payments,
+        csv_import_profiles,
+        budgets,
+        debts,
+        installments,
+        installment_payments,
+        debt_status_history,
+        debt_prepayments,
+        exchange_rates,
+        settings,
+    })
+}
+
+/// Delete every row of every exported table, children before parents.
+/// Must be called within an active SQL transaction.
+pub async fn wipe_all(db_txn: &mut sqlx::Transaction<'_, Sqlite>) -> Result<(), sqlx::Error> {
+    for table in [
+        "settings",
+        "statement_payments",
+        "budgets",
+        "csv_import_profiles",
+        "transaction_tags",
+        "debt_status_history",
+        "debt_prepayments",
+        "installment_payments",
+        "installments",
+        "debts",
+        "transactions",
+        "transfers",
+        "recurring_transactions",
+        "tags",
+        "exchange_rates",
+        "categories",
+        "accounts",
+        "currencies",
+    ] {
+        // Table names come from the fixed list above, never from user input
+        sqlx::query(&format!("DELETE FROM {table}"))
+            .execute(&mut **db_txn)
+            .await?;
+    }
+    Ok(())
+}
+
+/// Insert a currency unless its ID already exists. Returns whether a row was written.
+pub async fn insert_currency(
+    db_txn: &mut sqlx::Transaction<'_, Sqlite>,
+    row: &Currency,
+) -> Result<bool, sqlx::Error> {
+    let result = sqlx::query(
+        "INSERT OR IGNORE INTO currencies (id, code, name, symbol, decimal_places, created_at)
+         VALUES (?, ?, ?, ?, ?, ?)",
+    )
+    .bind(&row.id)
+    .bind(&row.code)
+    .bind(&row.name)
+    .bind(&row.symbol)
+    .bind(row.decimal_places)
+    .bind(&row.created_at)
+    .execute(&mut **db_txn)
+    .await?;
+    Ok(result.rows_affected() > 0)
+}
+
+/// Insert an account unless its ID already exists. Returns whether a row was written.
+pub async fn insert_account(
+    db_txn: &mut sqlx::Transaction<'_, Sqlite>,
+    row: &Account,
+) -> Result<bool, sqlx::Error> {
+    let result = sqlx::query(
+        "INSERT OR IGNORE INTO accounts
+            (id, name, type, currency_id, balance, credit_limit, billing_day, payment_due_day,
+             is_active, created_at)
+         VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+    )
+    .bind(&row.id)
+    .bind(&row.name)
+    .bind(&row.account_type)
+    .bind(&row.currency_id)
+    .bind(row.balance)
+    .bind(row.credit_limit)
+    .bind(row.billing_day)
+    .bind(row.payment_due_day)
+    .bind(row.is_active)
+    .bind(&row.created_at)
+    .execute(&mut **db_txn)
+    .await?;
+    Ok(result.rows_affected() > 0)
+}
+
+/// Insert a category unless its ID already exists. Returns whether a row was written.
+pub async fn insert_category(
+    db_txn: &mut sqlx::Transaction<'_, Sqlite>,
+    row: &Category,
+) -> Result<bool, sqlx::Error> {
+    let result = sqlx::query(
+        "INSERT OR IGNORE INTO categories (id, name, type, icon, parent_id, is_active, created_at)
+         VALUES (?, ?, ?, ?, ?, ?, ?)",
+    )
+    .bind(&row.id)
+    .bind(&row.name)
+    .bind(&row.category_type)
+    .bind(&row.icon)
+    .bind(&row.parent_id)
+    .bind(row.is_active)
+    .bind(&row.created_at)
+    .execute(&mut **db_txn)
+    .await?;
+    Ok(result.rows_affected() > 0)
+}
+
+/// Insert a transaction unless its ID already exists. Returns whether a row was written.
+pub async fn insert_transaction(
+    db_txn: &mut sqlx::Transaction<'_, Sqlite>,
+    row: &Transaction,
+) -> Result<bool, sqlx::Error> {
+    let result = sqlx::query(
+        "INSERT OR IGNORE INTO transactions
+            (id, account_id, category_id, amount, type, description, date, notes, recurring_id,
+             transfer_id, fitid, created_at)
+         VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+    )
+    .bind(&row.id)
+    .bind(&row.account_id)
+    .bind(&row.category_id)
+    .bind(row.amount)
+    .bind(&row.transaction_type)
+    .bind(&row.description)
+    .bind(&row.date)
+    .bind(&row.notes)
+    .bind(&row.recurring_id)
+    .bind(&row.transfer_id)
+    .bind(&row.fitid)
+    .bind(&row.created_at)
+    .execute(&mut **db_txn)
+    .await?;
+    Ok(result.rows_affected() > 0)
+}
+
+/// Insert a tag unless its ID already exists. Returns whether a row was written.
+pub async fn insert_tag(
+    db_txn: &mut sqlx::Transaction<'_, Sqlite>,
+    row: &Tag,
+) -> Result<bool, sqlx::Error> {
+    let result =
+        sqlx::query("INSERT OR IGNORE INTO tags (id, name, color, created_at) VALUES (?, ?, ?, ?)")
+            .bind(&row.id)
+            .bind(&row.name)
+            .bind(&row.color)
+            .bind(&row.created_at)
+            .execute(&mut **db_txn)
+            .await?;
+    Ok(result.rows_affected() > 0)
+}
+
+/// Insert a transaction/tag link unless it already exists. Returns whether a row was written.
+pub async fn insert_transaction_tag(
+    db_txn: &mut sqlx::Transaction<'_, Sqlite>,
+    row: &TransactionTag,
+) -> Result<bool, sqlx::Error> {
+    let result = sqlx::query(
+        "INSERT OR IGNORE INTO transaction_tags (transaction_id, tag_id) VALUES (?, ?)",
+    )
+    .bind(&row.transaction_id)
+    .bind(&row.tag_id)
+    .execute(&mut **db_txn)
+    .await?;
+    Ok(result.rows_affected() > 0)
+}
+
+/// Insert a recurring rule unless its ID already exists. Returns whether a row was written.
+pub async fn insert_recurring_transaction(
+    db_txn: &mut sqlx::Transaction<'_, Sqlite>,
+    row: &RecurringTransaction,
+) -> Result<bool, sqlx::Error> {
+    let result = sqlx::query(
+        "INSERT OR IGNORE INTO recurring_transactions
+            (id, account_id, category_id, amount, type, description, frequency, next_date,
+             anchor_day, end_date, is_active, created_at)
+         VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+    )
+    .bind(&row.id)
+    .bind(&row.account_id)
+    .bind(&row.category_id)
+    .bind(row.amount)
+    .bind(&row.transaction_type)
+    .bind(&row.description)
+    .bind(&row.frequency)
+    .bind(&row.next_date)
+    .bind(row.anchor_day)
+    .bind(&row.end_date)
+    .bind(row.is_active)
+    .bind(&row.created_at)
+    .execute(&mut **db_txn)
+    .await?;
+    Ok(result.rows_affected() > 0)
+}
+
+/// Insert a transfer unless its ID already exists. Returns whether a row was written.
+pub async fn insert_transfer(
+    db_txn: &mut sqlx::Transaction<'_, Sqlite>,
+    row: &TransferRecord,
+) -> Result<bool, sqlx::Error> {
+    let result =
+        sqlx::query("INSERT OR IGNORE INTO transfers (id, rate, created_at) VALUES (?, ?, ?)")
+            .bind(&row.id)
+            .bind(row.rate)
+            .bind(&row.created_at)
+            .execute(&mut **db_txn)
+            .await?;
+    Ok(result.rows_affected() > 0)
+}
+
+/// Insert a statement payment unless its ID already exists. Returns whether a row was written.
+pub async fn insert_statement_payment(
+    db_txn: &mut sqlx::Transaction<'_, Sqlite>,
+    row: &StatementPayment,
+) -> Result<bool, sqlx::Error> {
+    let result = sqlx::query(
+        "INSERT OR IGNORE INTO statement_payments
+            (id, account_id, closing_date, transfer_id, created_at)
+         VALUES (?, ?, ?, ?, ?)",
+    )
+    .bind(&row.id)
+    .bind(&row.account_id)
+    .bind(&row.closing_date)
+    .bind(&row.transfer_id)
+    .bind(&row.created_at)
+    .execute(&mut **db_txn)
+    .await?;
+    Ok(result.rows_affected() > 0)
+}
+
+/// Insert a debt unless its ID already exists. Returns whether a row was written.
+pub async fn insert_debt(
+    db_txn: &mut sqlx::Transaction<'_, Sqlite>,
+    row: &Debt,
+) -> Result<bool, sqlx::Error> {
+    let result = sqlx::query(
+        "INSERT OR IGNORE INTO debts
+            (id, account_id, description, original_amount, total_installments, paid_installments,
+             monthly_payment, interest_rate, rate_type, start_date, is_active, status, notes,
+             created_at)
+         VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+    )
+    .bind(&row.id)
+    .bind(&row.account_id)
+    .bind(&row.description)
+    .bind(row.original_amount)
+    .bind(row.total_installments)
+    .bind(row.paid_installments)
+    .bind(row.monthly_payment)
+    .bind(row.interest_rate)
+    .bind(&row.rate_type)
+    .bind(&row.start_date)
+    .bind(row.is_active)
+    .bind(&row.status)
+    .bind(&row.notes)
+    .bind(&row.created_at)
+    .execute(&mut **db_txn)
+    .await?;
+    Ok(result.rows_affected() > 0)
+}
+
+/// Insert an installment unless its ID already exists. Returns whether a row was written.
+pub async fn insert_installment(
+    db_txn: &mut sqlx::Transaction<'_, Sqlite>,
+    row: &Installment,
+) -> Result<bool, sqlx::Error> {
+    let result = sqlx::query(
+        "INSERT OR IGNORE INTO installments
+            (id, debt_id, installment_number, due_date, amount, principal, interest, balance,
+             status, paid_amount, actual_payment_date, transaction_id, created_at)
+         VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+    )
+    .bind(&row.id)
+    .bind(&row.debt_id)
+    .bind(row.installment_number)
+    .bind(&row.due_date)
+    .bind(row.amount)
+    .bind(row.principal)
+    .bind(row.interest)
+    .bind(row.balance)
+    .bind(&row.status)
+    // Backups taken before paid amounts were recorded only had full payments
+    .bind(
+        row.paid_amount
+            .or_else(|| (row.status != "pending").then_some(row.amount)),
+    )
+    .bind(&row.actual_payment_date)
+    .bind(&row.transaction_id)
+    .bind(&row.created_at)
+    .execute(&mut **db_txn)
+    .await?;
+    Ok(result.rows_affected() > 0)
+}
+
+/// Insert an installment payment unless its transaction is already linked. Returns whether a
+/// row was written.
+pub async fn insert_installment_payment(
+    db_txn: &mut sqlx::Transaction<'_, Sqlite>,
+    row: &InstallmentPayment,
+) -> Result<bool, sqlx::Error> {
+    let result = sqlx::query(
+        "INSERT OR IGNORE INTO installment_payments (transaction_id, installment_id, created_at)
+         VALUES (?, ?, ?)",
+    )
+    .bind(&row.transaction_id)
+    .bind(&row.installment_id)
+    .bind(&row.created_at)
+    .execute(&mut **db_txn)
+    .await?;
+    Ok(result.rows_affected() > 0)
+}
+
+/// Link installments to the transaction that paid them where no payment is recorded for it,
+/// as in backups taken before installment payments were tracked.
+/// Must be called within an active SQL transaction.
+pub async fn link_unrecorded_installment_payments(
+    db_txn: &mut sqlx::Transaction<'_, Sqlite>,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "INSERT INTO installment_payments (transaction_id, installment_id)
+         SELECT i.transaction_id, i.id
+         FROM installments i
+         JOIN transactions t ON t.id = i.transaction_id
+         WHERE i.transaction_id NOT IN (SELECT transaction_id FROM installment_payments)",
+    )
+    .execute(&mut **db_txn)
+    .await?;
+    Ok(())
+}
+
+/// Insert a debt status change unless its ID already exists. Returns whether a row was written.
+pub async fn insert_debt_status_change(
+    db_txn: &mut sqlx::Transaction<'_, Sqlite>,
+    row: &DebtStatusChange,
+) -> Result<bool, sqlx::Error> {
+    let result = sqlx::query(
+        "INSERT OR IGNORE INTO debt_status_history
+            (id, debt_id, from_status, to_status, reason, note, changed_at)
+         VALUES (?, ?, ?, ?, ?, ?, ?)",
+    )
+    .bind(&row.id)
+    .bind(&row.debt_id)
+    .bind(&row.from_status)
+    .bind(&row.to_status)
+    .bind(&row.reason)
+    .bind(&row.note)
+    .bind(&row.changed_at)
+    .execute(&mut **db_txn)
+    .await?;
+    Ok(result.rows_affected() > 0)
+}
+
+/// Insert a debt prepayment unless its ID already exists. Returns whether a row was written.
+pub async fn insert_debt_prepayment(
+    db_txn: &mut sqlx::Transaction<'_, Sqlite>,
+    row: &DebtPrepayment,
+) -> Result<bool, sqlx::Error> {
+    let result = sqlx::query(
+        "INSERT OR IGNORE INTO debt_prepayments (id, debt_id, transaction_id, mode, created_at)
+         VALUES (?, ?, ?, ?, ?)",
+    )
+    .bind(&row.id)
+    .bind(&row.debt_id)
+    .bind(&row.transaction_id)
+    .bind(&row.mode)
+    .bind(&row.created_at)
+    .execute(&mut **db_txn)
+    .await?;
+    Ok(result.rows_affected() > 0)
+}
+
+/// Insert an exchange rate unless its ID (or pair/date) already exists.
+/// Returns whether a row was written.
+pub async fn insert_exchange_rate(
+    db_txn: &mut sqlx::Transaction<'_, Sqlite>,
+    row: &ExchangeRate,
+) -> Result<bool, sqlx::Error> {
+    let result = sqlx::query(
+        "INSERT OR IGNORE INTO exchange_rates
+            (id, from_currency_id, to_currency_id, rate, date, created_at)
+         VALUES (?, ?, ?, ?, ?, ?)",
+    )
+    .bind(&row.id)
+    .bind(&row.from_currency_id)
+    .bind(&row.to_currency_id)
+    .bind(row.rate)
+    .bind(&row.date)
+    .bind(&row.created_at)
+    .execute(&mut **db_txn)
+    .await?;
+    Ok(result.rows_affected() > 0)
+}
+
+/// Insert a CSV import profile unless its ID already exists. Returns whether a row was written.
+pub async fn insert_csv_import_profile(
+    db_txn: &mut sqlx::Transaction<'_, Sqlite>,
+    row: &CsvImportProfile,
+) -> Result<bool, sqlx::Error> {
+    let result = sqlx::query(
+        "INSERT OR IGNORE INTO csv_import_profiles
+            (id, name, delimiter, skip_rows, date_column, date_format, description_column,
+             sign_convention, amount_column, debit_column, credit_column, decimal_separator,
+             default_expense_category_id, default_income_category_id, created_at)
+         VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+    )
+    .bind(&row.id)
+    .bind(&row.name)
+    .bind(&row.delimiter)
+    .bind(row.skip_rows)
+    .bind(row.date_column)
+    .bind(&row.date_format)
+    .bind(row.description_column)
+    .bind(&row.sign_convention)
+    .bind(row.amount_column)
+    .bind(row.debit_column)
+    .bind(row.credit_column)
+    .bind(&row.decimal_separator)
+    .bind(&row.default_expense_category_id)
+    .bind(&row.default_income_category_id)
+    .bind(&row.created_at)
+    .execute(&mut **db_txn)
+    .await?;
+    Ok(result.rows_affected() > 0)
+}
+
+/// Insert a setting unless its key already exists. Returns whether a row was written.
+pub async fn insert_setting(
+    db_txn: &mut sqlx::Transaction<'_, Sqlite>,
+    row: &Setting,
+) -> Result<bool, sqlx::Error> {
+    let result =
+        sqlx::query("INSERT OR IGNORE INTO settings (key, value, updated_at) VALUES (?, ?, ?)")
+            .bind(&row.key)
+            .bind(&row.value)
+            .bind(&row.updated_at)
+            .execute(&mut **db_txn)
+            .await?;
+    Ok(result.rows_affected() > 0)
+}
+
+/// Insert a budget unless its ID already exists. Returns whether a row was written.
+pub async fn insert_budget(
+    db_txn: &mut sqlx::Transaction<'_, Sqlite>,
+    row: &Budget,
+) -> Result<bool, sqlx::Error> {
+    let result = sqlx::query(
+        "INSERT OR IGNORE INTO budgets (id, category_id, amount, rollover, start_month, created_at)
+         VALUES (?, ?, ?, ?, ?, ?)",
+    )
+    .bind(&row.id)
+    .bind(&row.category_id)
+    .bind(row.amount)
+    .bind(row.rollover)
+    .bind(&row.start_month)
+    .bind(&row.created_at)
+    .execute(&mut **db_txn)
+    .await?;
+    Ok(result.rows_affected() > 0)
+}
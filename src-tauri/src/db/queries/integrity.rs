@@ -0,0 +1,147 @@
+use sqlx::sqlite::Sqlite;
+
+use crate::db::models::{
+    BalanceDrift, DanglingInstallment, Debt, OrphanedTagLink, PaidInstallmentsMismatch,
+};
+
+/// Accounts whose stored balance differs from the sum of their transactions, archived ones
+/// included.
+pub async fn balance_drift(
+    db_txn: &mut sqlx::Transaction<'_, Sqlite>,
+) -> Result<Vec<BalanceDrift>, sqlx::Error> {
+    sqlx::query_as::<_, BalanceDrift>(
+        "SELECT account_id, account_name, stored_balance, computed_balance
+         FROM (
+             SELECT a.id AS account_id,
+                    a.name AS account_name,
+                    a.balance AS stored_balance,
+                    COALESCE(SUM(CASE WHEN t.type = 'income' THEN t.amount ELSE -t.amount END), 0)
+                        AS computed_balance
+             FROM accounts a
+             LEFT JOIN transactions t ON t.account_id = a.id
+             GROUP BY a.id
+         )
+         WHERE stored_balance != computed_balance
+         ORDER BY account_name",
+    )
+    .fetch_all(&mut **db_txn)
+    .await
+}
+
+/// Debts whose `paid_installments` counter differs from their installments marked paid.
+pub async fn paid_installments_mismatches(
+    db_txn: &mut sqlx::Transaction<'_, Sqlite>,
+) -> Result<Vec<PaidInstallmentsMismatch>, sqlx::Error> {
+    sqlx::query_as::<_, PaidInstallmentsMismatch>(
+        "SELECT debt_id, description, stored_count, actual_count
+         FROM (
+             SELECT d.id AS debt_id,
+                    d.description,
+                    d.paid_installments AS stored_count,
+                    (SELECT COUNT(*) FROM installments i
+                     WHERE i.debt_id = d.id AND i.status IN ('paid', 'paid_late')) AS actual_count
+             FROM debts d
+         )
+         WHERE stored_count != actual_count
+         ORDER BY description",
+    )
+    .fetch_all(&mut **db_txn)
+    .await
+}
+
+/// Installments paid by a transaction that has been deleted.
+pub async fn dangling_installments(
+    db_txn: &mut sqlx::Transaction<'_, Sqlite>,
+) -> Result<Vec<DanglingInstallment>, sqlx::Error> {
+    sqlx::query_as::<_, DanglingInstallment>(
+        "SELECT i.id AS installment_id, i.debt_id, i.installment_number, p.transaction_id
+         FROM installment_payments p
+         JOIN installments i ON i.id = p.installment_id
+         WHERE p.transaction_id NOT IN (SELECT id FROM transactions)
+         UNION
+         SELECT i.id, i.debt_id, i.installment_number, i.transaction_id
+         FROM installments i
+         WHERE i.transaction_id IS NOT NULL
+           AND i.transaction_id NOT IN (SELECT id FROM transactions)
+         ORDER BY debt_id, installment_number",
+    )
+    .fetch_all(&mut **db_txn)
+    .await
+}
+
+/// Tag links whose transaction or tag has been deleted.
+pub async fn orphaned_tag_links(
+    db_txn: &mut sqlx::Transaction<'_, Sqlite>,
+) -> Result<Vec<OrphanedTagLink>, sqlx::Error> {
+    sqlx::query_as::<_, OrphanedTagLink>(
+        "SELECT tt.transaction_id, tt.tag_id
+         FROM transaction_tags tt
+         LEFT JOIN transactions t ON t.id = tt.transaction_id
+         LEFT JOIN tags g ON g.id = tt.tag_id
+         WHERE t.id IS NULL OR g.id IS NULL
+         ORDER BY tt.transaction_id, tt.tag_id",
+    )
+    .fetch_all(&mut **db_txn)
+    .await
+}
+
+/// Delete tag links whose transaction or tag has been deleted.
+pub async fn delete_orphaned_tag_links(
+    db_txn: &mut sqlx::Transaction<'_, Sqlite>,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "DELETE FROM transaction_tags
+         WHERE transaction_id NOT IN (SELECT id FROM transactions)
+            OR tag_id NOT IN (SELECT id FROM tags)",
+    )
+    .execute(&mut **db_txn)
+    .await?;
+    Ok(())
+}
+
+/// Forget installment payments whose transaction has been deleted: without the transaction
+/// the payment is no longer recorded anywhere.
+pub async fn delete_dangling_installment_payments(
+    db_txn: &mut sqlx::Transaction<'_, Sqlite>,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "DELETE FROM installment_payments
+         WHERE transaction_id NOT IN (SELECT id FROM transactions)",
+    )
+    .execute(&mut **db_txn)
+    .await?;
+    Ok(())
+}
+
+/// Paid-off debts that have installments left to pay again, such as after their payments were
+/// reset.
+pub async fn paid_off_debts_with_unpaid_installments(
+    db_txn: &mut sqlx::Transaction<'_, Sqlite>,
+) -> Result<Vec<Debt>, sqlx::Error> {
+    sqlx::query_as::<_, Debt>(
+        "SELECT d.id, d.account_id, d.description, d.original_amount, d.total_installments,
+                d.paid_installments, d.monthly_payment, d.interest_rate, d.rate_type, d.start_date,
+                d.is_active, d.status, d.notes, d.created_at
+         FROM debts d
+         WHERE d.status = 'paid_off'
+           AND EXISTS (SELECT 1 FROM installments i WHERE i.debt_id = d.id AND i.status IN ('pending', 'partial'))
+         ORDER BY d.description",
+    )
+    .fetch_all(&mut **db_txn)
+    .await
+}
+
+/// Recount `paid_installments` on every debt from its installments.
+pub async fn recount_paid_installments(
+    db_txn: &mut sqlx::Transaction<'_, Sqlite>,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "UPDATE debts SET paid_installments = (
+             SELECT COUNT(*) FROM installments
+             WHERE installments.debt_id = debts.id AND installments.status IN ('paid', 'paid_late')
+         )",
+    )
+    .execute(&mut **db_txn)
+    .await?;
+    Ok(())
+}
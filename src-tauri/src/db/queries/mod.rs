@@ -1,6 +1,16 @@
 // Database queries organized by entity
 pub mod accounts;
+pub mod budgets;
 pub mod categories;
+pub mod csv_import;
 pub mod dashboard;
 pub mod debts;
+pub mod exchange_rates;
+pub mod export;
+pub mod integrity;
+pub mod recurring;
+pub mod settings;
+pub mod statements;
+pub mod tags;
 pub mod transactions;
+pub mod transfers;
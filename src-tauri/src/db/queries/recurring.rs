@@ -0,0 +1,121 @@
+use sqlx::SqlitePool;
+
+use crate::db::models::RecurringTransaction;
+
+/// List recurring rules ordered by next execution date.
+/// Inactive rules are only included when `include_inactive` is set.
+pub async fn get_all(
+    pool: &SqlitePool,
+    include_inactive: bool,
+) -> Result<Vec<RecurringTransaction>, sqlx::Error> {
+    sqlx::query_as::<_, RecurringTransaction>(
+        "SELECT id, account_id, category_id, amount, type, description, frequency, next_date,
+                anchor_day, end_date, is_active, created_at
+         FROM recurring_transactions
+         WHERE is_active = 1 OR ?
+         ORDER BY is_active DESC, next_date, description",
+    )
+    .bind(include_inactive)
+    .fetch_all(pool)
+    .await
+}
+
+/// Get a single recurring rule by ID.
+pub async fn get_by_id(
+    pool: &SqlitePool,
+    id: &str,
+) -> Result<Option<RecurringTransaction>, sqlx::Error> {
+    sqlx::query_as::<_, RecurringTransaction>(
+        "SELECT id, account_id, category_id, amount, type, description, frequency, next_date,
+                anchor_day, end_date, is_active, created_at
+         FROM recurring_transactions
+         WHERE id = ?",
+    )
+    .bind(id)
+    .fetch_optional(pool)
+    .await
+}
+
+/// Get active rules whose next date is on or before `today` (YYYY-MM-DD).
+pub async fn get_due(
+    pool: &SqlitePool,
+    today: &str,
+) -> Result<Vec<RecurringTransaction>, sqlx::Error> {
+    sqlx::query_as::<_, RecurringTransaction>(
+        "SELECT id, account_id, category_id, amount, type, description, frequency, next_date,
+                anchor_day, end_date, is_active, created_at
+         FROM recurring_transactions
+         WHERE is_active = 1 AND next_date <= ?
+         ORDER BY next_date",
+    )
+    .bind(today)
+    .fetch_all(pool)
+    .await
+}
+
+/// Create a new recurring rule from a fully-populated struct and return the stored row.
+pub async fn create(
+    pool: &SqlitePool,
+    rule: &RecurringTransaction,
+) -> Result<RecurringTransaction, sqlx::Error> {
+    sqlx::query(
+        "INSERT INTO recurring_transactions
+            (id, account_id, category_id, amount, type, description, frequency, next_date,
+             anchor_day, end_date)
+         VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+    )
+    .bind(&rule.id)
+    .bind(&rule.account_id)
+    .bind(&rule.category_id)
+    .bind(rule.amount)
+    .bind(&rule.transaction_type)
+    .bind(&rule.description)
+    .bind(&rule.frequency)
+    .bind(&rule.next_date)
+    .bind(rule.anchor_day)
+    .bind(&rule.end_date)
+    .execute(pool)
+    .await?;
+
+    get_by_id(pool, &rule.id)
+        .await?
+        .ok_or(sqlx::Error::RowNotFound)
+}
+
+/// Update all mutable fields of a recurring rule and return it.
+pub async fn update(
+    pool: &SqlitePool,
+    rule: &RecurringTransaction,
+) -> Result<RecurringTransaction, sqlx::Error> {
+    sqlx::query(
+        "UPDATE recurring_transactions
+         SET account_id = ?, category_id = ?, amount = ?, description = ?, frequency = ?,
+             next_date = ?, anchor_day = ?, end_date = ?, is_active = ?
+         WHERE id = ?",
+    )
+    .bind(&rule.account_id)
+    .bind(&rule.category_id)
+    .bind(rule.amount)
+    .bind(&rule.description)
+    .bind(&rule.frequency)
+    .bind(&rule.next_date)
+    .bind(rule.anchor_day)
+    .bind(&rule.end_date)
+    .bind(rule.is_active)
+    .bind(&rule.id)
+    .execute(pool)
+    .await?;
+
+    get_by_id(pool, &rule.id)
+        .await?
+        .ok_or(sqlx::Error::RowNotFound)
+}
+
+/// Delete a recurring rule. Generated transactions are kept (their link is set to NULL).
+pub async fn delete(pool: &SqlitePool, id: &str) -> Result<(), sqlx::Error> {
+    sqlx::query("DELETE FROM recurring_transactions WHERE id = ?")
+        .bind(id)
+        .execute(pool)
+        .await?;
+    Ok(())
+}
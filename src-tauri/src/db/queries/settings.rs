@@ -0,0 +1,47 @@
+use sqlx::sqlite::Sqlite;
+use sqlx::SqlitePool;
+
+/// Setting keys. Values are stored as text; typed access lives in `services::settings`.
+pub const BASE_CURRENCY_KEY: &str = "base_currency_id";
+pub const LOCALE_KEY: &str = "locale";
+pub const FIRST_DAY_OF_WEEK_KEY: &str = "first_day_of_week";
+pub const DEFAULT_ACCOUNT_KEY: &str = "default_account_id";
+pub const DEFAULT_DEBT_CATEGORY_KEY: &str = "default_debt_category_id";
+
+/// Get every stored setting as (key, value) pairs.
+pub async fn get_all(pool: &SqlitePool) -> Result<Vec<(String, String)>, sqlx::Error> {
+    sqlx::query_as("SELECT key, value FROM settings")
+        .fetch_all(pool)
+        .await
+}
+
+/// Insert or overwrite a setting within an existing SQL transaction.
+pub async fn set_value(
+    db_txn: &mut sqlx::Transaction<'_, Sqlite>,
+    key: &str,
+    value: &str,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "INSERT INTO settings (key, value) VALUES (?, ?)
+         ON CONFLICT(key) DO UPDATE SET value = excluded.value, updated_at = datetime('now')",
+    )
+    .bind(key)
+    .bind(value)
+    .execute(&mut **db_txn)
+    .await?;
+
+    Ok(())
+}
+
+/// Remove a setting so its default applies again, within an existing SQL transaction.
+pub async fn delete_value(
+    db_txn: &mut sqlx::Transaction<'_, Sqlite>,
+    key: &str,
+) -> Result<(), sqlx::Error> {
+    sqlx::query("DELETE FROM settings WHERE key = ?")
+        .bind(key)
+        .execute(&mut **db_txn)
+        .await?;
+
+    Ok(())
+}
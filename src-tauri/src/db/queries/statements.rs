@@ -0,0 +1,99 @@
+use sqlx::sqlite::Sqlite;
+use sqlx::SqlitePool;
+
+use crate::db::models::{StatementInstallment, StatementPayment, Transaction};
+
+/// Purchases and refunds on an account between two dates (inclusive), oldest first.
+/// Transfer legs, installment payments and debt prepayments are left out: installments are
+/// billed by due date.
+pub async fn get_charges(
+    pool: &SqlitePool,
+    account_id: &str,
+    date_from: &str,
+    date_to: &str,
+) -> Result<Vec<Transaction>, sqlx::Error> {
+    sqlx::query_as::<_, Transaction>(
+        "SELECT id, account_id, category_id, amount, type, description, date, notes, recurring_id,
+                transfer_id, fitid, created_at
+         FROM transactions
+         WHERE account_id = ?
+           AND date >= ?
+           AND date <= ?
+           AND transfer_id IS NULL
+           AND id NOT IN (SELECT transaction_id FROM installment_payments)
+           AND id NOT IN (SELECT transaction_id FROM debt_prepayments)
+         ORDER BY date, created_at",
+    )
+    .bind(account_id)
+    .bind(date_from)
+    .bind(date_to)
+    .fetch_all(pool)
+    .await
+}
+
+/// Installments of debts on an account falling due between two dates (inclusive), paid or not,
+/// with what was paid on each from other accounts.
+pub async fn get_installments_due(
+    pool: &SqlitePool,
+    account_id: &str,
+    date_from: &str,
+    date_to: &str,
+) -> Result<Vec<StatementInstallment>, sqlx::Error> {
+    sqlx::query_as::<_, StatementInstallment>(
+        "SELECT i.id AS installment_id, i.debt_id, d.description AS debt_description,
+                i.installment_number, d.total_installments, i.amount, i.due_date, i.status,
+                (SELECT COALESCE(SUM(t.amount), 0)
+                 FROM installment_payments p
+                 JOIN transactions t ON t.id = p.transaction_id
+                 WHERE p.installment_id = i.id AND t.account_id <> d.account_id) AS paid_elsewhere
+         FROM installments i
+         JOIN debts d ON d.id = i.debt_id
+         WHERE d.account_id = ?
+           AND i.due_date >= ?
+           AND i.due_date <= ?
+         ORDER BY i.due_date, d.description, i.installment_number",
+    )
+    .bind(account_id)
+    .bind(date_from)
+    .bind(date_to)
+    .fetch_all(pool)
+    .await
+}
+
+/// Amount credited to the card by the payments recorded against one statement.
+pub async fn get_paid_amount(
+    pool: &SqlitePool,
+    account_id: &str,
+    closing_date: &str,
+) -> Result<i64, sqlx::Error> {
+    let (paid,): (i64,) = sqlx::query_as(
+        "SELECT COALESCE(SUM(t.amount), 0)
+         FROM statement_payments sp
+         JOIN transactions t ON t.transfer_id = sp.transfer_id AND t.account_id = sp.account_id
+         WHERE sp.account_id = ? AND sp.closing_date = ? AND t.type = 'income'",
+    )
+    .bind(account_id)
+    .bind(closing_date)
+    .fetch_one(pool)
+    .await?;
+    Ok(paid)
+}
+
+/// Record a transfer as a payment of a statement.
+/// Must be called within an active SQL transaction.
+pub async fn create_payment(
+    db_txn: &mut sqlx::Transaction<'_, Sqlite>,
+    payment: &StatementPayment,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "INSERT INTO statement_payments (id, account_id, closing_date, transfer_id)
+         VALUES (?, ?, ?, ?)",
+    )
+    .bind(&payment.id)
+    .bind(&payment.account_id)
+    .bind(&payment.closing_date)
+    .bind(&payment.transfer_id)
+    .execute(&mut **db_txn)
+    .await?;
+    Ok(())
+}
@@ -0,0 +1,183 @@
+use sqlx::sqlite::Sqlite;
+use sqlx::{QueryBuilder, SqlitePool};
+
+use crate::db::models::Tag;
+
+/// Get all tags, ordered by name.
+pub async fn get_all(pool: &SqlitePool) -> Result<Vec<Tag>, sqlx::Error> {
+    sqlx::query_as::<_, Tag>(
+        "SELECT id, name, color, created_at
+         FROM tags
+         ORDER BY name",
+    )
+    .fetch_all(pool)
+    .await
+}
+
+/// Get a single tag by ID.
+pub async fn get_by_id(pool: &SqlitePool, id: &str) -> Result<Option<Tag>, sqlx::Error> {
+    sqlx::query_as::<_, Tag>(
+        "SELECT id, name, color, created_at
+         FROM tags
+         WHERE id = ?",
+    )
+    .bind(id)
+    .fetch_optional(pool)
+    .await
+}
+
+/// Get a single tag by its (unique) name, case-insensitively.
+pub async fn get_by_name(pool: &SqlitePool, name: &str) -> Result<Option<Tag>, sqlx::Error> {
+    sqlx::query_as::<_, Tag>(
+        "SELECT id, name, color, created_at
+         FROM tags
+         WHERE name = ? COLLATE NOCASE",
+    )
+    .bind(name)
+    .fetch_optional(pool)
+    .await
+}
+
+/// Create a new tag and return it.
+pub async fn create(
+    pool: &SqlitePool,
+    id: &str,
+    name: &str,
+    color: &str,
+) -> Result<Tag, sqlx::Error> {
+    sqlx::query("INSERT INTO tags (id, name, color) VALUES (?, ?, ?)")
+        .bind(id)
+        .bind(name)
+        .bind(color)
+        .execute(pool)
+        .await?;
+
+    get_by_id(pool, id).await?.ok_or(sqlx::Error::RowNotFound)
+}
+
+/// Update a tag's name and color and return it.
+pub async fn update(
+    pool: &SqlitePool,
+    id: &str,
+    name: &str,
+    color: &str,
+) -> Result<Tag, sqlx::Error> {
+    sqlx::query("UPDATE tags SET name = ?, color = ? WHERE id = ?")
+        .bind(name)
+        .bind(color)
+        .bind(id)
+        .execute(pool)
+        .await?;
+
+    get_by_id(pool, id).await?.ok_or(sqlx::Error::RowNotFound)
+}
+
+/// Delete a tag. CASCADE removes its `transaction_tags` links.
+pub async fn delete(pool: &SqlitePool, id: &str) -> Result<(), sqlx::Error> {
+    sqlx::query("DELETE FROM tags WHERE id = ?")
+        .bind(id)
+        .execute(pool)
+        .await?;
+    Ok(())
+}
+
+/// Count how many of the given tag IDs exist.
+pub async fn count_existing(pool: &SqlitePool, tag_ids: &[String]) -> Result<i64, sqlx::Error> {
+    if tag_ids.is_empty() {
+        return Ok(0);
+    }
+
+    let mut builder: QueryBuilder<Sqlite> =
+        QueryBuilder::new("SELECT COUNT(*) FROM tags WHERE id IN (");
+    let mut separated = builder.separated(", ");
+    for tag_id in tag_ids {
+        separated.push_bind(tag_id.clone());
+    }
+    separated.push_unseparated(")");
+
+    let row: (i64,) = builder.build_query_as().fetch_one(pool).await?;
+    Ok(row.0)
+}
+
+/// Get the tags assigned to a single transaction, ordered by name.
+pub async fn get_for_transaction(
+    pool: &SqlitePool,
+    transaction_id: &str,
+) -> Result<Vec<Tag>, sqlx::Error> {
+    sqlx::query_as::<_, Tag>(
+        "SELECT t.id, t.name, t.color, t.created_at
+         FROM tags t
+         JOIN transaction_tags tt ON tt.tag_id = t.id
+         WHERE tt.transaction_id = ?
+         ORDER BY t.name",
+    )
+    .bind(transaction_id)
+    .fetch_all(pool)
+    .await
+}
+
+/// Get the tags for a batch of transactions in one query.
+/// Returns (transaction_id, tag) pairs ordered by tag name.
+pub async fn get_for_transactions(
+    pool: &SqlitePool,
+    transaction_ids: &[String],
+) -> Result<Vec<(String, Tag)>, sqlx::Error> {
+    if transaction_ids.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let mut builder: QueryBuilder<Sqlite> = QueryBuilder::new(
+        "SELECT tt.transaction_id, t.id, t.name, t.color, t.created_at
+         FROM transaction_tags tt
+         JOIN tags t ON tt.tag_id = t.id
+         WHERE tt.transaction_id IN (",
+    );
+    let mut separated = builder.separated(", ");
+    for transaction_id in transaction_ids {
+        separated.push_bind(transaction_id.clone());
+    }
+    separated.push_unseparated(") ORDER BY t.name");
+
+    let rows: Vec<(String, String, String, String, String)> =
+        builder.build_query_as().fetch_all(pool).await?;
+
+    Ok(rows
+        .into_iter()
+        .map(|(transaction_id, id, name, color, created_at)| {
+            (
+                transaction_id,
+                Tag {
+                    id,
+                    name,
+                    color,
+                    created_at,
+                },
+            )
+        })
+        .collect())
+}
+
+/// Replace the full tag set of a transaction.
+/// Must be called within an active SQL transaction.
+pub async fn set_for_transaction(
+    db_txn: &mut sqlx::Transaction<'_, Sqlite>,
+    transaction_id: &str,
+    tag_ids: &[String],
+) -> Result<(), sqlx::Error> {
+    sqlx::query("DELETE FROM transaction_tags WHERE transaction_id = ?")
+        .bind(transaction_id)
+        .execute(&mut **db_txn)
+        .await?;
+
+    for tag_id in tag_ids {
+        sqlx::query(
+            "INSERT OR IGNORE INTO transaction_tags (transaction_id, tag_id) VALUES (?, ?)",
+        )
+        .bind(transaction_id)
+        .bind(tag_id)
+        .execute(&mut **db_txn)
+        .await?;
+    }
+
+    Ok(())
+}
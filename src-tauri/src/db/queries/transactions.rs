@@ -1,3 +1,5 @@
+use std::collections::HashSet;
+
 use sqlx::sqlite::Sqlite;
 use sqlx::{QueryBuilder, SqlitePool};
 
@@ -37,12 +39,35 @@ fn apply_filters(builder: &mut QueryBuilder<'_, Sqlite>, filter: &TransactionFil
         let pattern = format!("%{search}%");
         builder.push(" AND description LIKE ").push_bind(pattern);
     }
+    if let Some(ref tag_ids) = filter.tag_ids {
+        if !tag_ids.is_empty() {
+            builder
+                .push(" AND id IN (SELECT transaction_id FROM transaction_tags WHERE tag_id IN (");
+            let mut separated = builder.separated(", ");
+            for tag_id in tag_ids {
+                separated.push_bind(tag_id.clone());
+            }
+            separated.push_unseparated(")");
+
+            // "all" requires every requested tag to be present on the transaction
+            if filter.tag_match.as_deref() == Some("all") {
+                let unique: HashSet<&String> = tag_ids.iter().collect();
+                #[allow(clippy::cast_possible_wrap)]
+                let required = unique.len() as i64;
+                builder
+                    .push(" GROUP BY transaction_id HAVING COUNT(DISTINCT tag_id) = ")
+                    .push_bind(required);
+            }
+            builder.push(")");
+        }
+    }
 }
 
 /// Get a single transaction by ID.
 pub async fn get_by_id(pool: &SqlitePool, id: &str) -> Result<Option<Transaction>, sqlx::Error> {
     sqlx::query_as::<_, Transaction>(
-        "SELECT id, account_id, category_id, amount, type, description, date, notes, created_at
+        "SELECT id, account_id, category_id, amount, type, description, date, notes, recurring_id,
+                transfer_id, fitid, created_at
          FROM transactions
          WHERE id = ?",
     )
@@ -141,7 +166,8 @@ pub async fn list_filtered(
     filter: &TransactionFilter,
 ) -> Result<Vec<Transaction>, sqlx::Error> {
     let mut builder: QueryBuilder<Sqlite> = QueryBuilder::new(
-        "SELECT id, account_id, category_id, amount, type, description, date, notes, created_at
+        "SELECT id, account_id, category_id, amount, type, description, date, notes, recurring_id,
+                transfer_id, fitid, created_at
          FROM transactions WHERE 1=1",
     );
 
@@ -188,3 +214,71 @@ pub async fn get_account_id_for_transaction(
         .await?;
     Ok(row.0)
 }
+
+/// Get the FITIDs of an account's imported transactions, used to skip re-imported lines.
+pub async fn get_fitids(pool: &SqlitePool, account_id: &str) -> Result<Vec<String>, sqlx::Error> {
+    let rows: Vec<(String,)> =
+        sqlx::query_as("SELECT fitid FROM transactions WHERE account_id = ? AND fitid IS NOT NULL")
+            .bind(account_id)
+            .fetch_all(pool)
+            .await?;
+    Ok(rows.into_iter().map(|(fitid,)| fitid).collect())
+}
+
+/// Rank the distinct descriptions starting with `prefix` (case-insensitive) by how often
+/// they were used, each use weighted by its age relative to `today`: a 30-day-old use counts
+/// half as much as one from today. Descriptions differing only in case are one suggestion,
+/// spelled as last used. Transfer legs are excluded.
+/// Returns `(description, use_count, last_used)` tuples, best match first.
+pub async fn rank_descriptions(
+    pool: &SqlitePool,
+    prefix: &str,
+    today: &str,
+    limit: i64,
+) -> Result<Vec<(String, i64, String)>, sqlx::Error> {
+    // Escape LIKE wildcards so the prefix is matched literally
+    let escaped = prefix
+        .replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_");
+
+    sqlx::query_as::<_, (String, i64, String)>(
+        "SELECT description, COUNT(*), MAX(date)
+         FROM transactions
+         WHERE description LIKE ? ESCAPE '\\'
+           AND transfer_id IS NULL
+         GROUP BY description COLLATE NOCASE
+         ORDER BY SUM(1.0 / (1.0 + MAX(julianday(?) - julianday(date), 0) / 30.0)) DESC,
+                  MAX(date) DESC
+         LIMIT ?",
+    )
+    .bind(format!("{escaped}%"))
+    .bind(today)
+    .bind(limit)
+    .fetch_all(pool)
+    .await
+}
+
+/// Fetch every non-transfer transaction with one of the given descriptions, ignoring case,
+/// newest first.
+/// Returns `(description, category_id, account_id, amount, type)` tuples.
+pub async fn get_by_descriptions(
+    pool: &SqlitePool,
+    descriptions: &[String],
+) -> Result<Vec<(String, String, String, i64, String)>, sqlx::Error> {
+    let mut builder: QueryBuilder<Sqlite> = QueryBuilder::new(
+        "SELECT description, category_id, account_id, amount, type
+         FROM transactions
+         WHERE transfer_id IS NULL AND description COLLATE NOCASE IN (",
+    );
+    let mut separated = builder.separated(", ");
+    for description in descriptions {
+        separated.push_bind(description.clone());
+    }
+    separated.push_unseparated(") ORDER BY date DESC, created_at DESC");
+
+    builder
+        .build_query_as::<(String, String, String, i64, String)>()
+        .fetch_all(pool)
+        .await
+}
@@ -0,0 +1,157 @@
+use sqlx::sqlite::Sqlite;
+use sqlx::SqlitePool;
+
+use crate::db::models::Transfer;
+
+/// Category assigned to the outgoing (expense) leg of every transfer.
+pub const OUTGOING_CATEGORY_ID: &str = "cat_transferencia_salida";
+
+/// Category assigned to the incoming (income) leg of every transfer.
+pub const INCOMING_CATEGORY_ID: &str = "cat_transferencia_entrada";
+
+/// Shared SELECT joining a transfer with its outgoing and incoming legs.
+const TRANSFER_SELECT: &str =
+    "SELECT tr.id, o.id AS from_transaction_id, i.id AS to_transaction_id,
+        o.account_id AS from_account_id, i.account_id AS to_account_id,
+        o.amount AS from_amount, i.amount AS to_amount, tr.rate,
+        o.description, o.date, o.notes, tr.created_at
+     FROM transfers tr
+     JOIN transactions o ON o.transfer_id = tr.id AND o.type = 'expense'
+     JOIN transactions i ON i.transfer_id = tr.id AND i.type = 'income'";
+
+/// Get a single transfer with both legs by ID.
+pub async fn get_by_id(pool: &SqlitePool, id: &str) -> Result<Option<Transfer>, sqlx::Error> {
+    sqlx::query_as::<_, Transfer>(&format!("{TRANSFER_SELECT} WHERE tr.id = ?"))
+        .bind(id)
+        .fetch_optional(pool)
+        .await
+}
+
+/// List transfers, newest first, optionally limited to those touching one account.
+pub async fn get_all(
+    pool: &SqlitePool,
+    account_id: Option<&str>,
+) -> Result<Vec<Transfer>, sqlx::Error> {
+    sqlx::query_as::<_, Transfer>(&format!(
+        "{TRANSFER_SELECT}
+         WHERE ? IS NULL OR o.account_id = ? OR i.account_id = ?
+         ORDER BY o.date DESC, tr.created_at DESC"
+    ))
+    .bind(account_id)
+    .bind(account_id)
+    .bind(account_id)
+    .fetch_all(pool)
+    .await
+}
+
+/// Insert a transfer row and its two legs.
+/// Must be called within an active SQL transaction.
+pub async fn create(
+    db_txn: &mut sqlx::Transaction<'_, Sqlite>,
+    transfer: &Transfer,
+) -> Result<(), sqlx::Error> {
+    sqlx::query("INSERT INTO transfers (id, rate) VALUES (?, ?)")
+        .bind(&transfer.id)
+        .bind(transfer.rate)
+        .execute(&mut **db_txn)
+        .await?;
+
+    let legs = [
+        (
+            &transfer.from_transaction_id,
+            &transfer.from_account_id,
+            OUTGOING_CATEGORY_ID,
+            transfer.from_amount,
+            "expense",
+        ),
+        (
+            &transfer.to_transaction_id,
+            &transfer.to_account_id,
+            INCOMING_CATEGORY_ID,
+            transfer.to_amount,
+            "income",
+        ),
+    ];
+
+    for (id, account_id, category_id, amount, transaction_type) in legs {
+        sqlx::query(
+            "INSERT INTO transactions
+                (id, account_id, category_id, amount, type, description, date, notes, transfer_id)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)",
+        )
+        .bind(id)
+        .bind(account_id)
+        .bind(category_id)
+        .bind(amount)
+        .bind(transaction_type)
+        .bind(&transfer.description)
+        .bind(&transfer.date)
+        .bind(&transfer.notes)
+        .bind(&transfer.id)
+        .execute(&mut **db_txn)
+        .await?;
+    }
+
+    Ok(())
+}
+
+/// Rewrite a transfer's rate and both legs from the given values.
+/// Must be called within an active SQL transaction.
+pub async fn update(
+    db_txn: &mut sqlx::Transaction<'_, Sqlite>,
+    transfer: &Transfer,
+) -> Result<(), sqlx::Error> {
+    sqlx::query("UPDATE transfers SET rate = ? WHERE id = ?")
+        .bind(transfer.rate)
+        .bind(&transfer.id)
+        .execute(&mut **db_txn)
+        .await?;
+
+    let legs = [
+        (
+            &transfer.from_transaction_id,
+            &transfer.from_account_id,
+            transfer.from_amount,
+        ),
+        (
+            &transfer.to_transaction_id,
+            &transfer.to_account_id,
+            transfer.to_amount,
+        ),
+    ];
+
+    for (id, account_id, amount) in legs {
+        sqlx::query(
+            "UPDATE transactions
+             SET account_id = ?, amount = ?, description = ?, date = ?, notes = ?
+             WHERE id = ?",
+        )
+        .bind(account_id)
+        .bind(amount)
+        .bind(&transfer.description)
+        .bind(&transfer.date)
+        .bind(&transfer.notes)
+        .bind(id)
+        .execute(&mut **db_txn)
+        .await?;
+    }
+
+    Ok(())
+}
+
+/// Delete a transfer together with both legs.
+/// Must be called within an active SQL transaction.
+pub async fn delete(
+    db_txn: &mut sqlx::Transaction<'_, Sqlite>,
+    id: &str,
+) -> Result<(), sqlx::Error> {
+    sqlx::query("DELETE FROM transactions WHERE transfer_id = ?")
+        .bind(id)
+        .execute(&mut **db_txn)
+        .await?;
+    sqlx::query("DELETE FROM transfers WHERE id = ?")
+        .bind(id)
+        .execute(&mut **db_txn)
+        .await?;
+    Ok(())
+}
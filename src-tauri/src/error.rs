@@ -0,0 +1,192 @@
+use std::fmt;
+
+use serde::Serialize;
+use sqlx::error::ErrorKind;
+
+use crate::services::i18n::{t, t_with};
+
+/// Primary result codes of a busy or locked database.
+const SQLITE_BUSY: i32 = 5;
+const SQLITE_LOCKED: i32 = 6;
+
+/// Error returned by Tauri commands. Serialized as `{ code, field, message }`: `code` is a
+/// stable identifier the frontend can branch on, `field` names the offending input (in the
+/// camelCase used by the frontend) when there is one, and `message` is ready to display.
+#[derive(Debug, Serialize)]
+#[serde(tag = "code", rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum AppError {
+    /// Input breaks a business rule or a CHECK / NOT NULL constraint.
+    Validation {
+        field: Option<String>,
+        message: String,
+    },
+    /// A requested or referenced record does not exist.
+    NotFound {
+        field: Option<String>,
+        message: String,
+    },
+    /// The change clashes with existing data, e.g. a UNIQUE constraint or a record in use.
+    Conflict {
+        field: Option<String>,
+        message: String,
+    },
+    /// A FOREIGN KEY constraint failed.
+    ForeignKey {
+        field: Option<String>,
+        message: String,
+    },
+    /// The database is locked by another connection; retrying may succeed.
+    DatabaseBusy {
+        field: Option<String>,
+        message: String,
+    },
+    /// Any other database failure.
+    Database {
+        field: Option<String>,
+        message: String,
+    },
+}
+
+impl AppError {
+    pub fn validation(message: impl Into<String>) -> Self {
+        Self::Validation {
+            field: None,
+            message: message.into(),
+        }
+    }
+
+    pub fn not_found(message: impl Into<String>) -> Self {
+        Self::NotFound {
+            field: None,
+            message: message.into(),
+        }
+    }
+
+    pub fn conflict(message: impl Into<String>) -> Self {
+        Self::Conflict {
+            field: None,
+            message: message.into(),
+        }
+    }
+
+    pub fn database(message: impl Into<String>) -> Self {
+        Self::Database {
+            field: None,
+            message: message.into(),
+        }
+    }
+
+    /// Attach the input field the error refers to.
+    #[must_use]
+    pub fn on(mut self, name: &str) -> Self {
+        match &mut self {
+            Self::Validation { field, .. }
+            | Self::NotFound { field, .. }
+            | Self::Conflict { field, .. }
+            | Self::ForeignKey { field, .. }
+            | Self::DatabaseBusy { field, .. }
+            | Self::Database { field, .. } => *field = Some(name.to_string()),
+        }
+        self
+    }
+
+    pub fn message(&self) -> &str {
+        match self {
+            Self::Validation { message, .. }
+            | Self::NotFound { message, .. }
+            | Self::Conflict { message, .. }
+            | Self::ForeignKey { message, .. }
+            | Self::DatabaseBusy { message, .. }
+            | Self::Database { message, .. } => message,
+        }
+    }
+}
+
+impl fmt::Display for AppError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.message())
+    }
+}
+
+impl std::error::Error for AppError {}
+
+/// Lets commands that still return `Result<_, String>` call helpers returning `AppError`.
+impl From<AppError> for String {
+    fn from(error: AppError) -> Self {
+        error.message().to_string()
+    }
+}
+
+/// Column named in a constraint message such as
+/// `UNIQUE constraint failed: budgets.category_id`, converted to camelCase.
+fn constraint_field(message: &str) -> Option<String> {
+    let column = message
+        .split_once(": ")?
+        .1
+        .split(", ")
+        .next()?
+        .rsplit('.')
+        .next()?;
+
+    let mut field = String::with_capacity(column.len());
+    let mut upper = false;
+    for c in column.chars() {
+        if c == '_' {
+            upper = true;
+        } else if upper {
+            field.extend(c.to_uppercase());
+            upper = false;
+        } else {
+            field.push(c);
+        }
+    }
+    (!field.is_empty()).then_some(field)
+}
+
+impl From<sqlx::Error> for AppError {
+    fn from(error: sqlx::Error) -> Self {
+        match error {
+            sqlx::Error::RowNotFound => Self::not_found(t("db.not_found")),
+            sqlx::Error::PoolTimedOut => Self::DatabaseBusy {
+                field: None,
+                message: t("db.busy"),
+            },
+            sqlx::Error::Database(db_error) => {
+                let field = constraint_field(db_error.message());
+                match db_error.kind() {
+                    ErrorKind::UniqueViolation => Self::Conflict {
+                        field,
+                        message: t("db.unique"),
+                    },
+                    ErrorKind::ForeignKeyViolation => Self::ForeignKey {
+                        field,
+                        message: t("db.foreign_key"),
+                    },
+                    ErrorKind::NotNullViolation => Self::Validation {
+                        field,
+                        message: t("db.not_null"),
+                    },
+                    ErrorKind::CheckViolation => Self::Validation {
+                        field: None,
+                        message: t_with("db.check", &[("detail", &db_error.message())]),
+                    },
+                    _ => {
+                        let primary = db_error
+                            .code()
+                            .and_then(|code| code.parse::<i32>().ok())
+                            .map(|code| code & 0xff);
+                        if matches!(primary, Some(SQLITE_BUSY | SQLITE_LOCKED)) {
+                            Self::DatabaseBusy {
+                                field: None,
+                                message: t("db.busy"),
+                            }
+                        } else {
+                            Self::database(db_error.message())
+                        }
+                    }
+                }
+            }
+            other => Self::database(other.to_string()),
+        }
+    }
+}
@@ -1,7 +1,16 @@
-mod commands;
 mod db;
+mod error;
 mod services;
 
+// Only exposed for the integration tests in `tests/`
+pub mod commands;
+pub use db::models;
+pub use db::{dry_run_migrations, rollback_last_migration, run_migrations};
+pub use error::AppError;
+pub use services::{amortization, backup, i18n, ofx, statements};
+
+use std::sync::Mutex;
+
 use tauri::Manager;
 
 /// Initialize the database during app setup.
@@ -12,11 +21,35 @@ async fn init_database(app: &tauri::AppHandle) -> Result<(), Box<dyn std::error:
         .app_data_dir()
         .expect("failed to resolve app data dir");
 
-    let pool = db::create_pool(&app_data_dir).await?;
+    let db_path = app_data_dir.join(db::DATABASE_FILE);
+    let pool = db::create_pool(&db_path).await?;
+
+    // Snapshot the data before migrations touch it and rotate old snapshots.
+    // A failed backup must not prevent the app from opening.
+    let now = chrono::Local::now().naive_local();
+    if let Err(e) = services::backup::on_startup(&pool, &db_path, now).await {
+        eprintln!("Failed to back up the database: {e}");
+    }
+
     db::run_migrations(&pool).await?;
+    commands::settings::restore_locale(&pool).await?;
+
+    // Materialize recurring transactions that came due while the app was closed.
+    // A failure here must not prevent the app from opening.
+    let today = chrono::Local::now().date_naive();
+    let recurring_summary = match services::recurring::process_due(&pool, today).await {
+        Ok(summary) => Some(summary),
+        Err(e) => {
+            eprintln!("Failed to process recurring transactions: {e}");
+            None
+        }
+    };
+    app.manage(services::recurring::StartupRecurringSummary(Mutex::new(
+        recurring_summary,
+    )));
 
     // Store the pool in Tauri's managed state for later use
-    app.manage(pool);
+    app.manage(db::Database::new(db_path, pool));
 
     Ok(())
 }
@@ -26,32 +59,86 @@ pub fn run() {
     tauri::Builder::default()
         .plugin(tauri_plugin_opener::init())
         .invoke_handler(tauri::generate_handler![
-            commands::accounts::list_accounts,
-            commands::accounts::get_account,
-            commands::accounts::create_account,
-            commands::accounts::update_account,
-            commands::accounts::archive_account,
-            commands::accounts::delete_account,
-            commands::accounts::list_currencies,
-            commands::categories::list_categories,
-            commands::categories::get_category,
-            commands::categories::create_category,
-            commands::categories::update_category,
-            commands::categories::delete_category,
-            commands::transactions::create_transaction,
-            commands::transactions::update_transaction,
-            commands::transactions::delete_transaction,
-            commands::transactions::list_transactions,
-            commands::transactions::get_balance_summary,
-            commands::debts::create_debt,
-            commands::debts::update_debt,
-            commands::debts::delete_debt,
-            commands::debts::list_debts,
-            commands::debts::get_debt_detail,
-            commands::debts::mark_installment_paid,
-            commands::debts::get_credit_utilization,
-            commands::debts::get_payment_projections,
-            commands::dashboard::get_dashboard_data,
+            commands::accounts::ipc::list_accounts,
+            commands::accounts::ipc::get_account,
+            commands::accounts::ipc::create_account,
+            commands::accounts::ipc::update_account,
+            commands::accounts::ipc::archive_account,
+            commands::accounts::ipc::delete_account,
+            commands::accounts::ipc::list_currencies,
+            commands::categories::ipc::list_categories,
+            commands::categories::ipc::get_category,
+            commands::categories::ipc::create_category,
+            commands::categories::ipc::update_category,
+            commands::categories::ipc::delete_category,
+            commands::transactions::ipc::create_transaction,
+            commands::transactions::ipc::update_transaction,
+            commands::transactions::ipc::delete_transaction,
+            commands::transactions::ipc::list_transactions,
+            commands::transactions::ipc::get_balance_summary,
+            commands::transactions::ipc::get_suggestions,
+            commands::debts::ipc::create_debt,
+            commands::debts::ipc::update_debt,
+            commands::debts::ipc::delete_debt,
+            commands::debts::ipc::list_debts,
+            commands::debts::ipc::change_debt_status,
+            commands::debts::ipc::list_debt_status_history,
+            commands::debts::ipc::get_debt_detail,
+            commands::debts::ipc::mark_installment_paid,
+            commands::debts::ipc::unmark_installment_paid,
+            commands::debts::ipc::prepay_debt,
+            commands::debts::ipc::pay_off_debt,
+            commands::debts::ipc::get_credit_utilization,
+            commands::debts::ipc::get_payment_projections,
+            commands::dashboard::ipc::get_dashboard_data,
+            commands::dashboard::ipc::get_monthly_comparison,
+            commands::balance_history::ipc::get_balance_history,
+            commands::exchange_rates::ipc::set_exchange_rate,
+            commands::exchange_rates::ipc::get_exchange_rate,
+            commands::exchange_rates::ipc::list_exchange_rates,
+            commands::exchange_rates::ipc::delete_exchange_rate,
+            commands::exchange_rates::ipc::convert_amount,
+            commands::tags::ipc::list_tags,
+            commands::tags::ipc::create_tag,
+            commands::tags::ipc::update_tag,
+            commands::tags::ipc::delete_tag,
+            commands::tags::ipc::set_transaction_tags,
+            commands::recurring::ipc::list_recurring,
+            commands::recurring::ipc::create_recurring,
+            commands::recurring::ipc::update_recurring,
+            commands::recurring::ipc::delete_recurring,
+            commands::recurring::ipc::process_recurring,
+            commands::recurring::ipc::take_startup_recurring_summary,
+            commands::export::ipc::export_data,
+            commands::export::ipc::import_data,
+            commands::transfers::ipc::list_transfers,
+            commands::transfers::ipc::create_transfer,
+            commands::transfers::ipc::update_transfer,
+            commands::transfers::ipc::delete_transfer,
+            commands::csv_import::ipc::list_csv_import_profiles,
+            commands::csv_import::ipc::create_csv_import_profile,
+            commands::csv_import::ipc::update_csv_import_profile,
+            commands::csv_import::ipc::delete_csv_import_profile,
+            commands::csv_import::ipc::preview_csv_import,
+            commands::csv_import::ipc::commit_csv_import,
+            commands::ofx_import::ipc::preview_ofx_import,
+            commands::ofx_import::ipc::import_ofx,
+            commands::budgets::ipc::list_budgets,
+            commands::budgets::ipc::create_budget,
+            commands::budgets::ipc::update_budget,
+            commands::budgets::ipc::delete_budget,
+            commands::budgets::ipc::get_budget_status,
+            commands::settings::ipc::get_settings,
+            commands::settings::ipc::update_settings,
+            commands::backups::ipc::list_backups,
+            commands::backups::ipc::restore_backup,
+            commands::integrity::ipc::verify_integrity,
+            commands::integrity::ipc::repair_integrity,
+            commands::migrations::ipc::dry_run_migrations,
+            commands::migrations::ipc::rollback_last_migration,
+            commands::statements::ipc::list_statements,
+            commands::statements::ipc::get_statement,
+            commands::statements::ipc::settle_statement,
         ])
         .setup(|app| {
             let handle = app.handle().clone();
@@ -0,0 +1,128 @@
+/// How a debt's `interest_rate` (a percentage) is quoted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RateType {
+    /// Monthly rate, applied as is.
+    Monthly,
+    /// Nominal annual rate, divided evenly across the twelve months.
+    Annual,
+    /// Chilean CAE (carga anual equivalente): an effective annual rate, compounded monthly.
+    Cae,
+}
+
+impl RateType {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "monthly" => Some(Self::Monthly),
+            "annual" => Some(Self::Annual),
+            "cae" => Some(Self::Cae),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Monthly => "monthly",
+            Self::Annual => "annual",
+            Self::Cae => "cae",
+        }
+    }
+
+    /// Monthly rate, as a fraction, of a rate of `percent` quoted this way.
+    pub fn monthly_rate(self, percent: f64) -> f64 {
+        let rate = percent / 100.0;
+        match self {
+            Self::Monthly => rate,
+            Self::Annual => rate / 12.0,
+            Self::Cae => (1.0 + rate).powf(1.0 / 12.0) - 1.0,
+        }
+    }
+}
+
+/// One installment of an amortization schedule, in minor units.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ScheduleRow {
+    pub amount: i64,
+    pub principal: i64,
+    pub interest: i64,
+    /// Principal still owed after this installment.
+    pub balance: i64,
+}
+
+fn round(value: f64) -> i64 {
+    #[allow(clippy::cast_possible_truncation)]
+    let rounded = value.round() as i64;
+    rounded
+}
+
+/// French-system (level) payment that repays `principal` in `periods` monthly installments
+/// at `monthly_rate`, rounded to the nearest minor unit.
+pub fn level_payment(principal: i64, monthly_rate: f64, periods: u32) -> i64 {
+    #[allow(clippy::cast_precision_loss)]
+    let principal = principal as f64;
+    let periods = f64::from(periods.max(1));
+    if monthly_rate.abs() < f64::EPSILON {
+        return round(principal / periods);
+    }
+    round(principal * monthly_rate / (1.0 - (1.0 + monthly_rate).powf(-periods)))
+}
+
+/// Split `periods` installments of `payment` into interest on the outstanding balance and
+/// principal. The last installment repays whatever principal is left, so rounding never
+/// leaves a balance behind.
+///
+/// Returns `None` when `payment` cannot repay the debt in exactly `periods` installments:
+/// it does not cover the interest of a month, or the debt is repaid before the last one.
+pub fn schedule(
+    principal: i64,
+    monthly_rate: f64,
+    periods: u32,
+    payment: i64,
+) -> Option<Vec<ScheduleRow>> {
+    let mut balance = principal;
+    let mut rows = Vec::new();
+
+    for number in 1..=periods {
+        if balance <= 0 {
+            return None;
+        }
+        #[allow(clippy::cast_precision_loss)]
+        let interest = round(balance as f64 * monthly_rate);
+        let principal = if number == periods {
+            balance
+        } else {
+            payment - interest
+        };
+        if principal <= 0 {
+            return None;
+        }
+
+        balance -= principal;
+        rows.push(ScheduleRow {
+            amount: principal + interest,
+            principal,
+            interest,
+            balance,
+        });
+    }
+
+    Some(rows)
+}
+
+/// Number of installments of `payment` needed to repay `principal` at `monthly_rate`, the
+/// last one possibly smaller. `None` when `payment` does not cover the interest of a month.
+pub fn term_for_payment(principal: i64, monthly_rate: f64, payment: i64) -> Option<u32> {
+    let mut balance = principal;
+    let mut periods = 0;
+
+    while balance > 0 {
+        #[allow(clippy::cast_precision_loss)]
+        let interest = round(balance as f64 * monthly_rate);
+        if payment <= interest {
+            return None;
+        }
+        balance -= payment - interest;
+        periods += 1;
+    }
+
+    Some(periods)
+}
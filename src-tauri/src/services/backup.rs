@@ -0,0 +1,310 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use chrono::{Datelike, NaiveDateTime};
+use sqlx::sqlite::SqliteConnectOptions;
+use sqlx::{ConnectOptions, Connection, SqlitePool};
+
+use crate::db;
+use crate::db::models::BackupSnapshot;
+use crate::services::i18n::{t, t_with};
+
+/// Folder, next to the database file, that holds the snapshots.
+pub const BACKUP_DIR: &str = "backups";
+
+/// Most recent snapshots kept whatever their age, so a bad change can still be undone after
+/// restarting the app on the same day.
+pub const KEEP_LAST: usize = 5;
+/// Number of days for which the newest snapshot of the day is kept.
+pub const KEEP_DAILY: usize = 7;
+/// Number of ISO weeks for which the newest snapshot of the week is kept.
+pub const KEEP_WEEKLY: usize = 4;
+
+const FILE_PREFIX: &str = "necronomics-";
+const FILE_EXTENSION: &str = ".db";
+const TIMESTAMP_FORMAT: &str = "%Y%m%d-%H%M%S";
+
+/// Why a snapshot was taken. Stored in the file name.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SnapshotReason {
+    Startup,
+    PreMigration,
+    PreRestore,
+}
+
+impl SnapshotReason {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Startup => "startup",
+            Self::PreMigration => "pre_migration",
+            Self::PreRestore => "pre_restore",
+        }
+    }
+
+    fn parse(value: &str) -> Option<Self> {
+        match value {
+            "startup" => Some(Self::Startup),
+            "pre_migration" => Some(Self::PreMigration),
+            "pre_restore" => Some(Self::PreRestore),
+            _ => None,
+        }
+    }
+}
+
+/// Folder where the snapshots of the database at `db_path` are kept.
+pub fn backup_dir(db_path: &Path) -> PathBuf {
+    db_path
+        .parent()
+        .unwrap_or_else(|| Path::new("."))
+        .join(BACKUP_DIR)
+}
+
+/// File name of a snapshot, e.g. `necronomics-20250301-093000-startup.db`.
+pub fn file_name(created_at: NaiveDateTime, reason: SnapshotReason) -> String {
+    format!(
+        "{FILE_PREFIX}{}-{}{FILE_EXTENSION}",
+        created_at.format(TIMESTAMP_FORMAT),
+        reason.as_str()
+    )
+}
+
+/// Time and reason encoded in a snapshot file name. `None` for any other file.
+pub fn parse_file_name(name: &str) -> Option<(NaiveDateTime, SnapshotReason)> {
+    let stem = name
+        .strip_prefix(FILE_PREFIX)?
+        .strip_suffix(FILE_EXTENSION)?;
+    // The timestamp is `YYYYMMDD-HHMMSS`, followed by `-` and the reason
+    let (timestamp, reason) = (stem.get(..15)?, stem.get(15..)?.strip_prefix('-')?);
+
+    let created_at = NaiveDateTime::parse_from_str(timestamp, TIMESTAMP_FORMAT).ok()?;
+    Some((created_at, SnapshotReason::parse(reason)?))
+}
+
+/// Every snapshot in `dir`, newest first. A missing folder just means no snapshots yet.
+///
+/// # Errors
+///
+/// Returns an error when the folder cannot be read.
+pub fn list(dir: &Path) -> Result<Vec<BackupSnapshot>, String> {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(t_with("file.read_failed", &[("error", &e)])),
+    };
+
+    let mut snapshots = Vec::new();
+    for entry in entries {
+        let entry = entry.map_err(|e| t_with("file.read_failed", &[("error", &e)]))?;
+        let name = entry.file_name().to_string_lossy().into_owned();
+        let Some((created_at, reason)) = parse_file_name(&name) else {
+            continue;
+        };
+        let metadata = entry
+            .metadata()
+            .map_err(|e| t_with("file.read_failed", &[("error", &e)]))?;
+
+        snapshots.push(BackupSnapshot {
+            file_name: name,
+            created_at: created_at.format("%Y-%m-%d %H:%M:%S").to_string(),
+            reason: reason.as_str().to_string(),
+            size_bytes: metadata.len(),
+        });
+    }
+
+    // The timestamp format sorts chronologically as text
+    snapshots.sort_by(|a, b| {
+        b.created_at
+            .cmp(&a.created_at)
+            .then_with(|| b.file_name.cmp(&a.file_name))
+    });
+    Ok(snapshots)
+}
+
+/// Indexes of the snapshots that fall outside the retention policy: the `KEEP_LAST` most
+/// recent, plus the newest of each of the last `KEEP_DAILY` days and `KEEP_WEEKLY` weeks.
+/// `created_at` must be sorted newest first.
+pub fn expired(created_at: &[NaiveDateTime]) -> Vec<usize> {
+    let mut keep: Vec<bool> = (0..created_at.len()).map(|i| i < KEEP_LAST).collect();
+    let mut days = Vec::new();
+    let mut weeks = Vec::new();
+
+    for (i, at) in created_at.iter().enumerate() {
+        let day = at.date();
+        if !days.contains(&day) && days.len() < KEEP_DAILY {
+            days.push(day);
+            keep[i] = true;
+        }
+
+        let week = day.iso_week();
+        if !weeks.contains(&week) && weeks.len() < KEEP_WEEKLY {
+            weeks.push(week);
+            keep[i] = true;
+        }
+    }
+
+    keep.iter()
+        .enumerate()
+        .filter(|(_, kept)| !**kept)
+        .map(|(i, _)| i)
+        .collect()
+}
+
+/// Delete the snapshots in `dir` that fall outside the retention policy. Returns how many
+/// were deleted.
+///
+/// # Errors
+///
+/// Returns an error when the folder cannot be read or a snapshot cannot be deleted.
+pub fn prune(dir: &Path) -> Result<usize, String> {
+    let snapshots = list(dir)?;
+    let created_at: Vec<NaiveDateTime> = snapshots
+        .iter()
+        .filter_map(|s| parse_file_name(&s.file_name).map(|(at, _)| at))
+        .collect();
+
+    let expired = expired(&created_at);
+    for &i in &expired {
+        fs::remove_file(dir.join(&snapshots[i].file_name))
+            .map_err(|e| t_with("file.write_failed", &[("error", &e)]))?;
+    }
+    Ok(expired.len())
+}
+
+/// Write a consistent copy of the database to `dir` with `VACUUM INTO`. Safe while other
+/// connections are in use.
+///
+/// # Errors
+///
+/// Returns an error when the folder cannot be created or SQLite fails to write the copy.
+pub async fn create(
+    pool: &SqlitePool,
+    dir: &Path,
+    reason: SnapshotReason,
+    now: NaiveDateTime,
+) -> Result<PathBuf, String> {
+    fs::create_dir_all(dir).map_err(|e| t_with("file.write_failed", &[("error", &e)]))?;
+
+    // Two snapshots in the same second hold the same data, so an existing file is reused
+    let path = dir.join(file_name(now, reason));
+    if !path.exists() {
+        sqlx::query("VACUUM INTO ?")
+            .bind(path.to_string_lossy().into_owned())
+            .execute(pool)
+            .await
+            .map_err(|e| e.to_string())?;
+    }
+
+    Ok(path)
+}
+
+/// Snapshot the database at startup and rotate old snapshots. The snapshot is tagged
+/// `pre_migration` when migrations are about to run. A database that has never been migrated
+/// holds no data, so nothing is saved.
+///
+/// # Errors
+///
+/// Returns an error when the snapshot cannot be written or old ones cannot be deleted.
+pub async fn on_startup(
+    pool: &SqlitePool,
+    db_path: &Path,
+    now: NaiveDateTime,
+) -> Result<(), String> {
+    let pending = db::dry_run_migrations(pool)
+        .await
+        .map_err(|e| e.to_string())?;
+    if pending.len() == db::MIGRATIONS.len() {
+        return Ok(());
+    }
+
+    let reason = if pending.is_empty() {
+        SnapshotReason::Startup
+    } else {
+        SnapshotReason::PreMigration
+    };
+
+    let dir = backup_dir(db_path);
+    create(pool, &dir, reason, now).await?;
+    prune(&dir)?;
+    Ok(())
+}
+
+/// Snapshot the database as `pre_migration` when migrations are about to run on it, so a
+/// failed migration leaves something to roll back to. Returns whether a snapshot was taken.
+///
+/// # Errors
+///
+/// Returns an error when the pending migrations cannot be read or the snapshot cannot be
+/// written.
+pub async fn before_migrations(
+    pool: &SqlitePool,
+    dir: &Path,
+    now: NaiveDateTime,
+) -> Result<bool, String> {
+    let pending = db::dry_run_migrations(pool)
+        .await
+        .map_err(|e| e.to_string())?;
+    if pending.is_empty() {
+        return Ok(false);
+    }
+    create(pool, dir, SnapshotReason::PreMigration, now).await?;
+    Ok(true)
+}
+
+/// Check that a snapshot is an intact Necronomics database before restoring it.
+///
+/// # Errors
+///
+/// Returns an error when the file cannot be opened, fails `PRAGMA integrity_check` or has
+/// no `_migrations` table.
+pub async fn verify(path: &Path) -> Result<(), String> {
+    let mut conn = SqliteConnectOptions::new()
+        .filename(path)
+        .read_only(true)
+        .connect()
+        .await
+        .map_err(|e| t_with("backup.corrupt", &[("detail", &e)]))?;
+
+    let (check,): (String,) = sqlx::query_as("PRAGMA integrity_check")
+        .fetch_one(&mut conn)
+        .await
+        .map_err(|e| t_with("backup.corrupt", &[("detail", &e)]))?;
+    if check != "ok" {
+        return Err(t_with("backup.corrupt", &[("detail", &check)]));
+    }
+
+    let tracked: Option<(String,)> = sqlx::query_as(
+        "SELECT name FROM sqlite_master WHERE type = 'table' AND name = '_migrations'",
+    )
+    .fetch_optional(&mut conn)
+    .await
+    .map_err(|e| e.to_string())?;
+    if tracked.is_none() {
+        return Err(t("backup.not_necronomics"));
+    }
+
+    conn.close().await.map_err(|e| e.to_string())
+}
+
+/// Copy a snapshot over the database file. Every connection to the database must be closed.
+///
+/// # Errors
+///
+/// Returns an error when the snapshot cannot be copied or the old WAL files removed. The
+/// database file is left untouched in that case.
+pub fn replace_database(snapshot: &Path, db_path: &Path) -> Result<(), String> {
+    let staging = db_path.with_extension("db.restoring");
+    fs::copy(snapshot, &staging).map_err(|e| t_with("file.write_failed", &[("error", &e)]))?;
+
+    // WAL pages left by the old file would be replayed over the restored one
+    for suffix in ["-wal", "-shm"] {
+        let mut side_file = db_path.as_os_str().to_owned();
+        side_file.push(suffix);
+        match fs::remove_file(&side_file) {
+            Ok(()) => {}
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => return Err(t_with("file.write_failed", &[("error", &e)])),
+        }
+    }
+
+    fs::rename(&staging, db_path).map_err(|e| t_with("file.write_failed", &[("error", &e)]))
+}
@@ -0,0 +1,517 @@
+use std::collections::{HashMap, HashSet};
+
+use chrono::format::{Item, StrftimeItems};
+use chrono::NaiveDate;
+use sqlx::SqlitePool;
+
+use crate::commands::transactions::{
+    insert_transaction, recalculate_account_balance, NewTransaction,
+};
+use crate::db::models::{
+    CsvImportPreview, CsvImportProfile, CsvImportProfileInput, CsvImportResult, CsvImportRow,
+    CsvPreviewRow,
+};
+use crate::db::queries::csv_import;
+use crate::services::i18n::{t, t_with};
+
+/// Valid values for `csv_import_profiles.sign_convention`.
+pub const SIGN_CONVENTIONS: [&str; 3] = [
+    "negative_is_expense",
+    "positive_is_expense",
+    "debit_credit_columns",
+];
+
+/// Category used for imported expenses when the profile has no default.
+const FALLBACK_EXPENSE_CATEGORY_ID: &str = "cat_otros_expense";
+
+/// Category used for imported income when the profile has no default.
+const FALLBACK_INCOME_CATEGORY_ID: &str = "cat_otros_income";
+
+/// Check a profile's mapping before it is saved.
+pub fn validate_profile(input: &CsvImportProfileInput) -> Result<(), String> {
+    if input.name.trim().is_empty() {
+        return Err(t("csv.profile_name_required"));
+    }
+    if input.delimiter.chars().count() != 1 {
+        return Err(t("csv.delimiter"));
+    }
+    if input.skip_rows < 0 {
+        return Err(t("csv.skip_rows"));
+    }
+    if input.date_column < 0 || input.description_column < 0 {
+        return Err(t("csv.column_index"));
+    }
+    if input.decimal_separator != "," && input.decimal_separator != "." {
+        return Err(t("csv.decimal_separator"));
+    }
+    if !SIGN_CONVENTIONS.contains(&input.sign_convention.as_str()) {
+        return Err(t_with(
+            "csv.sign_convention",
+            &[
+                ("value", &input.sign_convention),
+                ("options", &SIGN_CONVENTIONS.join(", ")),
+            ],
+        ));
+    }
+
+    let columns_ok = if input.sign_convention == "debit_credit_columns" {
+        matches!((input.debit_column, input.credit_column), (Some(d), Some(c)) if d >= 0 && c >= 0)
+    } else {
+        input.amount_column.is_some_and(|c| c >= 0)
+    };
+    if !columns_ok {
+        return Err(if input.sign_convention == "debit_credit_columns" {
+            t("csv.debit_credit_columns")
+        } else {
+            t("csv.amount_column")
+        });
+    }
+
+    // The format must be valid strftime and able to round-trip a full date
+    if StrftimeItems::new(&input.date_format).any(|item| matches!(item, Item::Error)) {
+        return Err(t_with("csv.date_format", &[("format", &input.date_format)]));
+    }
+    let sample = NaiveDate::from_ymd_opt(2024, 12, 31).ok_or_else(|| t("csv.sample_date"))?;
+    let formatted = sample.format(&input.date_format).to_string();
+    if NaiveDate::parse_from_str(&formatted, &input.date_format).ok() != Some(sample) {
+        return Err(t_with(
+            "csv.date_format_parts",
+            &[("format", &input.date_format)],
+        ));
+    }
+
+    Ok(())
+}
+
+/// Decode a statement file. Bank exports are often Latin-1, so anything that is not
+/// valid UTF-8 is decoded byte-per-character. A UTF-8 BOM is stripped.
+pub fn decode_content(bytes: Vec<u8>) -> String {
+    let text = String::from_utf8(bytes)
+        .unwrap_or_else(|e| e.into_bytes().iter().map(|&b| char::from(b)).collect());
+    text.strip_prefix('\u{feff}')
+        .map(str::to_string)
+        .unwrap_or(text)
+}
+
+/// Split CSV content into records of fields, honoring double-quoted fields (which may
+/// contain the delimiter, escaped `""` quotes and line breaks).
+/// Returns (1-based line number where the record starts, fields).
+pub fn parse_records(content: &str, delimiter: char) -> Vec<(i64, Vec<String>)> {
+    let mut records = Vec::new();
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut line: i64 = 1;
+    let mut record_line: i64 = 1;
+    let mut chars = content.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if in_quotes {
+            match c {
+                '"' if chars.peek() == Some(&'"') => {
+                    field.push('"');
+                    chars.next();
+                }
+                '"' => in_quotes = false,
+                '\n' => {
+                    line += 1;
+                    field.push(c);
+                }
+                _ => field.push(c),
+            }
+            continue;
+        }
+
+        match c {
+            '"' if field.is_empty() => in_quotes = true,
+            '\r' => {}
+            '\n' => {
+                fields.push(std::mem::take(&mut field));
+                records.push((record_line, std::mem::take(&mut fields)));
+                line += 1;
+                record_line = line;
+            }
+            _ if c == delimiter => fields.push(std::mem::take(&mut field)),
+            _ => field.push(c),
+        }
+    }
+
+    if !field.is_empty() || !fields.is_empty() {
+        fields.push(field);
+        records.push((record_line, fields));
+    }
+
+    records
+}
+
+/// Parse a statement amount into signed minor units.
+/// Accepts currency symbols, spaces, thousands separators, a leading or trailing `-`,
+/// and accounting-style parentheses for negatives. Extra decimals are rounded half up.
+pub fn parse_amount(
+    raw: &str,
+    decimal_separator: char,
+    decimal_places: u32,
+) -> Result<i64, String> {
+    let trimmed = raw.trim();
+    let mut negative = trimmed.starts_with('(') && trimmed.ends_with(')');
+    let thousands = if decimal_separator == ',' { '.' } else { ',' };
+
+    let mut integer_digits = String::new();
+    let mut fraction_digits = String::new();
+    let mut seen_decimal = false;
+
+    for c in trimmed.chars() {
+        match c {
+            '0'..='9' if seen_decimal => fraction_digits.push(c),
+            '0'..='9' => integer_digits.push(c),
+            '-' => negative = true,
+            _ if c == decimal_separator && !seen_decimal => seen_decimal = true,
+            _ if c == thousands => {}
+            '$' | '(' | ')' | '+' | ' ' | '\u{a0}' => {}
+            _ if c.is_alphabetic() => {} // currency codes such as "CLP" or "US"
+            _ => return Err(t_with("csv.invalid_amount", &[("value", &raw)])),
+        }
+    }
+
+    if integer_digits.is_empty() && fraction_digits.is_empty() {
+        return Err(t_with("csv.invalid_amount", &[("value", &raw)]));
+    }
+
+    let places = decimal_places as usize;
+    let mut kept: String = fraction_digits.chars().take(places).collect();
+    while kept.len() < places {
+        kept.push('0');
+    }
+    let round_up = fraction_digits
+        .chars()
+        .nth(places)
+        .is_some_and(|d| d >= '5');
+
+    let digits = format!("{integer_digits}{kept}");
+    let mut value: i64 = if digits.is_empty() {
+        0
+    } else {
+        digits
+            .parse()
+            .map_err(|_| t_with("csv.amount_out_of_range", &[("value", &raw)]))?
+    };
+    if round_up {
+        value += 1;
+    }
+
+    Ok(if negative { -value } else { value })
+}
+
+/// Read a column from a record, trimmed. Missing columns read as empty.
+fn column(fields: &[String], index: Option<i32>) -> &str {
+    index
+        .and_then(|i| usize::try_from(i).ok())
+        .and_then(|i| fields.get(i))
+        .map_or("", |f| f.trim())
+}
+
+/// Resolve the absolute amount and transaction type of a record per the profile convention.
+fn parse_signed(
+    fields: &[String],
+    profile: &CsvImportProfile,
+    decimal_places: u32,
+) -> Result<(i64, &'static str), String> {
+    let separator = if profile.decimal_separator == "." {
+        '.'
+    } else {
+        ','
+    };
+
+    if profile.sign_convention == "debit_credit_columns" {
+        let debit = column(fields, profile.debit_column);
+        let credit = column(fields, profile.credit_column);
+        let debit = if debit.is_empty() {
+            0
+        } else {
+            parse_amount(debit, separator, decimal_places)?.abs()
+        };
+        let credit = if credit.is_empty() {
+            0
+        } else {
+            parse_amount(credit, separator, decimal_places)?.abs()
+        };
+        return match (debit, credit) {
+            (0, 0) => Err(t("csv.no_debit_credit")),
+            (d, 0) => Ok((d, "expense")),
+            (0, c) => Ok((c, "income")),
+            _ => Err(t("csv.both_debit_credit")),
+        };
+    }
+
+    let raw = column(fields, profile.amount_column);
+    if raw.is_empty() {
+        return Err(t("csv.no_amount"));
+    }
+    let value = parse_amount(raw, separator, decimal_places)?;
+    let negative_is_expense = profile.sign_convention == "negative_is_expense";
+    match value {
+        0 => Err(t("csv.zero_amount")),
+        v if (v < 0) == negative_is_expense => Ok((v.abs(), "expense")),
+        v => Ok((v.abs(), "income")),
+    }
+}
+
+/// Build a normalized key used to compare rows against existing transactions.
+fn duplicate_key(date: &str, amount: i64, transaction_type: &str, description: &str) -> String {
+    format!(
+        "{date}|{amount}|{transaction_type}|{}",
+        description.trim().to_lowercase()
+    )
+}
+
+/// Turn one CSV record into a preview row. Parse failures are reported on the row
+/// instead of aborting the whole preview.
+fn parse_row(
+    line_number: i64,
+    fields: &[String],
+    profile: &CsvImportProfile,
+    decimal_places: u32,
+) -> CsvPreviewRow {
+    let description = column(fields, Some(profile.description_column)).to_string();
+    let date = NaiveDate::parse_from_str(
+        column(fields, Some(profile.date_column)),
+        &profile.date_format,
+    )
+    .map(|d| d.format("%Y-%m-%d").to_string())
+    .map_err(|_| {
+        t_with(
+            "csv.invalid_row_date",
+            &[
+                ("value", &column(fields, Some(profile.date_column))),
+                ("format", &profile.date_format),
+            ],
+        )
+    });
+    let signed = parse_signed(fields, profile, decimal_places);
+
+    match (date, signed) {
+        (Ok(date), Ok((amount, transaction_type))) => {
+            let category_id = if transaction_type == "expense" {
+                profile
+                    .default_expense_category_id
+                    .clone()
+                    .unwrap_or_else(|| FALLBACK_EXPENSE_CATEGORY_ID.to_string())
+            } else {
+                profile
+                    .default_income_category_id
+                    .clone()
+                    .unwrap_or_else(|| FALLBACK_INCOME_CATEGORY_ID.to_string())
+            };
+            let error = description.is_empty().then(|| t("csv.no_description"));
+            CsvPreviewRow {
+                line_number,
+                date: Some(date),
+                amount: Some(amount),
+                transaction_type: Some(transaction_type.to_string()),
+                description,
+                category_id: Some(category_id),
+                is_duplicate: false,
+                error,
+            }
+        }
+        (Err(error), _) | (_, Err(error)) => CsvPreviewRow {
+            line_number,
+            date: None,
+            amount: None,
+            transaction_type: None,
+            description,
+            category_id: None,
+            is_duplicate: false,
+            error: Some(error),
+        },
+    }
+}
+
+/// Parse CSV content with a profile and flag likely duplicates against the account's
+/// existing transactions. Nothing is written.
+pub async fn preview(
+    pool: &SqlitePool,
+    account_id: &str,
+    profile: &CsvImportProfile,
+    content: &str,
+) -> Result<CsvImportPreview, String> {
+    let account: Option<(i32,)> = sqlx::query_as(
+        "SELECT c.decimal_places FROM accounts a
+         JOIN currencies c ON a.currency_id = c.id
+         WHERE a.id = ?",
+    )
+    .bind(account_id)
+    .fetch_optional(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+    let decimal_places = account
+        .and_then(|(places,)| u32::try_from(places).ok())
+        .ok_or_else(|| t_with("account.not_found", &[("id", &account_id)]))?;
+
+    let delimiter = profile.delimiter.chars().next().unwrap_or(',');
+    let skip = usize::try_from(profile.skip_rows).unwrap_or(0);
+
+    let mut rows: Vec<CsvPreviewRow> = Vec::new();
+    for (line_number, fields) in parse_records(content, delimiter).into_iter().skip(skip) {
+        // Blank lines and trailing separators are common at the end of bank exports
+        if fields.iter().all(|f| f.trim().is_empty()) {
+            continue;
+        }
+
+        rows.push(parse_row(line_number, &fields, profile, decimal_places));
+    }
+
+    // Compare against existing transactions in the date range covered by the file
+    let dates: Vec<&String> = rows.iter().filter_map(|r| r.date.as_ref()).collect();
+    if let (Some(min), Some(max)) = (dates.iter().min(), dates.iter().max()) {
+        let existing: HashSet<String> = csv_import::get_existing_keys(pool, account_id, min, max)
+            .await
+            .map_err(|e| e.to_string())?
+            .iter()
+            .map(|(date, amount, transaction_type, description)| {
+                duplicate_key(date, *amount, transaction_type, description)
+            })
+            .collect();
+
+        for row in &mut rows {
+            if let (Some(date), Some(amount), Some(transaction_type)) =
+                (&row.date, row.amount, &row.transaction_type)
+            {
+                row.is_duplicate = existing.contains(&duplicate_key(
+                    date,
+                    amount,
+                    transaction_type,
+                    &row.description,
+                ));
+            }
+        }
+    }
+
+    #[allow(clippy::cast_possible_wrap)]
+    let count = |pred: fn(&CsvPreviewRow) -> bool| rows.iter().filter(|r| pred(r)).count() as i64;
+
+    Ok(CsvImportPreview {
+        account_id: account_id.to_string(),
+        profile_id: profile.id.clone(),
+        valid_count: count(|r| r.error.is_none()),
+        duplicate_count: count(|r| r.is_duplicate),
+        error_count: count(|r| r.error.is_some()),
+        rows,
+    })
+}
+
+/// Import reviewed rows into an account. All rows are inserted in one SQL transaction
+/// and the balance is recalculated once at the end; any invalid row aborts the import.
+pub async fn commit(
+    pool: &SqlitePool,
+    account_id: &str,
+    rows: &[CsvImportRow],
+) -> Result<CsvImportResult, String> {
+    if rows.is_empty() {
+        return Err(t("import.no_rows"));
+    }
+
+    let account: Option<(i32,)> = sqlx::query_as("SELECT is_active FROM accounts WHERE id = ?")
+        .bind(account_id)
+        .fetch_optional(pool)
+        .await
+        .map_err(|e| e.to_string())?;
+    match account {
+        None => return Err(t_with("account.not_found", &[("id", &account_id)])),
+        Some((is_active,)) if is_active != 1 => {
+            return Err(t("import.archived_account"));
+        }
+        _ => {}
+    }
+
+    // Validate every row up front so nothing is written when one is wrong
+    let mut categories: HashMap<String, Option<(String, i32)>> = HashMap::new();
+    for (index, row) in rows.iter().enumerate() {
+        let n = index + 1;
+        let row_error =
+            |error: String| t_with("import.row_error", &[("row", &n), ("error", &error)]);
+        if row.amount <= 0 {
+            return Err(row_error(t("validation.amount_positive")));
+        }
+        if row.transaction_type != "income" && row.transaction_type != "expense" {
+            return Err(row_error(t("validation.transaction_type")));
+        }
+        if NaiveDate::parse_from_str(&row.date, "%Y-%m-%d").is_err() {
+            return Err(row_error(t_with(
+                "validation.invalid_date",
+                &[("error", &row.date)],
+            )));
+        }
+        if row.description.trim().is_empty() {
+            return Err(row_error(t("validation.description_required")));
+        }
+
+        if !categories.contains_key(&row.category_id) {
+            let category: Option<(String, i32)> =
+                sqlx::query_as("SELECT type, is_active FROM categories WHERE id = ?")
+                    .bind(&row.category_id)
+                    .fetch_optional(pool)
+                    .await
+                    .map_err(|e| e.to_string())?;
+            categories.insert(row.category_id.clone(), category);
+        }
+        match categories.get(&row.category_id).and_then(Option::as_ref) {
+            None => {
+                return Err(row_error(t_with(
+                    "category.not_found",
+                    &[("id", &row.category_id)],
+                )))
+            }
+            Some((_, is_active)) if *is_active != 1 => {
+                return Err(row_error(t("category.inactive")));
+            }
+            Some((cat_type, _)) if *cat_type != row.transaction_type => {
+                return Err(row_error(t_with(
+                    "category.type_mismatch",
+                    &[
+                        ("transaction_type", &row.transaction_type),
+                        ("category_type", cat_type),
+                    ],
+                )));
+            }
+            _ => {}
+        }
+    }
+
+    let mut db_txn = pool.begin().await.map_err(|e| e.to_string())?;
+
+    for row in rows {
+        insert_transaction(
+            &mut db_txn,
+            &NewTransaction {
+                account_id,
+                category_id: &row.category_id,
+                amount: row.amount,
+                transaction_type: &row.transaction_type,
+                description: row.description.trim(),
+                date: &row.date,
+                fitid: None,
+            },
+        )
+        .await?;
+    }
+
+    recalculate_account_balance(&mut db_txn, account_id).await?;
+
+    let balance: (i64,) = sqlx::query_as("SELECT balance FROM accounts WHERE id = ?")
+        .bind(account_id)
+        .fetch_one(&mut *db_txn)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    db_txn.commit().await.map_err(|e| e.to_string())?;
+
+    #[allow(clippy::cast_possible_wrap)]
+    let inserted_count = rows.len() as i64;
+
+    Ok(CsvImportResult {
+        account_id: account_id.to_string(),
+        inserted_count,
+        balance: balance.0,
+    })
+}
@@ -0,0 +1,32 @@
+use chrono::{Datelike, NaiveDate};
+
+/// Get the last day of a given month/year.
+pub fn last_day_of_month(year: i32, month: u32) -> u32 {
+    // Get the first day of the next month and subtract one day
+    if month == 12 {
+        NaiveDate::from_ymd_opt(year + 1, 1, 1)
+    } else {
+        NaiveDate::from_ymd_opt(year, month + 1, 1)
+    }
+    .map(|d| d.pred_opt().map(|p| p.day()).unwrap_or(28))
+    .unwrap_or(28)
+}
+
+/// Add `months` to a date, placing the result on `day` clamped to the end of the target month.
+/// e.g. 2025-01-31 + 1 month (day 31) -> 2025-02-28, and + 2 months -> 2025-03-31.
+pub fn add_months_clamped(date: NaiveDate, months: u32, day: u32) -> Option<NaiveDate> {
+    let months_offset = date.month0() + months;
+    let target_year = date.year() + i32::try_from(months_offset / 12).ok()?;
+    let target_month = (months_offset % 12) + 1;
+
+    let clamped_day = day.min(last_day_of_month(target_year, target_month));
+    NaiveDate::from_ymd_opt(target_year, target_month, clamped_day)
+}
+
+/// First day of the month containing `date` and first day of the following month,
+/// i.e. the half-open range `[start, end)` covering that month.
+pub fn month_bounds(date: NaiveDate) -> (NaiveDate, NaiveDate) {
+    let start = date.with_day(1).unwrap_or(date);
+    let end = add_months_clamped(start, 1, 1).unwrap_or(start);
+    (start, end)
+}
@@ -0,0 +1,354 @@
+use sqlx::sqlite::Sqlite;
+use sqlx::SqlitePool;
+
+use crate::commands::transactions::recalculate_account_balance;
+use crate::db::models::{ExportData, ExportDocument, ImportSummary, TableCounts};
+use crate::db::queries::export;
+use crate::services::i18n::{t, t_with};
+
+/// Current layout version of the export document. Bump when the JSON shape changes.
+pub const EXPORT_FORMAT_VERSION: u32 = 1;
+
+/// Valid values for the import `mode` argument.
+pub const IMPORT_MODES: [&str; 2] = ["merge", "replace"];
+
+#[allow(clippy::cast_possible_wrap)]
+fn len_i64(len: usize) -> i64 {
+    len as i64
+}
+
+/// Count the rows of each table contained in an export.
+pub fn count_rows(data: &ExportData) -> TableCounts {
+    TableCounts {
+        currencies: len_i64(data.currencies.len()),
+        accounts: len_i64(data.accounts.len()),
+        categories: len_i64(data.categories.len()),
+        transactions: len_i64(data.transactions.len()),
+        tags: len_i64(data.tags.len()),
+        transaction_tags: len_i64(data.transaction_tags.len()),
+        recurring_transactions: len_i64(data.recurring_transactions.len()),
+        transfers: len_i64(data.transfers.len()),
+        statement_payments: len_i64(data.statement_payments.len()),
+        csv_import_profiles: len_i64(data.csv_import_profiles.len()),
+        budgets: len_i64(data.budgets.len()),
+        debts: len_i64(data.debts.len()),
+        installments: len_i64(data.installments.len()),
+        installment_payments: len_i64(data.installment_payments.len()),
+        debt_status_history: len_i64(data.debt_status_history.len()),
+        debt_prepayments: len_i64(data.debt_prepayments.len()),
+        exchange_rates: len_i64(data.exchange_rates.len()),
+        settings: len_i64(data.settings.len()),
+    }
+}
+
+/// Build a full export document from the current database contents.
+pub async fn build_document(pool: &SqlitePool) -> Result<ExportDocument, String> {
+    let schema_version = export::get_schema_version(pool)
+        .await
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| t("export.no_migrations"))?;
+
+    let data = export::fetch_all(pool).await.map_err(|e| e.to_string())?;
+
+    Ok(ExportDocument {
+        format_version: EXPORT_FORMAT_VERSION,
+        app_version: env!("CARGO_PKG_VERSION").to_string(),
+        schema_version,
+        exported_at: chrono::Utc::now().to_rfc3339(),
+        counts: count_rows(&data),
+        data,
+    })
+}
+
+/// Check that a document can be loaded into this database before touching any data.
+async fn validate_document(pool: &SqlitePool, doc: &ExportDocument) -> Result<(), String> {
+    if doc.format_version != EXPORT_FORMAT_VERSION {
+        return Err(t_with(
+            "export.unsupported_version",
+            &[
+                ("version", &doc.format_version),
+                ("expected", &EXPORT_FORMAT_VERSION),
+            ],
+        ));
+    }
+
+    // A document exported by a newer schema may contain columns this build doesn't know
+    let known = export::is_migration_applied(pool, &doc.schema_version)
+        .await
+        .map_err(|e| e.to_string())?;
+    if !known {
+        return Err(t_with(
+            "export.unknown_schema",
+            &[("version", &doc.schema_version)],
+        ));
+    }
+
+    if count_rows(&doc.data) != doc.counts {
+        return Err(t("export.incomplete"));
+    }
+
+    Ok(())
+}
+
+/// Insert every row of an export, parents before children, skipping IDs that already exist.
+/// Returns how many rows of each table were actually written.
+async fn insert_rows(
+    db_txn: &mut sqlx::Transaction<'_, Sqlite>,
+    data: &ExportData,
+) -> Result<TableCounts, String> {
+    let mut inserted = TableCounts::default();
+
+    for row in &data.currencies {
+        if export::insert_currency(db_txn, row)
+            .await
+            .map_err(|e| e.to_string())?
+        {
+            inserted.currencies += 1;
+        }
+    }
+    for row in &data.accounts {
+        if export::insert_account(db_txn, row)
+            .await
+            .map_err(|e| e.to_string())?
+        {
+            inserted.accounts += 1;
+        }
+    }
+    for row in &data.categories {
+        if export::insert_category(db_txn, row)
+            .await
+            .map_err(|e| e.to_string())?
+        {
+            inserted.categories += 1;
+        }
+    }
+    for row in &data.tags {
+        if export::insert_tag(db_txn, row)
+            .await
+            .map_err(|e| e.to_string())?
+        {
+            inserted.tags += 1;
+        }
+    }
+    for row in &data.recurring_transactions {
+        if export::insert_recurring_transaction(db_txn, row)
+            .await
+            .map_err(|e| e.to_string())?
+        {
+            inserted.recurring_transactions += 1;
+        }
+    }
+    for row in &data.transfers {
+        if export::insert_transfer(db_txn, row)
+            .await
+            .map_err(|e| e.to_string())?
+        {
+            inserted.transfers += 1;
+        }
+    }
+    for row in &data.transactions {
+        if export::insert_transaction(db_txn, row)
+            .await
+            .map_err(|e| e.to_string())?
+        {
+            inserted.transactions += 1;
+        }
+    }
+    for row in &data.statement_payments {
+        if export::insert_statement_payment(db_txn, row)
+            .await
+            .map_err(|e| e.to_string())?
+        {
+            inserted.statement_payments += 1;
+        }
+    }
+    for row in &data.transaction_tags {
+        if export::insert_transaction_tag(db_txn, row)
+            .await
+            .map_err(|e| e.to_string())?
+        {
+            inserted.transaction_tags += 1;
+        }
+    }
+    insert_debt_rows(db_txn, data, &mut inserted).await?;
+    insert_config_rows(db_txn, data, &mut inserted).await?;
+
+    Ok(inserted)
+}
+
+/// Insert debts with their installments and payments, status history and prepayments, adding
+/// to the `inserted` counts. They are written after the transactions that installments and
+/// prepayments link to.
+async fn insert_debt_rows(
+    db_txn: &mut sqlx::Transaction<'_, Sqlite>,
+    data: &ExportData,
+    inserted: &mut TableCounts,
+) -> Result<(), String> {
+    for row in &data.debts {
+        if export::insert_debt(db_txn, row)
+            .await
+            .map_err(|e| e.to_string())?
+        {
+            inserted.debts += 1;
+        }
+    }
+    for row in &data.installments {
+        if export::insert_installment(db_txn, row)
+            .await
+            .map_err(|e| e.to_string())?
+        {
+            inserted.installments += 1;
+        }
+    }
+    for row in &data.installment_payments {
+        if export::insert_installment_payment(db_txn, row)
+            .await
+            .map_err(|e| e.to_string())?
+        {
+            inserted.installment_payments += 1;
+        }
+    }
+    export::link_unrecorded_installment_payments(db_txn)
+        .await
+        .map_err(|e| e.to_string())?;
+    for row in &data.debt_status_history {
+        if export::insert_debt_status_change(db_txn, row)
+            .await
+            .map_err(|e| e.to_string())?
+        {
+            inserted.debt_status_history += 1;
+        }
+    }
+    for row in &data.debt_prepayments {
+        if export::insert_debt_prepayment(db_txn, row)
+            .await
+            .map_err(|e| e.to_string())?
+        {
+            inserted.debt_prepayments += 1;
+        }
+    }
+    Ok(())
+}
+
+/// Insert the rows of tables that only configure the app (rates, import profiles, budgets,
+/// settings), adding to the `inserted` counts. They are written after every table they
+/// reference.
+async fn insert_config_rows(
+    db_txn: &mut sqlx::Transaction<'_, Sqlite>,
+    data: &ExportData,
+    inserted: &mut TableCounts,
+) -> Result<(), String> {
+    for row in &data.exchange_rates {
+        if export::insert_exchange_rate(db_txn, row)
+            .await
+            .map_err(|e| e.to_string())?
+        {
+            inserted.exchange_rates += 1;
+        }
+    }
+    for row in &data.csv_import_profiles {
+        if export::insert_csv_import_profile(db_txn, row)
+            .await
+            .map_err(|e| e.to_string())?
+        {
+            inserted.csv_import_profiles += 1;
+        }
+    }
+    for row in &data.budgets {
+        if export::insert_budget(db_txn, row)
+            .await
+            .map_err(|e| e.to_string())?
+        {
+            inserted.budgets += 1;
+        }
+    }
+    for row in &data.settings {
+        if export::insert_setting(db_txn, row)
+            .await
+            .map_err(|e| e.to_string())?
+        {
+            inserted.settings += 1;
+        }
+    }
+
+    Ok(())
+}
+
+/// Load an export document into the database.
+///
+/// `replace` wipes every exported table first, `merge` only inserts rows whose ID is not
+/// already present. Either way the whole import runs in one SQL transaction, foreign keys
+/// are checked at commit, and every account balance is recalculated before committing.
+pub async fn import_document(
+    pool: &SqlitePool,
+    doc: &ExportDocument,
+    mode: &str,
+) -> Result<ImportSummary, String> {
+    if !IMPORT_MODES.contains(&mode) {
+        return Err(t_with(
+            "export.invalid_mode",
+            &[("mode", &mode), ("options", &IMPORT_MODES.join(", "))],
+        ));
+    }
+
+    validate_document(pool, doc).await?;
+
+    let mut db_txn = pool.begin().await.map_err(|e| e.to_string())?;
+
+    // Rows may reference each other in any order (e.g. nested categories), so
+    // foreign keys are only enforced when the transaction commits
+    sqlx::query("PRAGMA defer_foreign_keys = ON")
+        .execute(&mut *db_txn)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    if mode == "replace" {
+        export::wipe_all(&mut db_txn)
+            .await
+            .map_err(|e| e.to_string())?;
+    }
+
+    let inserted = insert_rows(&mut db_txn, &doc.data).await?;
+
+    // Stored balances are derived data: rebuild them from the resulting transactions
+    let account_ids: Vec<(String,)> = sqlx::query_as("SELECT id FROM accounts")
+        .fetch_all(&mut *db_txn)
+        .await
+        .map_err(|e| e.to_string())?;
+    for (account_id,) in &account_ids {
+        recalculate_account_balance(&mut db_txn, account_id).await?;
+    }
+
+    db_txn
+        .commit()
+        .await
+        .map_err(|e| t_with("export.inconsistent", &[("error", &e)]))?;
+
+    let skipped = TableCounts {
+        currencies: doc.counts.currencies - inserted.currencies,
+        accounts: doc.counts.accounts - inserted.accounts,
+        categories: doc.counts.categories - inserted.categories,
+        transactions: doc.counts.transactions - inserted.transactions,
+        tags: doc.counts.tags - inserted.tags,
+        transaction_tags: doc.counts.transaction_tags - inserted.transaction_tags,
+        recurring_transactions: doc.counts.recurring_transactions - inserted.recurring_transactions,
+        transfers: doc.counts.transfers - inserted.transfers,
+        statement_payments: doc.counts.statement_payments - inserted.statement_payments,
+        csv_import_profiles: doc.counts.csv_import_profiles - inserted.csv_import_profiles,
+        budgets: doc.counts.budgets - inserted.budgets,
+        debts: doc.counts.debts - inserted.debts,
+        installments: doc.counts.installments - inserted.installments,
+        installment_payments: doc.counts.installment_payments - inserted.installment_payments,
+        debt_status_history: doc.counts.debt_status_history - inserted.debt_status_history,
+        debt_prepayments: doc.counts.debt_prepayments - inserted.debt_prepayments,
+        exchange_rates: doc.counts.exchange_rates - inserted.exchange_rates,
+        settings: doc.counts.settings - inserted.settings,
+    };
+
+    Ok(ImportSummary {
+        mode: mode.to_string(),
+        schema_version: doc.schema_version.clone(),
+        inserted,
+        skipped,
+    })
+}
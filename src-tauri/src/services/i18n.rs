@@ -0,0 +1,99 @@
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::OnceLock;
+
+/// Message catalogs, keyed by message ID. Placeholders are written as `{name}`.
+const ES_CATALOG: &str = include_str!("../../locales/es.json");
+const EN_CATALOG: &str = include_str!("../../locales/en.json");
+
+/// Languages the backend can produce messages in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Locale {
+    Es,
+    En,
+}
+
+/// Supported locale codes, as stored in settings.
+pub const LOCALES: [&str; 2] = ["es", "en"];
+
+impl Locale {
+    pub fn code(self) -> &'static str {
+        match self {
+            Self::Es => "es",
+            Self::En => "en",
+        }
+    }
+
+    pub fn from_code(code: &str) -> Option<Self> {
+        match code {
+            "es" => Some(Self::Es),
+            "en" => Some(Self::En),
+            _ => None,
+        }
+    }
+}
+
+/// Locale used for every message, set at startup from the saved setting.
+static CURRENT: AtomicU8 = AtomicU8::new(0);
+
+/// Get the active locale.
+pub fn current() -> Locale {
+    match CURRENT.load(Ordering::Relaxed) {
+        1 => Locale::En,
+        _ => Locale::Es,
+    }
+}
+
+/// Switch the active locale for all subsequent messages.
+pub fn set_current(locale: Locale) {
+    let value = match locale {
+        Locale::Es => 0,
+        Locale::En => 1,
+    };
+    CURRENT.store(value, Ordering::Relaxed);
+}
+
+/// Parse a catalog once. An unparsable catalog yields an empty map, so lookups fall back
+/// to the Spanish text or the key itself instead of failing.
+fn load(source: &str) -> HashMap<String, String> {
+    serde_json::from_str(source).unwrap_or_default()
+}
+
+/// The full catalog of a locale.
+pub fn catalog(locale: Locale) -> &'static HashMap<String, String> {
+    static ES: OnceLock<HashMap<String, String>> = OnceLock::new();
+    static EN: OnceLock<HashMap<String, String>> = OnceLock::new();
+
+    match locale {
+        Locale::Es => ES.get_or_init(|| load(ES_CATALOG)),
+        Locale::En => EN.get_or_init(|| load(EN_CATALOG)),
+    }
+}
+
+/// Look up a message in the active locale, falling back to Spanish and then to the key.
+pub fn t(key: &str) -> String {
+    catalog(current())
+        .get(key)
+        .or_else(|| catalog(Locale::Es).get(key))
+        .cloned()
+        .unwrap_or_else(|| key.to_string())
+}
+
+/// Look up a message and fill its `{name}` placeholders.
+pub fn t_with(key: &str, args: &[(&str, &dyn Display)]) -> String {
+    let mut message = t(key);
+    for (name, value) in args {
+        message = message.replace(&format!("{{{name}}}"), &value.to_string());
+    }
+    message
+}
+
+/// Name of a month (1-12) in the active locale.
+pub fn month_name(month: u32) -> String {
+    if (1..=12).contains(&month) {
+        t(&format!("month.{month}"))
+    } else {
+        t("month.unknown")
+    }
+}
@@ -1 +1,12 @@
 // Services module - Business logic
+pub mod amortization;
+pub mod backup;
+pub mod csv_import;
+pub mod dates;
+pub mod export;
+pub mod i18n;
+pub mod ofx;
+pub mod periods;
+pub mod recurring;
+pub mod settings;
+pub mod statements;
@@ -0,0 +1,171 @@
+use crate::services::i18n::{t, t_with};
+
+/// A single `<STMTTRN>` entry of an OFX statement.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OfxTransaction {
+    /// Bank-assigned unique ID, used to skip lines that were already imported.
+    pub fitid: String,
+    /// Posting date as YYYY-MM-DD.
+    pub date: String,
+    /// Signed amount exactly as written in the file (negative = money out).
+    pub amount: String,
+    /// `TRNTYPE`, e.g. `DEBIT`, `CREDIT`, `POS`, `FEE`.
+    pub trn_type: String,
+    pub name: Option<String>,
+    pub memo: Option<String>,
+}
+
+impl OfxTransaction {
+    /// Best human-readable description: NAME, then MEMO, then the transaction type.
+    pub fn description(&self) -> String {
+        self.name
+            .as_deref()
+            .or(self.memo.as_deref())
+            .filter(|d| !d.is_empty())
+            .unwrap_or(&self.trn_type)
+            .to_string()
+    }
+
+    /// Decimal separator used by the amount. OFX mandates `.`, but some banks emit `,`.
+    pub fn decimal_separator(&self) -> char {
+        if self.amount.contains(',') && !self.amount.contains('.') {
+            ','
+        } else {
+            '.'
+        }
+    }
+}
+
+/// The statement data the importer needs from an OFX file.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct OfxStatement {
+    /// `CURDEF`, the ISO currency code of the statement.
+    pub currency: Option<String>,
+    /// `ACCTID` of the bank account or card.
+    pub account_number: Option<String>,
+    pub transactions: Vec<OfxTransaction>,
+}
+
+/// Decode the XML/SGML character entities that appear in OFX values.
+fn decode_entities(value: &str) -> String {
+    value
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&nbsp;", " ")
+        .replace("&amp;", "&")
+}
+
+/// Convert an OFX datetime (`YYYYMMDD[HHMMSS[.XXX]][[offset:TZ]]`) to YYYY-MM-DD.
+fn parse_ofx_date(value: &str) -> Result<String, String> {
+    let digits = value.get(..8).unwrap_or("");
+    chrono::NaiveDate::parse_from_str(digits, "%Y%m%d")
+        .map(|d| d.format("%Y-%m-%d").to_string())
+        .map_err(|_| t_with("ofx.invalid_date", &[("value", &value)]))
+}
+
+/// Split the body of an OFX document into (tag, text) tokens. Works for both OFX 1.x SGML,
+/// where leaf elements are usually left unclosed, and OFX 2.x XML: the value of a leaf is
+/// simply the text between its opening tag and the next tag.
+fn tokenize(body: &str) -> Vec<(String, String)> {
+    let mut tokens = Vec::new();
+    let mut rest = body;
+
+    while let Some(open) = rest.find('<') {
+        let Some(close) = rest[open..].find('>') else {
+            break;
+        };
+        let tag = rest[open + 1..open + close].trim().to_ascii_uppercase();
+        rest = &rest[open + close + 1..];
+        let text_end = rest.find('<').unwrap_or(rest.len());
+        let text = decode_entities(rest[..text_end].trim());
+        // Processing instructions and comments (<?xml ...?>, <!-- -->) carry no data
+        if !tag.starts_with('?') && !tag.starts_with('!') {
+            tokens.push((tag, text));
+        }
+    }
+
+    tokens
+}
+
+/// Parse an OFX 1.x (SGML) or 2.x (XML) statement. Only the fields needed to import
+/// transactions are read; everything else in the document is ignored.
+///
+/// # Errors
+///
+/// Returns an error when the content has no `<OFX>` element or a transaction lacks its
+/// FITID, a valid posting date or an amount.
+pub fn parse_ofx(content: &str) -> Result<OfxStatement, String> {
+    // The 1.x header is plain `KEY:VALUE` lines before <OFX>, the 2.x header is XML
+    // processing instructions. Either way the data starts at the <OFX> element.
+    let start = content
+        .to_ascii_uppercase()
+        .find("<OFX>")
+        .ok_or_else(|| t("ofx.invalid_document"))?;
+
+    let mut statement = OfxStatement::default();
+    let mut current: Option<Vec<(String, String)>> = None;
+
+    for (tag, text) in tokenize(&content[start..]) {
+        match tag.as_str() {
+            // In SGML the closing tag is optional: a new <STMTTRN> or the end of the list
+            // implicitly closes the previous one
+            "STMTTRN" => {
+                if let Some(fields) = current.replace(Vec::new()) {
+                    statement.transactions.push(build_transaction(&fields)?);
+                }
+            }
+            "/STMTTRN" | "/BANKTRANLIST" => {
+                if let Some(fields) = current.take() {
+                    statement.transactions.push(build_transaction(&fields)?);
+                }
+            }
+            "CURDEF" if statement.currency.is_none() => {
+                statement.currency = Some(text.to_ascii_uppercase());
+            }
+            "ACCTID" if statement.account_number.is_none() => {
+                statement.account_number = Some(text);
+            }
+            _ => {
+                if let Some(fields) = current.as_mut() {
+                    if !tag.starts_with('/') {
+                        fields.push((tag, text));
+                    }
+                }
+            }
+        }
+    }
+
+    if let Some(fields) = current.take() {
+        statement.transactions.push(build_transaction(&fields)?);
+    }
+
+    Ok(statement)
+}
+
+/// Assemble one transaction from the leaf elements collected inside `<STMTTRN>`.
+fn build_transaction(fields: &[(String, String)]) -> Result<OfxTransaction, String> {
+    let get = |name: &str| {
+        fields
+            .iter()
+            .find(|(tag, _)| tag == name)
+            .map(|(_, text)| text.clone())
+            .filter(|text| !text.is_empty())
+    };
+
+    let fitid = get("FITID").ok_or_else(|| t("ofx.missing_fitid"))?;
+    let date = parse_ofx_date(
+        &get("DTPOSTED").ok_or_else(|| t_with("ofx.missing_date", &[("fitid", &fitid)]))?,
+    )?;
+    let amount = get("TRNAMT").ok_or_else(|| t_with("ofx.missing_amount", &[("fitid", &fitid)]))?;
+
+    Ok(OfxTransaction {
+        fitid,
+        date,
+        amount,
+        trn_type: get("TRNTYPE").unwrap_or_else(|| "OTHER".to_string()),
+        name: get("NAME"),
+        memo: get("MEMO"),
+    })
+}
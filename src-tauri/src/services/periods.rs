@@ -0,0 +1,233 @@
+use chrono::{Datelike, Days, Months, NaiveDate, Weekday};
+
+use crate::db::models::{DashboardPeriod, PeriodInfo};
+use crate::services::dates::month_bounds;
+use crate::services::i18n::{month_name, t, t_with};
+
+/// Supported dashboard period types.
+pub const PERIOD_TYPES: [&str; 5] = [
+    "this_month",
+    "last_month",
+    "last_n_months",
+    "year_to_date",
+    "custom",
+];
+
+/// Longest window accepted for `last_n_months`.
+const MAX_MONTHS: u32 = 120;
+
+/// Supported balance history granularities.
+pub const GRANULARITIES: [&str; 3] = ["daily", "weekly", "monthly"];
+
+/// Most points a balance history may return.
+const MAX_POINTS: usize = 1000;
+
+/// Half-open date range `[start, end)`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DateRange {
+    pub start: NaiveDate,
+    pub end: NaiveDate,
+}
+
+impl DateRange {
+    /// First day of the range as YYYY-MM-DD, for inclusive SQL bounds.
+    pub fn start_str(self) -> String {
+        self.start.format("%Y-%m-%d").to_string()
+    }
+
+    /// Day after the last day of the range as YYYY-MM-DD, for exclusive SQL bounds.
+    pub fn end_str(self) -> String {
+        self.end.format("%Y-%m-%d").to_string()
+    }
+
+    /// Last day included in the range.
+    pub fn last_day(self) -> NaiveDate {
+        self.end.pred_opt().unwrap_or(self.end)
+    }
+}
+
+/// Move a date back by whole months, clamping the day to the end of the target month.
+fn months_back(date: NaiveDate, months: u32) -> Result<NaiveDate, String> {
+    date.checked_sub_months(Months::new(months))
+        .ok_or_else(|| t("period.out_of_range"))
+}
+
+/// Parse a YYYY-MM-DD date, with a field-specific error message. `field` is the catalog key
+/// of the field's name.
+pub fn parse_date(value: Option<&str>, field: &str) -> Result<NaiveDate, String> {
+    let value =
+        value.ok_or_else(|| t_with("validation.field_required", &[("field", &t(field))]))?;
+    NaiveDate::parse_from_str(value, "%Y-%m-%d").map_err(|e| {
+        t_with(
+            "validation.field_invalid",
+            &[("field", &t(field)), ("error", &e)],
+        )
+    })
+}
+
+/// Resolve a period selection into a date range, relative to `today`.
+/// Month-based periods always cover whole calendar months; year-to-date ends today.
+pub fn resolve(period: &DashboardPeriod, today: NaiveDate) -> Result<DateRange, String> {
+    let (this_month, next_month) = month_bounds(today);
+
+    let (start, end) = match period.period_type.as_str() {
+        "this_month" => (this_month, next_month),
+        "last_month" => (months_back(this_month, 1)?, this_month),
+        "last_n_months" => {
+            let months = period.months.ok_or_else(|| t("period.months_required"))?;
+            if months == 0 || months > MAX_MONTHS {
+                return Err(t_with("period.months_range", &[("max", &MAX_MONTHS)]));
+            }
+            (months_back(this_month, months - 1)?, next_month)
+        }
+        "year_to_date" => {
+            let start = NaiveDate::from_ymd_opt(today.year(), 1, 1).unwrap_or(this_month);
+            (start, today.succ_opt().unwrap_or(today))
+        }
+        "custom" => {
+            let from = parse_date(period.date_from.as_deref(), "field.start_date")?;
+            let to = parse_date(period.date_to.as_deref(), "field.end_date")?;
+            if to < from {
+                return Err(t("validation.end_before_start"));
+            }
+            (from, to.succ_opt().unwrap_or(to))
+        }
+        other => {
+            return Err(t_with(
+                "period.invalid",
+                &[("value", &other), ("options", &PERIOD_TYPES.join(", "))],
+            ))
+        }
+    };
+
+    Ok(DateRange { start, end })
+}
+
+/// The previous equivalent period: the same number of calendar months right before a
+/// month-based period, the same span one year earlier for year-to-date, and the same
+/// number of days right before a custom range.
+pub fn previous(period_type: &str, range: DateRange) -> Result<DateRange, String> {
+    match period_type {
+        "year_to_date" => Ok(DateRange {
+            start: months_back(range.start, 12)?,
+            end: months_back(range.end, 12)?,
+        }),
+        "custom" => {
+            let days = range.end - range.start;
+            Ok(DateRange {
+                start: range.start - days,
+                end: range.start,
+            })
+        }
+        _ => {
+            let months = month_span(range);
+            Ok(DateRange {
+                start: months_back(range.start, months)?,
+                end: range.start,
+            })
+        }
+    }
+}
+
+/// Months elapsed since year 0, used to count calendar months between dates.
+fn month_index(date: NaiveDate) -> i32 {
+    #[allow(clippy::cast_possible_wrap)]
+    let month0 = date.month0() as i32;
+    date.year() * 12 + month0
+}
+
+/// Number of calendar months covered by a month-aligned range.
+fn month_span(range: DateRange) -> u32 {
+    u32::try_from(month_index(range.end) - month_index(range.start))
+        .unwrap_or(1)
+        .max(1)
+}
+
+/// Describe a resolved range for display in the active locale: "Marzo 2025" for a single month,
+/// "Enero 2025 - Marzo 2025" for whole months, and explicit dates otherwise.
+pub fn info(period_type: &str, range: DateRange) -> PeriodInfo {
+    let last = range.last_day();
+    let whole_months = range.start.day() == 1 && range.end.day() == 1;
+
+    let label = if whole_months && month_span(range) == 1 {
+        format!("{} {}", month_name(range.start.month()), range.start.year())
+    } else if whole_months {
+        format!(
+            "{} {} - {} {}",
+            month_name(range.start.month()),
+            range.start.year(),
+            month_name(last.month()),
+            last.year()
+        )
+    } else {
+        format!(
+            "{} - {}",
+            range.start.format("%d/%m/%Y"),
+            last.format("%d/%m/%Y")
+        )
+    };
+
+    PeriodInfo {
+        period_type: period_type.to_string(),
+        date_from: range.start_str(),
+        date_to: last.format("%Y-%m-%d").to_string(),
+        label,
+    }
+}
+
+/// Percentage change from `previous` to `current`, or `None` when there is no base.
+pub fn change_percentage(current: i64, previous: i64) -> Option<f64> {
+    if previous == 0 {
+        return None;
+    }
+    #[allow(clippy::cast_precision_loss)]
+    let change = ((current - previous) as f64 / previous.abs() as f64) * 100.0;
+    Some(change)
+}
+
+/// Sample dates for a balance history over the inclusive range `[from, to]`: every day, the
+/// last day of every week (the day before `week_start`) or of every month, with the final
+/// one clamped to `to`.
+pub fn sample_dates(
+    granularity: &str,
+    from: NaiveDate,
+    to: NaiveDate,
+    week_start: Weekday,
+) -> Result<Vec<NaiveDate>, String> {
+    if to < from {
+        return Err(t("validation.end_before_start"));
+    }
+
+    let mut dates = Vec::new();
+    let mut cursor = from;
+
+    loop {
+        let bucket_end = match granularity {
+            "daily" => cursor,
+            "weekly" => {
+                let into_week = (7 + cursor.weekday().num_days_from_monday()
+                    - week_start.num_days_from_monday())
+                    % 7;
+                let offset = 6 - u64::from(into_week);
+                cursor.checked_add_days(Days::new(offset)).unwrap_or(to)
+            }
+            "monthly" => month_bounds(cursor).1.pred_opt().unwrap_or(to),
+            other => {
+                return Err(t_with(
+                    "period.invalid_granularity",
+                    &[("value", &other), ("options", &GRANULARITIES.join(", "))],
+                ))
+            }
+        };
+
+        let sample = bucket_end.min(to);
+        dates.push(sample);
+        if dates.len() > MAX_POINTS {
+            return Err(t_with("period.too_many_points", &[("max", &MAX_POINTS)]));
+        }
+        if sample >= to {
+            return Ok(dates);
+        }
+        cursor = sample.succ_opt().ok_or_else(|| t("period.out_of_range"))?;
+    }
+}
@@ -0,0 +1,160 @@
+use std::sync::Mutex;
+
+use chrono::{Datelike, Days, NaiveDate};
+use sqlx::SqlitePool;
+
+use crate::commands::transactions::recalculate_account_balance;
+use crate::db::models::{RecurringGenerated, RecurringRunSummary, RecurringTransaction};
+use crate::db::queries::recurring;
+use crate::services::dates::add_months_clamped;
+use crate::services::i18n::t_with;
+
+/// Valid values for `recurring_transactions.frequency`.
+pub const FREQUENCIES: [&str; 4] = ["weekly", "biweekly", "monthly", "yearly"];
+
+/// Summary of the run performed during startup, kept in Tauri state until the UI reads it.
+#[derive(Default)]
+pub struct StartupRecurringSummary(pub Mutex<Option<RecurringRunSummary>>);
+
+/// Compute the occurrence that follows `date` for the given frequency.
+/// Monthly and yearly rules land on `anchor_day`, clamped to the end of shorter months.
+pub fn next_occurrence(date: NaiveDate, frequency: &str, anchor_day: u32) -> Option<NaiveDate> {
+    match frequency {
+        "weekly" => date.checked_add_days(Days::new(7)),
+        "biweekly" => date.checked_add_days(Days::new(14)),
+        "monthly" => add_months_clamped(date, 1, anchor_day),
+        "yearly" => add_months_clamped(date, 12, anchor_day),
+        _ => None,
+    }
+}
+
+/// Materialize every occurrence of a single rule up to and including `today`.
+/// Inserting the transactions and advancing `next_date` happen in one SQL transaction, and
+/// the unique (recurring_id, date) index makes re-running over the same dates a no-op.
+async fn process_rule(
+    pool: &SqlitePool,
+    rule: &RecurringTransaction,
+    today: NaiveDate,
+) -> Result<Vec<String>, String> {
+    let mut next = NaiveDate::parse_from_str(&rule.next_date, "%Y-%m-%d")
+        .map_err(|e| t_with("recurring.invalid_date", &[("id", &rule.id), ("error", &e)]))?;
+    let end = rule
+        .end_date
+        .as_deref()
+        .map(|d| NaiveDate::parse_from_str(d, "%Y-%m-%d"))
+        .transpose()
+        .map_err(|e| {
+            t_with(
+                "recurring.invalid_end_date",
+                &[("id", &rule.id), ("error", &e)],
+            )
+        })?;
+    let anchor_day = u32::try_from(rule.anchor_day).unwrap_or(next.day());
+
+    let mut generated = Vec::new();
+    let mut db_txn = pool.begin().await.map_err(|e| e.to_string())?;
+
+    while next <= today && end.is_none_or(|e| next <= e) {
+        let date = next.format("%Y-%m-%d").to_string();
+        let result = sqlx::query(
+            "INSERT OR IGNORE INTO transactions
+                (id, account_id, category_id, amount, type, description, date, recurring_id)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?)",
+        )
+        .bind(uuid::Uuid::new_v4().to_string())
+        .bind(&rule.account_id)
+        .bind(&rule.category_id)
+        .bind(rule.amount)
+        .bind(&rule.transaction_type)
+        .bind(&rule.description)
+        .bind(&date)
+        .bind(&rule.id)
+        .execute(&mut *db_txn)
+        .await
+        .map_err(|e| e.to_string())?;
+
+        if result.rows_affected() > 0 {
+            generated.push(date);
+        }
+
+        next = next_occurrence(next, &rule.frequency, anchor_day)
+            .ok_or_else(|| t_with("recurring.next_date_failed", &[("id", &rule.id)]))?;
+    }
+
+    // A rule whose next occurrence falls past its end date is finished
+    let finished = end.is_some_and(|e| next > e);
+
+    sqlx::query(
+        "UPDATE recurring_transactions
+         SET next_date = ?, is_active = CASE WHEN ? THEN 0 ELSE is_active END
+         WHERE id = ?",
+    )
+    .bind(next.format("%Y-%m-%d").to_string())
+    .bind(finished)
+    .bind(&rule.id)
+    .execute(&mut *db_txn)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    if !generated.is_empty() {
+        recalculate_account_balance(&mut db_txn, &rule.account_id).await?;
+    }
+
+    db_txn.commit().await.map_err(|e| e.to_string())?;
+
+    Ok(generated)
+}
+
+/// Generate the transactions of every active rule that is due on or before `today`.
+/// Rules on archived accounts or inactive categories are left untouched until reactivated.
+pub async fn process_due(
+    pool: &SqlitePool,
+    today: NaiveDate,
+) -> Result<RecurringRunSummary, String> {
+    let run_date = today.format("%Y-%m-%d").to_string();
+    let due = recurring::get_due(pool, &run_date)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let mut rules = Vec::new();
+    let mut generated_count: i64 = 0;
+
+    for rule in due {
+        let usable: (i64,) = sqlx::query_as(
+            "SELECT COUNT(*) FROM accounts a, categories c
+             WHERE a.id = ? AND c.id = ? AND a.is_active = 1 AND c.is_active = 1",
+        )
+        .bind(&rule.account_id)
+        .bind(&rule.category_id)
+        .fetch_one(pool)
+        .await
+        .map_err(|e| e.to_string())?;
+
+        if usable.0 == 0 {
+            continue;
+        }
+
+        let dates = process_rule(pool, &rule, today).await?;
+        if dates.is_empty() {
+            continue;
+        }
+
+        #[allow(clippy::cast_possible_wrap)]
+        let count = dates.len() as i64;
+        generated_count += count;
+        rules.push(RecurringGenerated {
+            recurring_id: rule.id,
+            description: rule.description,
+            account_id: rule.account_id,
+            amount: rule.amount,
+            transaction_type: rule.transaction_type,
+            dates,
+        });
+    }
+
+    Ok(RecurringRunSummary {
+        run_date,
+        generated_count,
+        rules,
+    })
+}
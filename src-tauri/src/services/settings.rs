@@ -0,0 +1,91 @@
+use std::collections::HashMap;
+
+use chrono::Weekday;
+use sqlx::SqlitePool;
+
+use crate::db::models::AppSettings;
+use crate::db::queries::settings;
+use crate::services::i18n::Locale;
+
+/// Base currency used until one is saved.
+pub const DEFAULT_BASE_CURRENCY_ID: &str = "cur_clp";
+
+/// Values accepted for `first_day_of_week`, Monday first.
+pub const WEEKDAYS: [&str; 7] = [
+    "monday",
+    "tuesday",
+    "wednesday",
+    "thursday",
+    "friday",
+    "saturday",
+    "sunday",
+];
+
+/// Parse a weekday from one of `WEEKDAYS`.
+pub fn parse_weekday(name: &str) -> Option<Weekday> {
+    WEEKDAYS
+        .contains(&name)
+        .then(|| name.parse().ok())
+        .flatten()
+}
+
+/// Lowercase English name of a weekday, as stored in settings.
+fn weekday_name(day: Weekday) -> &'static str {
+    WEEKDAYS[day.num_days_from_monday() as usize]
+}
+
+/// Settings built from the stored values, ignoring any that no longer parse.
+fn from_stored(stored: &HashMap<String, String>) -> AppSettings {
+    let get = |key: &str| stored.get(key).filter(|v| !v.is_empty()).cloned();
+
+    AppSettings {
+        base_currency_id: get(settings::BASE_CURRENCY_KEY)
+            .unwrap_or_else(|| DEFAULT_BASE_CURRENCY_ID.to_string()),
+        locale: get(settings::LOCALE_KEY)
+            .and_then(|code| Locale::from_code(&code))
+            .unwrap_or(Locale::Es)
+            .code()
+            .to_string(),
+        first_day_of_week: weekday_name(
+            get(settings::FIRST_DAY_OF_WEEK_KEY)
+                .and_then(|name| parse_weekday(&name))
+                .unwrap_or(Weekday::Mon),
+        )
+        .to_string(),
+        default_account_id: get(settings::DEFAULT_ACCOUNT_KEY),
+        default_debt_category_id: get(settings::DEFAULT_DEBT_CATEGORY_KEY),
+    }
+}
+
+/// Load every setting, with defaults for the ones never saved.
+pub async fn load(pool: &SqlitePool) -> Result<AppSettings, sqlx::Error> {
+    let stored: HashMap<String, String> = settings::get_all(pool).await?.into_iter().collect();
+    Ok(from_stored(&stored))
+}
+
+/// Currency every dashboard, budget and balance summary amount is converted to.
+pub async fn base_currency_id(pool: &SqlitePool) -> Result<String, sqlx::Error> {
+    Ok(load(pool).await?.base_currency_id)
+}
+
+/// Language of backend messages.
+pub async fn locale(pool: &SqlitePool) -> Result<Locale, sqlx::Error> {
+    let settings = load(pool).await?;
+    Ok(Locale::from_code(&settings.locale).unwrap_or(Locale::Es))
+}
+
+/// Day weeks start on, used to bucket weekly balance history.
+pub async fn first_day_of_week(pool: &SqlitePool) -> Result<Weekday, sqlx::Error> {
+    let settings = load(pool).await?;
+    Ok(parse_weekday(&settings.first_day_of_week).unwrap_or(Weekday::Mon))
+}
+
+/// Account preselected for new debts, if one has been chosen.
+pub async fn default_account_id(pool: &SqlitePool) -> Result<Option<String>, sqlx::Error> {
+    Ok(load(pool).await?.default_account_id)
+}
+
+/// Expense category used for installment payments, if one has been chosen.
+pub async fn default_debt_category_id(pool: &SqlitePool) -> Result<Option<String>, sqlx::Error> {
+    Ok(load(pool).await?.default_debt_category_id)
+}
@@ -0,0 +1,78 @@
+use chrono::{Datelike, Days, Months, NaiveDate};
+
+use crate::services::dates::add_months_clamped;
+
+/// Days from closing to the payment due date for cards without a payment due day.
+pub const DEFAULT_PAYMENT_DAYS: u64 = 15;
+
+/// When a credit card closes its statements and when they must be paid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CardSchedule {
+    /// Day of the month statements close on, clamped to the end of shorter months.
+    pub billing_day: u32,
+    /// Day of the month statements are due. `None` means `DEFAULT_PAYMENT_DAYS` after closing.
+    pub payment_due_day: Option<u32>,
+}
+
+/// One statement cycle. Both `start` and `closing` belong to the cycle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cycle {
+    pub start: NaiveDate,
+    pub closing: NaiveDate,
+    pub due: NaiveDate,
+}
+
+impl CardSchedule {
+    /// Closing date in the month `months` after the month of `date`.
+    fn closing_after(self, date: NaiveDate, months: u32) -> Option<NaiveDate> {
+        add_months_clamped(date.with_day(1)?, months, self.billing_day)
+    }
+
+    /// Whether a statement closes on `date`.
+    pub fn is_closing_date(self, date: NaiveDate) -> bool {
+        self.closing_after(date, 0) == Some(date)
+    }
+
+    /// Payment due date of the statement closing on `closing`: the first payment due day
+    /// after it, or `DEFAULT_PAYMENT_DAYS` later.
+    pub fn due_date(self, closing: NaiveDate) -> Option<NaiveDate> {
+        let Some(day) = self.payment_due_day else {
+            return closing.checked_add_days(Days::new(DEFAULT_PAYMENT_DAYS));
+        };
+
+        let same_month = add_months_clamped(closing.with_day(1)?, 0, day)?;
+        if same_month > closing {
+            Some(same_month)
+        } else {
+            add_months_clamped(closing.with_day(1)?, 1, day)
+        }
+    }
+
+    /// The cycle that closes on `closing`, which must be a closing date.
+    pub fn cycle_closing_on(self, closing: NaiveDate) -> Option<Cycle> {
+        let month_before = closing.with_day(1)?.checked_sub_months(Months::new(1))?;
+        let previous_closing = self.closing_after(month_before, 0)?;
+
+        Some(Cycle {
+            start: previous_closing.succ_opt()?,
+            closing,
+            due: self.due_date(closing)?,
+        })
+    }
+
+    /// The cycle a charge made on `date` is billed in.
+    pub fn cycle_containing(self, date: NaiveDate) -> Option<Cycle> {
+        let this_month = self.closing_after(date, 0)?;
+        let closing = if date <= this_month {
+            this_month
+        } else {
+            self.closing_after(date, 1)?
+        };
+        self.cycle_closing_on(closing)
+    }
+
+    /// The cycle just before `cycle`.
+    pub fn previous(self, cycle: Cycle) -> Option<Cycle> {
+        self.cycle_closing_on(cycle.start.pred_opt()?)
+    }
+}
@@ -0,0 +1,74 @@
+use necronomics_lib::amortization::{
+    level_payment, schedule, term_for_payment, RateType, ScheduleRow,
+};
+
+fn row(amount: i64, principal: i64, interest: i64, balance: i64) -> ScheduleRow {
+    ScheduleRow {
+        amount,
+        principal,
+        interest,
+        balance,
+    }
+}
+
+#[test]
+fn french_schedule_repays_the_principal_with_level_payments() {
+    let rate = RateType::Monthly.monthly_rate(2.0);
+    let payment = level_payment(1_000_000, rate, 12);
+    assert_eq!(payment, 94_560);
+
+    let rows = schedule(1_000_000, rate, 12, payment)
+        .unwrap_or_else(|| panic!("the level payment must fit the term"));
+    assert_eq!(rows.len(), 12);
+    assert_eq!(rows[0], row(94_560, 74_560, 20_000, 925_440));
+    assert!(rows[..11].iter().all(|r| r.amount == payment));
+
+    // The last installment absorbs the rounding of the others
+    assert_eq!(rows[11], row(94_555, 92_701, 1_854, 0));
+    assert_eq!(rows.iter().map(|r| r.principal).sum::<i64>(), 1_000_000);
+    assert_eq!(rows.iter().map(|r| r.interest).sum::<i64>(), 134_715);
+
+    // Without interest the principal is split evenly
+    let rows = schedule(100_000, 0.0, 3, level_payment(100_000, 0.0, 3))
+        .unwrap_or_else(|| panic!("the level payment must fit the term"));
+    assert_eq!(
+        rows,
+        vec![
+            row(33_333, 33_333, 0, 66_667),
+            row(33_333, 33_333, 0, 33_334),
+            row(33_334, 33_334, 0, 0),
+        ]
+    );
+}
+
+#[test]
+fn annual_and_cae_rates_convert_to_monthly() {
+    let monthly = RateType::Monthly.monthly_rate(2.0);
+    assert!((RateType::Annual.monthly_rate(24.0) - monthly).abs() < 1e-12);
+
+    // A CAE compounds back to itself over a year
+    let cae = RateType::Cae.monthly_rate(30.0);
+    assert!((cae - 0.022_104_45).abs() < 1e-8);
+    assert!(((1.0 + cae).powi(12) - 1.3).abs() < 1e-12);
+    assert_eq!(level_payment(600_000, cae, 6), 107_877);
+
+    assert_eq!(RateType::parse("cae"), Some(RateType::Cae));
+    assert_eq!(RateType::parse("weekly"), None);
+}
+
+#[test]
+fn quoted_payments_must_fit_the_term() {
+    let rate = RateType::Monthly.monthly_rate(2.0);
+
+    // Does not even cover the first month of interest
+    assert_eq!(schedule(1_000_000, rate, 12, 20_000), None);
+    assert_eq!(term_for_payment(1_000_000, rate, 20_000), None);
+    assert_eq!(term_for_payment(1_000_000, rate, 94_560), Some(12));
+    // Repays the debt before the last installment
+    assert_eq!(schedule(300_000, 0.0, 3, 150_000), None);
+
+    // A payment quoted slightly off leaves the difference on the last installment
+    let rows = schedule(300_000, 0.0, 3, 99_000)
+        .unwrap_or_else(|| panic!("the quoted payment must fit the term"));
+    assert_eq!(rows[2], row(102_000, 102_000, 0, 0));
+}
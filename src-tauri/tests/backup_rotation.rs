@@ -0,0 +1,181 @@
+use std::path::PathBuf;
+
+use chrono::{Duration, NaiveDate, NaiveDateTime};
+use necronomics_lib::backup::{
+    self, expired, file_name, parse_file_name, SnapshotReason, KEEP_DAILY, KEEP_LAST,
+};
+use necronomics_lib::{rollback_last_migration, run_migrations};
+use sqlx::sqlite::SqliteConnectOptions;
+use sqlx::SqlitePool;
+
+fn at(date: &str, time: &str) -> NaiveDateTime {
+    NaiveDateTime::parse_from_str(&format!("{date} {time}"), "%Y-%m-%d %H:%M:%S")
+        .unwrap_or_else(|e| panic!("bad test date {date} {time}: {e}"))
+}
+
+/// Fresh folder under the system temp dir, removed by the caller.
+fn temp_dir() -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("necronomics-backup-{}", uuid::Uuid::new_v4()));
+    std::fs::create_dir_all(&dir)
+        .unwrap_or_else(|e| panic!("cannot create {}: {e}", dir.display()));
+    dir
+}
+
+#[test]
+fn file_names_round_trip() {
+    let created_at = at("2025-03-01", "09:30:00");
+    let name = file_name(created_at, SnapshotReason::PreMigration);
+    assert_eq!(name, "necronomics-20250301-093000-pre_migration.db");
+    assert_eq!(
+        parse_file_name(&name),
+        Some((created_at, SnapshotReason::PreMigration))
+    );
+
+    assert_eq!(parse_file_name("necronomics.db"), None);
+    assert_eq!(
+        parse_file_name("necronomics-20250301-093000-manual.db"),
+        None
+    );
+    assert_eq!(
+        parse_file_name("necronomics-20250301-093000-startup.db-wal"),
+        None
+    );
+}
+
+#[test]
+fn keeps_recent_daily_and_weekly_snapshots() {
+    // Three snapshots a day for 60 days, newest first
+    let newest = at("2025-03-31", "20:00:00");
+    let snapshots: Vec<NaiveDateTime> = (0..60)
+        .flat_map(|day| {
+            [0, 4, 8].map(|hours| newest - Duration::days(day) - Duration::hours(hours))
+        })
+        .collect();
+
+    let expired = expired(&snapshots);
+    let kept: Vec<NaiveDateTime> = (0..snapshots.len())
+        .filter(|i| !expired.contains(i))
+        .map(|i| snapshots[i])
+        .collect();
+
+    // The most recent snapshots are all kept, even several on the same day
+    assert!(kept.starts_with(&snapshots[..KEEP_LAST]));
+
+    // Beyond those, one per day for the last week
+    let daily: Vec<NaiveDate> = kept.iter().map(NaiveDateTime::date).collect();
+    for day in 0..KEEP_DAILY {
+        let date = newest.date() - Duration::days(i64::try_from(day).unwrap_or_default());
+        assert!(daily.contains(&date), "no snapshot kept for {date}");
+    }
+
+    // and then the newest of each older week, four weeks counting the current one
+    let older: Vec<NaiveDateTime> = kept
+        .into_iter()
+        .filter(|k| *k < newest - Duration::days(7))
+        .collect();
+    assert_eq!(
+        older,
+        vec![at("2025-03-23", "20:00:00"), at("2025-03-16", "20:00:00")]
+    );
+}
+
+#[test]
+fn keeps_everything_within_the_limits() {
+    let snapshots = [at("2025-03-31", "10:00:00"), at("2025-03-30", "10:00:00")];
+    assert!(expired(&snapshots).is_empty());
+    assert!(expired(&[]).is_empty());
+}
+
+#[tokio::test]
+async fn snapshot_restores_over_the_database() {
+    let dir = temp_dir();
+    let db_path = dir.join("necronomics.db");
+    let options = SqliteConnectOptions::new()
+        .filename(&db_path)
+        .create_if_missing(true);
+    let pool = SqlitePool::connect_with(options.clone())
+        .await
+        .unwrap_or_else(|e| panic!("cannot open the database: {e}"));
+
+    for sql in [
+        "CREATE TABLE _migrations (name TEXT NOT NULL)",
+        "CREATE TABLE notes (body TEXT NOT NULL)",
+        "INSERT INTO notes (body) VALUES ('before')",
+    ] {
+        sqlx::query(sql)
+            .execute(&pool)
+            .await
+            .unwrap_or_else(|e| panic!("{sql}: {e}"));
+    }
+
+    let backups = backup::backup_dir(&db_path);
+    let created_at = at("2025-03-01", "09:30:00");
+    let snapshot = backup::create(&pool, &backups, SnapshotReason::Startup, created_at)
+        .await
+        .unwrap_or_else(|e| panic!("snapshot failed: {e}"));
+
+    let listed = backup::list(&backups).unwrap_or_else(|e| panic!("list failed: {e}"));
+    assert_eq!(listed.len(), 1);
+    assert_eq!(listed[0].created_at, "2025-03-01 09:30:00");
+    assert_eq!(listed[0].reason, "startup");
+    backup::verify(&snapshot)
+        .await
+        .unwrap_or_else(|e| panic!("snapshot is not valid: {e}"));
+
+    // The accidental bulk delete
+    sqlx::query("DELETE FROM notes")
+        .execute(&pool)
+        .await
+        .unwrap_or_else(|e| panic!("delete failed: {e}"));
+    pool.close().await;
+
+    backup::replace_database(&snapshot, &db_path).unwrap_or_else(|e| panic!("restore failed: {e}"));
+    let pool = SqlitePool::connect_with(options)
+        .await
+        .unwrap_or_else(|e| panic!("cannot reopen the database: {e}"));
+    let (count,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM notes")
+        .fetch_one(&pool)
+        .await
+        .unwrap_or_else(|e| panic!("count failed: {e}"));
+    assert_eq!(count, 1);
+    pool.close().await;
+
+    std::fs::remove_dir_all(&dir).ok();
+}
+
+#[tokio::test]
+async fn a_database_behind_on_migrations_is_snapshotted_first() {
+    let dir = temp_dir();
+    let options = SqliteConnectOptions::new()
+        .filename(dir.join("necronomics.db"))
+        .create_if_missing(true);
+    let pool = SqlitePool::connect_with(options)
+        .await
+        .unwrap_or_else(|e| panic!("cannot open the database: {e}"));
+    run_migrations(&pool)
+        .await
+        .unwrap_or_else(|e| panic!("migrations failed: {e}"));
+
+    // Up to date: nothing to protect
+    let backups = dir.join("backups");
+    let now = at("2025-03-01", "09:30:00");
+    let taken = backup::before_migrations(&pool, &backups, now)
+        .await
+        .unwrap_or_else(|e| panic!("snapshot failed: {e}"));
+    assert!(!taken);
+
+    // Like a restored snapshot that predates the latest migration
+    rollback_last_migration(&pool)
+        .await
+        .unwrap_or_else(|e| panic!("rollback failed: {e}"));
+    let taken = backup::before_migrations(&pool, &backups, now)
+        .await
+        .unwrap_or_else(|e| panic!("snapshot failed: {e}"));
+    assert!(taken);
+    let listed = backup::list(&backups).unwrap_or_else(|e| panic!("list failed: {e}"));
+    assert_eq!(listed.len(), 1);
+    assert_eq!(listed[0].reason, "pre_migration");
+    pool.close().await;
+
+    std::fs::remove_dir_all(&dir).ok();
+}
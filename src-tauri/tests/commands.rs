@@ -0,0 +1,1159 @@
+use necronomics_lib::commands::{
+    accounts, categories, dashboard, debts, integrity, statements, tags, transactions,
+};
+use necronomics_lib::models::{
+    CreateDebtInput, DashboardPeriod, DebtFilter, DebtWithInstallments, PayInstallmentInput,
+    PrepayDebtInput, SettleStatementInput, UpdateDebtInput,
+};
+use necronomics_lib::{run_migrations, AppError};
+use sqlx::sqlite::SqlitePoolOptions;
+use sqlx::SqlitePool;
+
+/// Fresh in-memory database with every migration applied. A single connection, since every
+/// connection to `:memory:` opens a database of its own.
+async fn migrated_pool() -> SqlitePool {
+    let pool = SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await
+        .unwrap_or_else(|e| panic!("cannot open an in-memory database: {e}"));
+    run_migrations(&pool)
+        .await
+        .unwrap_or_else(|e| panic!("migrations failed: {e}"));
+    pool
+}
+
+async fn bank_account(pool: &SqlitePool, name: &str) -> String {
+    accounts::create_account(
+        pool,
+        name.to_string(),
+        "bank".to_string(),
+        "cur_clp".to_string(),
+        None,
+        None,
+        None,
+    )
+    .await
+    .unwrap_or_else(|e| panic!("cannot create account {name}: {e}"))
+    .id
+}
+
+/// Credit card closing on `billing_day`, with statements due on `payment_due_day`.
+async fn credit_card(pool: &SqlitePool, billing_day: i32, payment_due_day: i32) -> String {
+    accounts::create_account(
+        pool,
+        "Visa".to_string(),
+        "credit_card".to_string(),
+        "cur_clp".to_string(),
+        Some(1_000_000),
+        Some(billing_day),
+        Some(payment_due_day),
+    )
+    .await
+    .unwrap_or_else(|e| panic!("cannot create card: {e}"))
+    .id
+}
+
+/// A 300.000 debt on `account_id` repaid in three interest-free installments of 100.000, due
+/// from 2025-01-15.
+async fn notebook_debt(pool: &SqlitePool, account_id: &str) -> DebtWithInstallments {
+    debts::create_debt(
+        pool,
+        CreateDebtInput {
+            account_id: Some(account_id.to_string()),
+            description: "Notebook".to_string(),
+            original_amount: 300_000,
+            total_installments: 3,
+            monthly_payment: Some(100_000),
+            interest_rate: 0.0,
+            rate_type: None,
+            start_date: "2025-01-15".to_string(),
+            notes: None,
+        },
+    )
+    .await
+    .unwrap_or_else(|e| panic!("cannot create debt: {e}"))
+}
+
+/// Pay an installment in full today under `category_id`.
+fn category(category_id: &str) -> PayInstallmentInput {
+    PayInstallmentInput {
+        category_id: Some(category_id.to_string()),
+        ..PayInstallmentInput::default()
+    }
+}
+
+async fn add(
+    pool: &SqlitePool,
+    account_id: &str,
+    category_id: &str,
+    amount: i64,
+    transaction_type: &str,
+    date: &str,
+) -> String {
+    transactions::create_transaction(
+        pool,
+        account_id.to_string(),
+        category_id.to_string(),
+        amount,
+        transaction_type.to_string(),
+        format!("{transaction_type} {amount}"),
+        date.to_string(),
+    )
+    .await
+    .unwrap_or_else(|e| panic!("cannot create transaction: {e}"))
+    .id
+}
+
+async fn balance(pool: &SqlitePool, account_id: &str) -> i64 {
+    accounts::get_account(pool, account_id.to_string())
+        .await
+        .unwrap_or_else(|e| panic!("cannot read account {account_id}: {e}"))
+        .balance
+}
+
+#[tokio::test]
+async fn balance_follows_transaction_changes() {
+    let pool = migrated_pool().await;
+    let checking = bank_account(&pool, "Cuenta Corriente").await;
+    let savings = bank_account(&pool, "Ahorro").await;
+
+    let salary = add(
+        &pool,
+        &checking,
+        "cat_sueldo",
+        500_000,
+        "income",
+        "2025-03-01",
+    )
+    .await;
+    let groceries = add(
+        &pool,
+        &checking,
+        "cat_supermercado",
+        80_000,
+        "expense",
+        "2025-03-02",
+    )
+    .await;
+    assert_eq!(balance(&pool, &checking).await, 420_000);
+
+    transactions::update_transaction(
+        &pool,
+        groceries.clone(),
+        None,
+        None,
+        Some(100_000),
+        None,
+        None,
+        None,
+    )
+    .await
+    .unwrap_or_else(|e| panic!("update failed: {e}"));
+    assert_eq!(balance(&pool, &checking).await, 400_000);
+
+    // Moving it to another account rebalances both
+    transactions::update_transaction(
+        &pool,
+        groceries,
+        Some(savings.clone()),
+        None,
+        None,
+        None,
+        None,
+        None,
+    )
+    .await
+    .unwrap_or_else(|e| panic!("update failed: {e}"));
+    assert_eq!(balance(&pool, &checking).await, 500_000);
+    assert_eq!(balance(&pool, &savings).await, -100_000);
+
+    transactions::delete_transaction(&pool, salary)
+        .await
+        .unwrap_or_else(|e| panic!("delete failed: {e}"));
+    assert_eq!(balance(&pool, &checking).await, 0);
+}
+
+#[tokio::test]
+async fn invalid_transactions_leave_the_balance_alone() {
+    let pool = migrated_pool().await;
+    let checking = bank_account(&pool, "Cuenta Corriente").await;
+    let id = add(
+        &pool,
+        &checking,
+        "cat_supermercado",
+        80_000,
+        "expense",
+        "2025-03-02",
+    )
+    .await;
+
+    let error = transactions::update_transaction(&pool, id, None, None, Some(0), None, None, None)
+        .await
+        .err()
+        .unwrap_or_else(|| panic!("a zero amount was accepted"));
+    assert!(matches!(error, AppError::Validation { .. }));
+    assert_eq!(balance(&pool, &checking).await, -80_000);
+}
+
+#[tokio::test]
+async fn suggestions_ignore_description_case() {
+    let pool = migrated_pool().await;
+    let checking = bank_account(&pool, "Cuenta Corriente").await;
+    for (description, amount, date) in [
+        ("Uber", 5_000, "2025-03-01"),
+        ("Uber", 5_000, "2025-03-02"),
+        ("uber", 7_000, "2025-03-03"),
+    ] {
+        transactions::create_transaction(
+            &pool,
+            checking.clone(),
+            "cat_transporte".to_string(),
+            amount,
+            "expense".to_string(),
+            description.to_string(),
+            date.to_string(),
+        )
+        .await
+        .unwrap_or_else(|e| panic!("cannot create transaction: {e}"));
+    }
+
+    // One suggestion, spelled as last used, counting and weighing every use
+    let suggestions = transactions::get_suggestions(&pool, "ub".to_string(), None)
+        .await
+        .unwrap_or_else(|e| panic!("cannot suggest: {e}"));
+    assert_eq!(suggestions.len(), 1);
+    assert_eq!(suggestions[0].description, "uber");
+    assert_eq!(suggestions[0].use_count, 3);
+    assert_eq!(suggestions[0].amount, 5_000);
+}
+
+#[tokio::test]
+async fn paying_an_installment_records_the_expense() {
+    let pool = migrated_pool().await;
+    let checking = bank_account(&pool, "Cuenta Corriente").await;
+
+    let debt = notebook_debt(&pool, &checking).await;
+    assert_eq!(debt.installments.len(), 3);
+    assert_eq!(debt.remaining_amount, 300_000);
+
+    let first = debt.installments[0].id.clone();
+    let paid = debts::mark_installment_paid(&pool, first.clone(), category("cat_vivienda"))
+        .await
+        .unwrap_or_else(|e| panic!("cannot pay installment: {e}"));
+    // Paid today, long after it fell due
+    assert_eq!(paid.status, "paid_late");
+    assert!(paid.transaction_id.is_some());
+    assert_eq!(balance(&pool, &checking).await, -100_000);
+
+    let detail = debts::get_debt_detail(&pool, debt.debt.id.clone())
+        .await
+        .unwrap_or_else(|e| panic!("cannot read debt: {e}"));
+    assert_eq!(detail.debt.paid_installments, 1);
+    assert_eq!(detail.remaining_amount, 200_000);
+
+    // Paying twice is refused and charges nothing
+    assert!(
+        debts::mark_installment_paid(&pool, first, category("cat_vivienda"))
+            .await
+            .is_err()
+    );
+    assert_eq!(balance(&pool, &checking).await, -100_000);
+
+    // An income category cannot pay a debt
+    let second = debt.installments[1].id.clone();
+    assert!(
+        debts::mark_installment_paid(&pool, second, category("cat_sueldo"))
+            .await
+            .is_err()
+    );
+}
+
+#[tokio::test]
+async fn unmarking_an_installment_undoes_the_payment() {
+    let pool = migrated_pool().await;
+    let checking = bank_account(&pool, "Cuenta Corriente").await;
+
+    let debt = notebook_debt(&pool, &checking).await;
+    let first = debt.installments[0].id.clone();
+    let paid = debts::mark_installment_paid(&pool, first.clone(), category("cat_vivienda"))
+        .await
+        .unwrap_or_else(|e| panic!("cannot pay installment: {e}"));
+    let payment = paid
+        .transaction_id
+        .unwrap_or_else(|| panic!("the payment has no transaction"));
+
+    // The payment can only be removed or changed from its debt, short of relabelling it
+    let deleted = transactions::delete_transaction(&pool, payment.clone()).await;
+    assert!(matches!(deleted, Err(AppError::Conflict { .. })));
+    let edited = transactions::update_transaction(
+        &pool,
+        payment.clone(),
+        None,
+        None,
+        Some(50_000),
+        None,
+        None,
+        None,
+    )
+    .await;
+    assert!(matches!(edited, Err(AppError::Conflict { .. })));
+    let relabelled = transactions::update_transaction(
+        &pool,
+        payment.clone(),
+        None,
+        None,
+        None,
+        None,
+        Some("Cuota notebook".to_string()),
+        None,
+    )
+    .await
+    .unwrap_or_else(|e| panic!("cannot relabel the payment: {e}"));
+    assert_eq!(relabelled.amount, 100_000);
+
+    let unpaid = debts::unmark_installment_paid(&pool, first.clone())
+        .await
+        .unwrap_or_else(|e| panic!("cannot unmark installment: {e}"));
+    assert_eq!(unpaid.status, "pending");
+    assert_eq!(unpaid.transaction_id, None);
+    assert_eq!(balance(&pool, &checking).await, 0);
+    assert!(transactions::delete_transaction(&pool, payment)
+        .await
+        .is_err_and(|e| matches!(e, AppError::NotFound { .. })));
+
+    let detail = debts::get_debt_detail(&pool, debt.debt.id.clone())
+        .await
+        .unwrap_or_else(|e| panic!("cannot read debt: {e}"));
+    assert_eq!(detail.debt.paid_installments, 0);
+    assert_eq!(detail.remaining_amount, 300_000);
+
+    let again = debts::unmark_installment_paid(&pool, first).await;
+    assert!(matches!(again, Err(AppError::Conflict { .. })));
+
+    // Undoing an early payoff reopens the debt
+    let paid_off = debts::pay_off_debt(
+        &pool,
+        debt.debt.id.clone(),
+        Some("cat_vivienda".to_string()),
+    )
+    .await
+    .unwrap_or_else(|e| panic!("cannot pay off: {e}"));
+    assert_eq!(paid_off.debt.is_active, 0);
+    debts::unmark_installment_paid(&pool, paid_off.installments[0].id.clone())
+        .await
+        .unwrap_or_else(|e| panic!("cannot unmark payoff: {e}"));
+    let reopened = debts::get_debt_detail(&pool, debt.debt.id)
+        .await
+        .unwrap_or_else(|e| panic!("cannot read debt: {e}"));
+    assert_eq!(reopened.debt.is_active, 1);
+    assert_eq!(reopened.remaining_amount, 300_000);
+    assert_eq!(balance(&pool, &checking).await, 0);
+}
+
+#[tokio::test]
+async fn installments_record_how_they_were_actually_paid() {
+    let pool = migrated_pool().await;
+    let checking = bank_account(&pool, "Cuenta Corriente").await;
+    let card = credit_card(&pool, 20, 5).await;
+
+    let debt = notebook_debt(&pool, &card).await;
+    let ids: Vec<String> = debt.installments.iter().map(|i| i.id.clone()).collect();
+    let pay = |id: &String, date: &str, amount: Option<i64>| {
+        debts::mark_installment_paid(
+            &pool,
+            id.clone(),
+            PayInstallmentInput {
+                date: Some(date.to_string()),
+                amount,
+                ..category("cat_vivienda")
+            },
+        )
+    };
+
+    // Paid ahead of its due date from the bank: a little extra does not make it late
+    let on_time = debts::mark_installment_paid(
+        &pool,
+        ids[0].clone(),
+        PayInstallmentInput {
+            date: Some("2025-02-18".to_string()),
+            amount: Some(101_000),
+            source_account_id: Some(checking.clone()),
+            ..category("cat_vivienda")
+        },
+    )
+    .await
+    .unwrap_or_else(|e| panic!("cannot pay installment: {e}"));
+    assert_eq!(on_time.status, "paid");
+    assert_eq!(on_time.paid_amount, Some(101_000));
+    assert_eq!(on_time.actual_payment_date.as_deref(), Some("2025-02-18"));
+    assert_eq!(balance(&pool, &checking).await, -101_000);
+    assert_eq!(balance(&pool, &card).await, 0);
+
+    // The exact amount a week after the due date is late all the same
+    let late = pay(&ids[1], "2025-03-27", None)
+        .await
+        .unwrap_or_else(|e| panic!("cannot pay installment: {e}"));
+    assert_eq!(debt.installments[1].due_date, "2025-03-20");
+    assert_eq!(late.status, "paid_late");
+    assert_eq!(late.paid_amount, Some(100_000));
+
+    // Paid short: the 40.000 left stay owed and the debt stays open
+    let partial = pay(&ids[2], "2025-04-15", Some(60_000))
+        .await
+        .unwrap_or_else(|e| panic!("cannot pay installment: {e}"));
+    assert_eq!(partial.status, "partial");
+    let detail = debts::get_debt_detail(&pool, debt.debt.id.clone())
+        .await
+        .unwrap_or_else(|e| panic!("cannot read debt: {e}"));
+    assert_eq!(detail.debt.paid_installments, 2);
+    assert_eq!(detail.remaining_amount, 40_000);
+    assert_eq!(detail.debt.status, "active");
+    assert_eq!(detail.next_due_date.as_deref(), Some("2025-04-20"));
+
+    // Paying the rest after the due date completes it late and pays the debt off
+    let completed = pay(&ids[2], "2025-04-25", None)
+        .await
+        .unwrap_or_else(|e| panic!("cannot pay installment: {e}"));
+    assert_eq!(completed.status, "paid_late");
+    assert_eq!(completed.paid_amount, Some(100_000));
+    assert_eq!(balance(&pool, &card).await, -200_000);
+    let detail = debts::get_debt_detail(&pool, debt.debt.id.clone())
+        .await
+        .unwrap_or_else(|e| panic!("cannot read debt: {e}"));
+    assert_eq!(detail.remaining_amount, 0);
+    assert_eq!(detail.debt.status, "paid_off");
+
+    // Neither payment can be deleted on its own; unmarking undoes both
+    let first_payment = partial
+        .transaction_id
+        .unwrap_or_else(|| panic!("the payment has no transaction"));
+    let deleted = transactions::delete_transaction(&pool, first_payment).await;
+    assert!(matches!(deleted, Err(AppError::Conflict { .. })));
+    debts::unmark_installment_paid(&pool, ids[2].clone())
+        .await
+        .unwrap_or_else(|e| panic!("cannot unmark installment: {e}"));
+    assert_eq!(balance(&pool, &card).await, -100_000);
+    let detail = debts::get_debt_detail(&pool, debt.debt.id)
+        .await
+        .unwrap_or_else(|e| panic!("cannot read debt: {e}"));
+    assert_eq!(detail.remaining_amount, 100_000);
+    assert_eq!(detail.debt.status, "active");
+}
+
+#[tokio::test]
+async fn installment_payments_are_validated() {
+    let pool = migrated_pool().await;
+    let checking = bank_account(&pool, "Cuenta Corriente").await;
+
+    let debt = notebook_debt(&pool, &checking).await;
+
+    // Payments cannot be dated ahead, be empty or come from another currency
+    let tomorrow = (chrono::Local::now().date_naive() + chrono::Days::new(1))
+        .format("%Y-%m-%d")
+        .to_string();
+    let dollars = accounts::create_account(
+        &pool,
+        "Dolares".to_string(),
+        "bank".to_string(),
+        "cur_usd".to_string(),
+        None,
+        None,
+        None,
+    )
+    .await
+    .unwrap_or_else(|e| panic!("cannot create account: {e}"))
+    .id;
+    for input in [
+        PayInstallmentInput {
+            date: Some(tomorrow),
+            ..category("cat_vivienda")
+        },
+        PayInstallmentInput {
+            amount: Some(0),
+            ..category("cat_vivienda")
+        },
+        PayInstallmentInput {
+            source_account_id: Some(dollars),
+            ..category("cat_vivienda")
+        },
+    ] {
+        let refused =
+            debts::mark_installment_paid(&pool, debt.installments[0].id.clone(), input).await;
+        assert!(matches!(refused, Err(AppError::Validation { .. })));
+    }
+}
+
+#[tokio::test]
+async fn debts_move_through_their_lifecycle() {
+    let pool = migrated_pool().await;
+    let checking = bank_account(&pool, "Cuenta Corriente").await;
+    let create = |description: &str| {
+        debts::create_debt(
+            &pool,
+            CreateDebtInput {
+                account_id: Some(checking.clone()),
+                description: description.to_string(),
+                original_amount: 200_000,
+                total_installments: 2,
+                monthly_payment: None,
+                interest_rate: 0.0,
+                rate_type: None,
+                start_date: "2025-01-15".to_string(),
+                notes: None,
+            },
+        )
+    };
+    let with_status = |status: &str| DebtFilter {
+        account_id: None,
+        is_active: None,
+        statuses: Some(vec![status.to_string()]),
+        search: None,
+    };
+
+    // Paying the last installment pays the debt off
+    let notebook = create("Notebook")
+        .await
+        .unwrap_or_else(|e| panic!("cannot create debt: {e}"));
+    for installment in &notebook.installments {
+        debts::mark_installment_paid(&pool, installment.id.clone(), category("cat_vivienda"))
+            .await
+            .unwrap_or_else(|e| panic!("cannot pay installment: {e}"));
+    }
+    let paid_off = debts::list_debts(&pool, with_status("paid_off"))
+        .await
+        .unwrap_or_else(|e| panic!("cannot list debts: {e}"));
+    assert_eq!(paid_off.len(), 1);
+    assert_eq!(paid_off[0].id, notebook.debt.id);
+    assert_eq!(paid_off[0].is_active, 0);
+
+    let history = debts::list_debt_status_history(&pool, notebook.debt.id.clone())
+        .await
+        .unwrap_or_else(|e| panic!("cannot read history: {e}"));
+    let reasons: Vec<(Option<&str>, &str, &str)> = history
+        .iter()
+        .map(|c| {
+            (
+                c.from_status.as_deref(),
+                c.to_status.as_str(),
+                c.reason.as_str(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        reasons,
+        vec![
+            (None, "active", "created"),
+            (Some("active"), "paid_off", "last_installment_paid"),
+        ]
+    );
+
+    // Paid-off debts only change back when a payment is undone
+    let reopened =
+        debts::change_debt_status(&pool, notebook.debt.id.clone(), "active".to_string(), None)
+            .await;
+    assert!(matches!(reopened, Err(AppError::Conflict { .. })));
+
+    let tv = create("Televisor")
+        .await
+        .unwrap_or_else(|e| panic!("cannot create debt: {e}"));
+    let defaulted = debts::change_debt_status(
+        &pool,
+        tv.debt.id.clone(),
+        "defaulted".to_string(),
+        Some(" Sin pagar desde marzo ".to_string()),
+    )
+    .await
+    .unwrap_or_else(|e| panic!("cannot change status: {e}"));
+    assert_eq!(defaulted.status, "defaulted");
+    assert_eq!(defaulted.is_active, 1);
+
+    let refinanced =
+        debts::change_debt_status(&pool, tv.debt.id.clone(), "refinanced".to_string(), None)
+            .await
+            .unwrap_or_else(|e| panic!("cannot change status: {e}"));
+    assert_eq!(refinanced.is_active, 0);
+
+    // A refinanced debt is no longer repaid here
+    let payoff =
+        debts::pay_off_debt(&pool, tv.debt.id.clone(), Some("cat_vivienda".to_string())).await;
+    assert!(matches!(payoff, Err(AppError::Conflict { .. })));
+
+    let history = debts::list_debt_status_history(&pool, tv.debt.id)
+        .await
+        .unwrap_or_else(|e| panic!("cannot read history: {e}"));
+    assert_eq!(history.len(), 3);
+    assert_eq!(history[1].reason, "manual");
+    assert_eq!(history[1].note.as_deref(), Some("Sin pagar desde marzo"));
+
+    let invalid = debts::list_debts(&pool, with_status("closed")).await;
+    assert!(matches!(invalid, Err(AppError::Validation { .. })));
+}
+
+#[tokio::test]
+async fn debts_amortize_interest_over_the_installments() {
+    let pool = migrated_pool().await;
+    let checking = bank_account(&pool, "Cuenta Corriente").await;
+    let loan = |monthly_payment| CreateDebtInput {
+        account_id: Some(checking.clone()),
+        description: "Credito de consumo".to_string(),
+        original_amount: 1_000_000,
+        total_installments: 12,
+        monthly_payment,
+        interest_rate: 24.0,
+        rate_type: Some("annual".to_string()),
+        start_date: "2025-01-15".to_string(),
+        notes: None,
+    };
+
+    let debt = debts::create_debt(&pool, loan(None))
+        .await
+        .unwrap_or_else(|e| panic!("cannot create debt: {e}"));
+    assert_eq!(debt.debt.monthly_payment, 94_560);
+    assert_eq!(debt.debt.rate_type, "annual");
+    assert_eq!(debt.installments[0].interest, 20_000);
+    assert_eq!(debt.installments[0].balance, 925_440);
+    assert_eq!(debt.interest_paid, 0);
+    assert_eq!(debt.interest_remaining, 134_715);
+    assert_eq!(debt.remaining_amount, 1_134_715);
+
+    debts::mark_installment_paid(
+        &pool,
+        debt.installments[0].id.clone(),
+        category("cat_vivienda"),
+    )
+    .await
+    .unwrap_or_else(|e| panic!("cannot pay installment: {e}"));
+    let detail = debts::get_debt_detail(&pool, debt.debt.id.clone())
+        .await
+        .unwrap_or_else(|e| panic!("cannot read debt: {e}"));
+    assert_eq!(detail.interest_paid, 20_000);
+    assert_eq!(detail.interest_remaining, 114_715);
+
+    // A rate that is not a number is refused; a new rate re-amortizes the principal left over
+    // the same term
+    let nan = debts::update_debt(
+        &pool,
+        debt.debt.id.clone(),
+        UpdateDebtInput {
+            description: None,
+            interest_rate: Some(f64::NAN),
+            notes: None,
+        },
+    )
+    .await;
+    assert!(matches!(nan, Err(AppError::Validation { .. })));
+    let updated = debts::update_debt(
+        &pool,
+        debt.debt.id.clone(),
+        UpdateDebtInput {
+            description: None,
+            interest_rate: Some(0.0),
+            notes: None,
+        },
+    )
+    .await
+    .unwrap_or_else(|e| panic!("cannot update debt: {e}"));
+    assert_eq!(updated.monthly_payment, 84_131);
+    assert_eq!(updated.total_installments, 12);
+    let detail = debts::get_debt_detail(&pool, debt.debt.id.clone())
+        .await
+        .unwrap_or_else(|e| panic!("cannot read debt: {e}"));
+    assert_eq!(detail.interest_paid, 20_000);
+    assert_eq!(detail.interest_remaining, 0);
+    assert_eq!(detail.remaining_amount, 925_440);
+
+    // A quoted installment that does not fit the rate and term is refused
+    let mismatch = debts::create_debt(&pool, loan(Some(200_000))).await;
+    assert!(matches!(mismatch, Err(AppError::Validation { .. })));
+}
+
+#[tokio::test]
+async fn prepayments_reschedule_the_rest_of_the_debt() {
+    let pool = migrated_pool().await;
+    let checking = bank_account(&pool, "Cuenta Corriente").await;
+    // 1.000.000 at 2% a month: 11 installments and 925.440 of principal left after the first
+    let loan_with_first_paid = || async {
+        let debt = debts::create_debt(
+            &pool,
+            CreateDebtInput {
+                account_id: Some(checking.clone()),
+                description: "Credito de consumo".to_string(),
+                original_amount: 1_000_000,
+                total_installments: 12,
+                monthly_payment: None,
+                interest_rate: 2.0,
+                rate_type: None,
+                start_date: "2025-01-15".to_string(),
+                notes: None,
+            },
+        )
+        .await
+        .unwrap_or_else(|e| panic!("cannot create debt: {e}"));
+        debts::mark_installment_paid(
+            &pool,
+            debt.installments[0].id.clone(),
+            category("cat_vivienda"),
+        )
+        .await
+        .unwrap_or_else(|e| panic!("cannot pay installment: {e}"));
+        debt.debt.id
+    };
+    let prepayment = |debt_id: &str, amount, mode: &str| PrepayDebtInput {
+        debt_id: debt_id.to_string(),
+        amount,
+        mode: mode.to_string(),
+        category_id: Some("cat_vivienda".to_string()),
+    };
+
+    let shorter = loan_with_first_paid().await;
+    let detail = debts::prepay_debt(&pool, prepayment(&shorter, 200_000, "shorter_term"))
+        .await
+        .unwrap_or_else(|e| panic!("cannot prepay: {e}"));
+    assert_eq!(detail.debt.total_installments, 10);
+    assert_eq!(detail.installments.len(), 10);
+    assert_eq!(detail.debt.monthly_payment, 94_560);
+    assert_eq!(detail.installments[1].interest, 14_509);
+    assert_eq!(detail.installments[9].amount, 39_130);
+    assert_eq!(detail.interest_remaining, 70_170);
+    assert_eq!(balance(&pool, &checking).await, -294_560);
+    let payoff_due = detail.installments[1].due_date.clone();
+
+    let lower = loan_with_first_paid().await;
+    let detail = debts::prepay_debt(&pool, prepayment(&lower, 200_000, "lower_payment"))
+        .await
+        .unwrap_or_else(|e| panic!("cannot prepay: {e}"));
+    assert_eq!(detail.debt.total_installments, 12);
+    assert_eq!(detail.debt.monthly_payment, 74_124);
+    assert_eq!(detail.installments[11].amount, 74_124);
+    assert_eq!(detail.interest_remaining, 89_924);
+
+    // The prepayment's expense stays linked to the debt it rescheduled
+    let (expense,): (String,) =
+        sqlx::query_as("SELECT transaction_id FROM debt_prepayments WHERE debt_id = ?")
+            .bind(&lower)
+            .fetch_one(&pool)
+            .await
+            .unwrap_or_else(|e| panic!("the prepayment is not linked: {e}"));
+    let deleted = transactions::delete_transaction(&pool, expense).await;
+    assert!(matches!(deleted, Err(AppError::Conflict { .. })));
+
+    // Prepaying everything is an early payoff
+    let too_much = debts::prepay_debt(&pool, prepayment(&lower, 725_440, "lower_payment")).await;
+    assert!(matches!(too_much, Err(AppError::Validation { .. })));
+
+    let paid_off = debts::pay_off_debt(&pool, shorter.clone(), Some("cat_vivienda".to_string()))
+        .await
+        .unwrap_or_else(|e| panic!("cannot pay off: {e}"));
+    assert_eq!(paid_off.debt.is_active, 0);
+    assert_eq!(paid_off.debt.total_installments, 2);
+    assert_eq!(paid_off.debt.paid_installments, 2);
+    assert_eq!(paid_off.installments[1].amount, 725_440);
+    // The payoff keeps its place in the schedule and records when it was actually paid
+    let today = chrono::Local::now().format("%Y-%m-%d").to_string();
+    assert_eq!(paid_off.installments[1].due_date, payoff_due);
+    assert_eq!(
+        paid_off.installments[1].actual_payment_date.as_deref(),
+        Some(today.as_str())
+    );
+    assert_eq!(paid_off.remaining_amount, 0);
+    assert_eq!(balance(&pool, &checking).await, -1_314_560);
+
+    let again = debts::pay_off_debt(&pool, shorter, Some("cat_vivienda".to_string())).await;
+    assert!(matches!(again, Err(AppError::Conflict { .. })));
+}
+
+#[tokio::test]
+async fn categories_nest_a_single_level() {
+    let pool = migrated_pool().await;
+
+    // A subcategory takes the type of its parent
+    let ropa = categories::create_category(
+        &pool,
+        "Ropa".to_string(),
+        "income".to_string(),
+        None,
+        Some("cat_vivienda".to_string()),
+    )
+    .await
+    .unwrap_or_else(|e| panic!("cannot create category: {e}"));
+    assert_eq!(ropa.category_type, "expense");
+
+    // No subcategory under a subcategory
+    let nested = categories::create_category(
+        &pool,
+        "Zapatos".to_string(),
+        "expense".to_string(),
+        None,
+        Some(ropa.id.clone()),
+    )
+    .await;
+    assert!(matches!(nested, Err(AppError::Validation { .. })));
+
+    // Nor under a parent of another type
+    let mismatch = categories::update_category(
+        &pool,
+        ropa.id.clone(),
+        None,
+        None,
+        None,
+        Some("cat_sueldo".to_string()),
+    )
+    .await;
+    assert!(matches!(mismatch, Err(AppError::Validation { .. })));
+
+    // A category with subcategories cannot become one itself
+    let moved = categories::update_category(
+        &pool,
+        "cat_vivienda".to_string(),
+        None,
+        None,
+        None,
+        Some("cat_transporte".to_string()),
+    )
+    .await;
+    assert!(matches!(moved, Err(AppError::Validation { .. })));
+
+    // Moving a leaf to another parent of the same type is fine
+    let ropa = categories::update_category(
+        &pool,
+        ropa.id,
+        None,
+        None,
+        None,
+        Some("cat_transporte".to_string()),
+    )
+    .await
+    .unwrap_or_else(|e| panic!("cannot move category: {e}"));
+    assert_eq!(ropa.parent_id.as_deref(), Some("cat_transporte"));
+}
+
+#[tokio::test]
+async fn dashboard_aggregates_the_period() {
+    let pool = migrated_pool().await;
+    let checking = bank_account(&pool, "Cuenta Corriente").await;
+
+    add(
+        &pool,
+        &checking,
+        "cat_sueldo_base",
+        900_000,
+        "income",
+        "2025-03-01",
+    )
+    .await;
+    add(
+        &pool,
+        &checking,
+        "cat_supermercado",
+        60_000,
+        "expense",
+        "2025-03-05",
+    )
+    .await;
+    add(
+        &pool,
+        &checking,
+        "cat_restaurant",
+        40_000,
+        "expense",
+        "2025-03-12",
+    )
+    .await;
+    add(
+        &pool,
+        &checking,
+        "cat_bencina",
+        50_000,
+        "expense",
+        "2025-03-20",
+    )
+    .await;
+    // Outside the period: counted in the balance but not in the totals
+    add(
+        &pool,
+        &checking,
+        "cat_supermercado",
+        30_000,
+        "expense",
+        "2025-02-20",
+    )
+    .await;
+
+    let data = dashboard::get_dashboard_data(
+        &pool,
+        Some(DashboardPeriod {
+            period_type: "custom".to_string(),
+            months: None,
+            date_from: Some("2025-03-01".to_string()),
+            date_to: Some("2025-03-31".to_string()),
+        }),
+    )
+    .await
+    .unwrap_or_else(|e| panic!("dashboard failed: {e}"));
+
+    assert_eq!(data.balance_summary.consolidated_total, Some(720_000));
+    assert_eq!(data.monthly_income_expense.income, 900_000);
+    assert_eq!(data.monthly_income_expense.expense, 150_000);
+
+    // Subcategories roll up into their parent
+    let top: Vec<(&str, i64)> = data
+        .top_categories
+        .iter()
+        .map(|c| (c.category_id.as_str(), c.amount))
+        .collect();
+    assert_eq!(
+        top,
+        vec![("cat_alimentacion", 100_000), ("cat_transporte", 50_000)]
+    );
+    let shares: f64 = data.top_categories.iter().map(|c| c.percentage).sum();
+    assert!((shares - 100.0).abs() < 1e-9);
+}
+
+#[tokio::test]
+async fn integrity_repair_fixes_drifted_data() {
+    let pool = migrated_pool().await;
+    let checking = bank_account(&pool, "Cuenta Corriente").await;
+
+    let debt = notebook_debt(&pool, &checking).await;
+    let paid = debts::mark_installment_paid(
+        &pool,
+        debt.installments[0].id.clone(),
+        category("cat_vivienda"),
+    )
+    .await
+    .unwrap_or_else(|e| panic!("cannot pay installment: {e}"));
+    let payment = paid
+        .transaction_id
+        .unwrap_or_else(|| panic!("the payment has no transaction"));
+    for installment in &debt.installments[1..] {
+        debts::mark_installment_paid(&pool, installment.id.clone(), category("cat_vivienda"))
+            .await
+            .unwrap_or_else(|e| panic!("cannot pay installment: {e}"));
+    }
+
+    let salary = add(
+        &pool,
+        &checking,
+        "cat_sueldo",
+        500_000,
+        "income",
+        "2025-03-01",
+    )
+    .await;
+    let tag = tags::create_tag(&pool, "Trabajo".to_string(), None)
+        .await
+        .unwrap_or_else(|e| panic!("cannot create tag: {e}"));
+    tags::set_transaction_tags(&pool, salary.clone(), vec![tag.id])
+        .await
+        .unwrap_or_else(|e| panic!("cannot tag transaction: {e}"));
+
+    assert!(integrity::verify_integrity(&pool)
+        .await
+        .unwrap_or_else(|e| panic!("verify failed: {e}"))
+        .balance_drift
+        .is_empty());
+
+    // Writes that bypassed the commands, with foreign keys off as on an old connection
+    for sql in [
+        "PRAGMA foreign_keys = OFF".to_string(),
+        format!("DELETE FROM transactions WHERE id IN ('{payment}', '{salary}')"),
+        format!("UPDATE accounts SET balance = 1 WHERE id = '{checking}'"),
+    ] {
+        sqlx::query(&sql)
+            .execute(&pool)
+            .await
+            .unwrap_or_else(|e| panic!("{sql}: {e}"));
+    }
+
+    let report = integrity::verify_integrity(&pool)
+        .await
+        .unwrap_or_else(|e| panic!("verify failed: {e}"));
+    assert!(!report.repaired);
+    assert_eq!(report.balance_drift.len(), 1);
+    assert_eq!(report.balance_drift[0].stored_balance, 1);
+    assert_eq!(report.balance_drift[0].computed_balance, -200_000);
+    assert_eq!(report.dangling_installments.len(), 1);
+    assert_eq!(report.dangling_installments[0].transaction_id, payment);
+    assert_eq!(report.orphaned_tag_links.len(), 1);
+    assert!(report.paid_installments_mismatches.is_empty());
+
+    // Verifying changes nothing
+    assert_eq!(balance(&pool, &checking).await, 1);
+
+    let report = integrity::repair_integrity(&pool)
+        .await
+        .unwrap_or_else(|e| panic!("repair failed: {e}"));
+    assert!(report.repaired);
+    assert_eq!(balance(&pool, &checking).await, -200_000);
+
+    // The debt its last payments closed is open again
+    let detail = debts::get_debt_detail(&pool, debt.debt.id)
+        .await
+        .unwrap_or_else(|e| panic!("cannot read debt: {e}"));
+    assert_eq!(detail.debt.paid_installments, 2);
+    assert_eq!(detail.installments[0].status, "pending");
+    assert_eq!(detail.debt.status, "active");
+
+    let report = integrity::verify_integrity(&pool)
+        .await
+        .unwrap_or_else(|e| panic!("verify failed: {e}"));
+    assert!(report.balance_drift.is_empty());
+    assert!(report.paid_installments_mismatches.is_empty());
+    assert!(report.dangling_installments.is_empty());
+    assert!(report.orphaned_tag_links.is_empty());
+}
+
+#[tokio::test]
+async fn statements_bill_charges_and_installments_of_the_cycle() {
+    let pool = migrated_pool().await;
+    let bank = bank_account(&pool, "Cuenta Corriente").await;
+    let card = credit_card(&pool, 20, 5).await;
+
+    // Installments are due on the billing day: 2025-02-20, 2025-03-20 and 2025-04-20
+    let debt = notebook_debt(&pool, &card).await;
+
+    add(
+        &pool,
+        &card,
+        "cat_supermercado",
+        30_000,
+        "expense",
+        "2025-02-21",
+    )
+    .await;
+    add(
+        &pool,
+        &card,
+        "cat_restaurant",
+        20_000,
+        "expense",
+        "2025-03-20",
+    )
+    .await;
+    add(
+        &pool,
+        &card,
+        "cat_otros_income",
+        10_000,
+        "income",
+        "2025-03-05",
+    )
+    .await;
+    add(&pool, &card, "cat_bencina", 5_000, "expense", "2025-03-21").await;
+
+    // Paying the installment adds an expense to the card, but it is billed by due date
+    debts::mark_installment_paid(
+        &pool,
+        debt.installments[1].id.clone(),
+        category("cat_vivienda"),
+    )
+    .await
+    .unwrap_or_else(|e| panic!("cannot pay installment: {e}"));
+
+    let statement = statements::get_statement(&pool, card.clone(), "2025-03-20".to_string())
+        .await
+        .unwrap_or_else(|e| panic!("cannot read statement: {e}"));
+    assert_eq!(statement.period_start, "2025-02-21");
+    assert_eq!(statement.due_date, "2025-04-05");
+    assert!(statement.is_closed);
+    assert_eq!(statement.charges.len(), 3);
+    assert_eq!(statement.charges_total, 40_000);
+    assert_eq!(statement.installments.len(), 1);
+    assert_eq!(statement.installments[0].installment_number, 2);
+    assert_eq!(statement.total, 140_000);
+    assert_eq!(statement.outstanding, 140_000);
+
+    assert!(matches!(
+        statements::get_statement(&pool, card.clone(), "2025-03-19".to_string()).await,
+        Err(AppError::Validation { .. })
+    ));
+    assert!(matches!(
+        statements::list_statements(&pool, bank.clone(), None).await,
+        Err(AppError::Validation { .. })
+    ));
+
+    let settle = |closing_date: &str| SettleStatementInput {
+        account_id: card.clone(),
+        closing_date: closing_date.to_string(),
+        from_account_id: bank.clone(),
+        date: Some("2025-04-02".to_string()),
+        description: None,
+    };
+    let statement = statements::settle_statement(&pool, settle("2025-03-20"))
+        .await
+        .unwrap_or_else(|e| panic!("cannot settle statement: {e}"));
+    assert_eq!(statement.paid_amount, 140_000);
+    assert_eq!(statement.outstanding, 0);
+    assert_eq!(balance(&pool, &bank).await, -140_000);
+    // -30 000 - 20 000 + 10 000 - 5 000 - 100 000 + 140 000
+    assert_eq!(balance(&pool, &card).await, -5_000);
+
+    // The payment is a transfer, so it is not a charge of the next statement
+    let next = statements::get_statement(&pool, card.clone(), "2025-04-20".to_string())
+        .await
+        .unwrap_or_else(|e| panic!("cannot read statement: {e}"));
+    assert_eq!(next.charges_total, 5_000);
+    assert_eq!(next.total, 105_000);
+
+    assert!(matches!(
+        statements::settle_statement(&pool, settle("2025-03-20")).await,
+        Err(AppError::Conflict { .. })
+    ));
+}
+
+#[tokio::test]
+async fn installments_paid_from_the_bank_are_not_billed_on_the_card() {
+    let pool = migrated_pool().await;
+    let bank = bank_account(&pool, "Cuenta Corriente").await;
+    let card = credit_card(&pool, 20, 5).await;
+    let debt = notebook_debt(&pool, &card).await;
+    add(
+        &pool,
+        &card,
+        "cat_supermercado",
+        30_000,
+        "expense",
+        "2025-03-01",
+    )
+    .await;
+
+    // The installment due 2025-03-20 is paid straight from the bank
+    debts::mark_installment_paid(
+        &pool,
+        debt.installments[1].id.clone(),
+        PayInstallmentInput {
+            date: Some("2025-03-18".to_string()),
+            source_account_id: Some(bank.clone()),
+            ..category("cat_vivienda")
+        },
+    )
+    .await
+    .unwrap_or_else(|e| panic!("cannot pay installment: {e}"));
+
+    let statement = statements::get_statement(&pool, card.clone(), "2025-03-20".to_string())
+        .await
+        .unwrap_or_else(|e| panic!("cannot read statement: {e}"));
+    assert_eq!(statement.installments.len(), 1);
+    assert_eq!(statement.installments[0].paid_elsewhere, 100_000);
+    assert_eq!(statement.installments_total, 0);
+    assert_eq!(statement.outstanding, 30_000);
+
+    // Settling the statement only moves the purchase, not the installment a second time
+    let settled = statements::settle_statement(
+        &pool,
+        SettleStatementInput {
+            account_id: card.clone(),
+            closing_date: "2025-03-20".to_string(),
+            from_account_id: bank.clone(),
+            date: Some("2025-04-02".to_string()),
+            description: None,
+        },
+    )
+    .await
+    .unwrap_or_else(|e| panic!("cannot settle statement: {e}"));
+    assert_eq!(settled.paid_amount, 30_000);
+    assert_eq!(settled.outstanding, 0);
+    assert_eq!(balance(&pool, &bank).await, -130_000);
+    assert_eq!(balance(&pool, &card).await, 0);
+}
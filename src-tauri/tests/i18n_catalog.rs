@@ -0,0 +1,55 @@
+use std::collections::BTreeSet;
+
+use necronomics_lib::i18n::{self, catalog, month_name, t, t_with, Locale, LOCALES};
+
+/// Names of the `{name}` placeholders in a message.
+fn placeholders(message: &str) -> BTreeSet<&str> {
+    message
+        .split('{')
+        .skip(1)
+        .filter_map(|part| part.split_once('}').map(|(name, _)| name))
+        .collect()
+}
+
+#[test]
+fn every_locale_has_the_same_keys_and_placeholders() {
+    let spanish = catalog(Locale::Es);
+    assert!(!spanish.is_empty(), "the Spanish catalog failed to parse");
+
+    for code in LOCALES {
+        let locale = Locale::from_code(code).unwrap_or_else(|| panic!("unknown locale {code}"));
+        let messages = catalog(locale);
+
+        let missing: Vec<_> = spanish.keys().filter(|k| !messages.contains_key(*k)).collect();
+        let extra: Vec<_> = messages.keys().filter(|k| !spanish.contains_key(*k)).collect();
+        assert!(missing.is_empty(), "{code} is missing {missing:?}");
+        assert!(extra.is_empty(), "{code} has unknown keys {extra:?}");
+
+        for (key, message) in messages {
+            assert_eq!(
+                placeholders(message),
+                placeholders(&spanish[key]),
+                "placeholders of {key} differ in {code}"
+            );
+        }
+    }
+}
+
+#[test]
+fn messages_follow_the_active_locale() {
+    i18n::set_current(Locale::En);
+    assert_eq!(t("validation.amount_positive"), "The amount must be greater than 0");
+    assert_eq!(month_name(3), "March");
+    assert_eq!(
+        t_with("account.not_found", &[("id", &"acc1")]),
+        "Account not found: acc1"
+    );
+
+    i18n::set_current(Locale::Es);
+    assert_eq!(t("validation.amount_positive"), "El monto debe ser mayor a 0");
+    assert_eq!(month_name(3), "Marzo");
+    assert_eq!(month_name(13), "Desconocido");
+
+    // Unknown keys come back unchanged instead of failing
+    assert_eq!(t("no.such.key"), "no.such.key");
+}
@@ -0,0 +1,233 @@
+use necronomics_lib::commands::migrations;
+use necronomics_lib::{dry_run_migrations, rollback_last_migration, run_migrations};
+use sqlx::sqlite::SqlitePoolOptions;
+use sqlx::SqlitePool;
+
+/// Fresh in-memory database. A single connection, since every connection to `:memory:`
+/// opens a database of its own.
+async fn empty_pool() -> SqlitePool {
+    SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await
+        .unwrap_or_else(|e| panic!("cannot open an in-memory database: {e}"))
+}
+
+async fn table_exists(pool: &SqlitePool, name: &str) -> bool {
+    let row: Option<(String,)> =
+        sqlx::query_as("SELECT name FROM sqlite_master WHERE type = 'table' AND name = ?")
+            .bind(name)
+            .fetch_optional(pool)
+            .await
+            .unwrap_or_else(|e| panic!("cannot read the schema: {e}"));
+    row.is_some()
+}
+
+async fn column_exists(pool: &SqlitePool, table: &str, column: &str) -> bool {
+    let row: Option<(String,)> =
+        sqlx::query_as("SELECT name FROM pragma_table_info(?) WHERE name = ?")
+            .bind(table)
+            .bind(column)
+            .fetch_optional(pool)
+            .await
+            .unwrap_or_else(|e| panic!("cannot read the schema: {e}"));
+    row.is_some()
+}
+
+#[tokio::test]
+async fn dry_run_reports_pending_migrations_without_applying_them() {
+    let pool = empty_pool().await;
+
+    let pending = dry_run_migrations(&pool)
+        .await
+        .unwrap_or_else(|e| panic!("dry run failed: {e}"));
+    assert_eq!(pending.first(), Some(&"001_initial_schema"));
+    assert!(!table_exists(&pool, "_migrations").await);
+
+    let applied = run_migrations(&pool)
+        .await
+        .unwrap_or_else(|e| panic!("migrations failed: {e}"));
+    assert_eq!(applied, pending);
+
+    let pending = dry_run_migrations(&pool)
+        .await
+        .unwrap_or_else(|e| panic!("dry run failed: {e}"));
+    assert!(pending.is_empty());
+
+    // Running again is a no-op
+    let applied = run_migrations(&pool)
+        .await
+        .unwrap_or_else(|e| panic!("migrations failed: {e}"));
+    assert!(applied.is_empty());
+}
+
+#[tokio::test]
+async fn a_modified_migration_is_rejected() {
+    let pool = empty_pool().await;
+    run_migrations(&pool)
+        .await
+        .unwrap_or_else(|e| panic!("migrations failed: {e}"));
+
+    sqlx::query("UPDATE _migrations SET checksum = 'edited' WHERE name = '003_seed_categories'")
+        .execute(&pool)
+        .await
+        .unwrap_or_else(|e| panic!("update failed: {e}"));
+
+    let error = run_migrations(&pool)
+        .await
+        .err()
+        .unwrap_or_else(|| panic!("a modified migration was accepted"));
+    assert!(error.to_string().contains("003_seed_categories"));
+    assert!(dry_run_migrations(&pool).await.is_err());
+}
+
+#[tokio::test]
+async fn checksums_are_recorded_for_databases_migrated_before_them() {
+    let pool = empty_pool().await;
+    sqlx::raw_sql(
+        "CREATE TABLE _migrations (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            name TEXT NOT NULL UNIQUE,
+            applied_at TEXT NOT NULL DEFAULT (datetime('now'))
+        );
+        INSERT INTO _migrations (name) VALUES ('001_initial_schema');",
+    )
+    .execute(&pool)
+    .await
+    .unwrap_or_else(|e| panic!("setup failed: {e}"));
+
+    // 001 was never really run here, so 002 fails; the checksum column is added regardless
+    let pending = dry_run_migrations(&pool)
+        .await
+        .unwrap_or_else(|e| panic!("dry run failed: {e}"));
+    assert_eq!(pending.first(), Some(&"002_seed_currencies"));
+    assert!(run_migrations(&pool).await.is_err());
+
+    let (checksum,): (Option<String>,) =
+        sqlx::query_as("SELECT checksum FROM _migrations WHERE name = '001_initial_schema'")
+            .fetch_one(&pool)
+            .await
+            .unwrap_or_else(|e| panic!("cannot read the checksum: {e}"));
+    assert_eq!(checksum.map(|c| c.len()), Some(64));
+
+    // The failed migration left no row behind
+    let (count,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM _migrations")
+        .fetch_one(&pool)
+        .await
+        .unwrap_or_else(|e| panic!("count failed: {e}"));
+    assert_eq!(count, 1);
+}
+
+#[tokio::test]
+async fn reversible_migrations_roll_back_and_reapply() {
+    let pool = empty_pool().await;
+    run_migrations(&pool)
+        .await
+        .unwrap_or_else(|e| panic!("migrations failed: {e}"));
+    assert!(table_exists(&pool, "debt_status_history").await);
+
+    let reverted = rollback_last_migration(&pool)
+        .await
+        .unwrap_or_else(|e| panic!("rollback failed: {e}"));
+    assert_eq!(reverted, Some("018_installment_partial_payments"));
+    assert!(!table_exists(&pool, "installment_payments").await);
+
+    let reverted = rollback_last_migration(&pool)
+        .await
+        .unwrap_or_else(|e| panic!("rollback failed: {e}"));
+    assert_eq!(reverted, Some("017_debt_prepayments"));
+    assert!(!table_exists(&pool, "debt_prepayments").await);
+
+    let reverted = rollback_last_migration(&pool)
+        .await
+        .unwrap_or_else(|e| panic!("rollback failed: {e}"));
+    assert_eq!(reverted, Some("016_installment_payments"));
+    assert!(!column_exists(&pool, "installments", "paid_amount").await);
+    assert!(column_exists(&pool, "installments", "interest").await);
+
+    let reverted = rollback_last_migration(&pool)
+        .await
+        .unwrap_or_else(|e| panic!("rollback failed: {e}"));
+    assert_eq!(reverted, Some("015_debt_lifecycle"));
+    assert!(!table_exists(&pool, "debt_status_history").await);
+    assert!(!column_exists(&pool, "debts", "status").await);
+
+    let reverted = rollback_last_migration(&pool)
+        .await
+        .unwrap_or_else(|e| panic!("rollback failed: {e}"));
+    assert_eq!(reverted, Some("014_debt_amortization"));
+    assert!(!column_exists(&pool, "installments", "interest").await);
+    assert!(!column_exists(&pool, "debts", "rate_type").await);
+
+    let reverted = rollback_last_migration(&pool)
+        .await
+        .unwrap_or_else(|e| panic!("rollback failed: {e}"));
+    assert_eq!(reverted, Some("013_credit_card_statements"));
+    assert!(!table_exists(&pool, "statement_payments").await);
+
+    let pending = dry_run_migrations(&pool)
+        .await
+        .unwrap_or_else(|e| panic!("dry run failed: {e}"));
+    assert_eq!(
+        pending,
+        vec![
+            "013_credit_card_statements",
+            "014_debt_amortization",
+            "015_debt_lifecycle",
+            "016_installment_payments",
+            "017_debt_prepayments",
+            "018_installment_partial_payments"
+        ]
+    );
+
+    run_migrations(&pool)
+        .await
+        .unwrap_or_else(|e| panic!("migrations failed: {e}"));
+    assert!(table_exists(&pool, "statement_payments").await);
+    assert!(column_exists(&pool, "installments", "interest").await);
+    assert!(table_exists(&pool, "debt_status_history").await);
+    assert!(column_exists(&pool, "installments", "paid_amount").await);
+    assert!(table_exists(&pool, "debt_prepayments").await);
+    assert!(table_exists(&pool, "installment_payments").await);
+}
+
+#[tokio::test]
+async fn migration_commands_report_and_roll_back_in_development() {
+    let pool = empty_pool().await;
+    run_migrations(&pool)
+        .await
+        .unwrap_or_else(|e| panic!("migrations failed: {e}"));
+
+    let reverted = migrations::rollback_last_migration(&pool)
+        .await
+        .unwrap_or_else(|e| panic!("rollback failed: {e}"));
+    assert_eq!(
+        reverted.as_deref(),
+        Some("018_installment_partial_payments")
+    );
+    let pending = migrations::dry_run_migrations(&pool)
+        .await
+        .unwrap_or_else(|e| panic!("dry run failed: {e}"));
+    assert_eq!(pending, vec!["018_installment_partial_payments"]);
+}
+
+#[tokio::test]
+async fn migrations_without_down_script_cannot_roll_back() {
+    let pool = empty_pool().await;
+    run_migrations(&pool)
+        .await
+        .unwrap_or_else(|e| panic!("migrations failed: {e}"));
+
+    for _ in 0..9 {
+        rollback_last_migration(&pool)
+            .await
+            .unwrap_or_else(|e| panic!("rollback failed: {e}"));
+    }
+    assert!(!table_exists(&pool, "budgets").await);
+
+    let error = rollback_last_migration(&pool)
+        .await
+        .err()
+        .unwrap_or_else(|| panic!("rolled back a migration without down script"));
+    assert!(error.to_string().contains("009_add_transaction_fitid"));
+}
@@ -0,0 +1,87 @@
+use necronomics_lib::ofx::{parse_ofx, OfxTransaction};
+
+fn fixture(name: &str) -> String {
+    let path = format!("{}/tests/fixtures/ofx/{name}", env!("CARGO_MANIFEST_DIR"));
+    std::fs::read_to_string(&path).unwrap_or_else(|e| panic!("cannot read {path}: {e}"))
+}
+
+fn find<'a>(transactions: &'a [OfxTransaction], fitid: &str) -> &'a OfxTransaction {
+    transactions
+        .iter()
+        .find(|t| t.fitid == fitid)
+        .unwrap_or_else(|| panic!("missing FITID {fitid}"))
+}
+
+#[test]
+fn parses_sgml_v1_bank_statement() -> Result<(), String> {
+    let statement = parse_ofx(&fixture("bank_sgml_v102.ofx"))?;
+
+    assert_eq!(statement.currency.as_deref(), Some("CLP"));
+    assert_eq!(statement.account_number.as_deref(), Some("00-123-45678-09"));
+    assert_eq!(statement.transactions.len(), 3);
+
+    let salary = find(&statement.transactions, "202502010001");
+    assert_eq!(salary.date, "2025-02-01");
+    assert_eq!(salary.amount, "1250000");
+    assert_eq!(salary.trn_type, "CREDIT");
+    assert_eq!(salary.description(), "REMUNERACION FEBRERO");
+
+    let groceries = find(&statement.transactions, "202502030002");
+    assert_eq!(groceries.date, "2025-02-03");
+    assert_eq!(groceries.amount, "-45990");
+    assert_eq!(groceries.description(), "SUPERMERCADO LIDER & CIA");
+    assert_eq!(groceries.memo.as_deref(), Some("COMPRA DEBITO"));
+    Ok(())
+}
+
+#[test]
+fn parses_xml_v2_card_statement() -> Result<(), String> {
+    let statement = parse_ofx(&fixture("card_xml_v220.qfx"))?;
+
+    assert_eq!(statement.currency.as_deref(), Some("USD"));
+    assert_eq!(
+        statement.account_number.as_deref(),
+        Some("4111XXXXXXXX1111")
+    );
+    assert_eq!(statement.transactions.len(), 3);
+
+    let coffee = find(&statement.transactions, "CC-20250302-01");
+    assert_eq!(coffee.description(), "Coffee <Downtown>");
+    assert_eq!(coffee.amount, "-12.50");
+
+    // Dates with a time and a timezone suffix keep only the calendar day
+    let subscription = find(&statement.transactions, "CC-20250304-02");
+    assert_eq!(subscription.date, "2025-03-04");
+    assert_eq!(subscription.name, None);
+    assert_eq!(subscription.description(), "Online subscription");
+
+    let payment = find(&statement.transactions, "CC-20250305-03");
+    assert_eq!(payment.trn_type, "CREDIT");
+    assert_eq!(payment.amount, "100.00");
+    Ok(())
+}
+
+#[test]
+fn sgml_transactions_without_closing_tags() -> Result<(), String> {
+    let content = "OFXHEADER:100\n<OFX><BANKTRANLIST>\
+        <STMTTRN><TRNTYPE>DEBIT<DTPOSTED>20250110<TRNAMT>-1000<FITID>A1<NAME>Uno\
+        <STMTTRN><TRNTYPE>DEBIT<DTPOSTED>20250111<TRNAMT>-2000<FITID>A2<NAME>Dos\
+        </BANKTRANLIST></OFX>";
+    let statement = parse_ofx(content)?;
+
+    let fitids: Vec<&str> = statement
+        .transactions
+        .iter()
+        .map(|t| t.fitid.as_str())
+        .collect();
+    assert_eq!(fitids, ["A1", "A2"]);
+    assert_eq!(statement.transactions[1].description(), "Dos");
+    Ok(())
+}
+
+#[test]
+fn rejects_invalid_documents() {
+    assert!(parse_ofx("date,amount\n2025-01-01,100").is_err());
+    assert!(parse_ofx("<OFX><STMTTRN><DTPOSTED>20250101<TRNAMT>1</STMTTRN></OFX>").is_err());
+    assert!(parse_ofx("<OFX><STMTTRN><FITID>X<DTPOSTED>2025<TRNAMT>1</STMTTRN></OFX>").is_err());
+}
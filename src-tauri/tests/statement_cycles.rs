@@ -0,0 +1,77 @@
+use chrono::NaiveDate;
+use necronomics_lib::statements::{CardSchedule, Cycle};
+
+fn date(value: &str) -> NaiveDate {
+    NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .unwrap_or_else(|e| panic!("bad test date {value}: {e}"))
+}
+
+fn cycle(start: &str, closing: &str, due: &str) -> Cycle {
+    Cycle {
+        start: date(start),
+        closing: date(closing),
+        due: date(due),
+    }
+}
+
+#[test]
+fn charges_fall_in_the_cycle_closing_on_or_after_them() {
+    let card = CardSchedule {
+        billing_day: 20,
+        payment_due_day: Some(5),
+    };
+
+    // The closing day itself still belongs to the cycle
+    assert_eq!(
+        card.cycle_containing(date("2025-03-20")),
+        Some(cycle("2025-02-21", "2025-03-20", "2025-04-05"))
+    );
+    assert_eq!(
+        card.cycle_containing(date("2025-03-21")),
+        Some(cycle("2025-03-21", "2025-04-20", "2025-05-05"))
+    );
+    assert_eq!(
+        card.cycle_containing(date("2025-12-25")),
+        Some(cycle("2025-12-21", "2026-01-20", "2026-02-05"))
+    );
+
+    // A due day later in the month is paid in the same month
+    let card = CardSchedule {
+        billing_day: 10,
+        payment_due_day: Some(25),
+    };
+    assert_eq!(
+        card.cycle_containing(date("2025-01-10")),
+        Some(cycle("2024-12-11", "2025-01-10", "2025-01-25"))
+    );
+}
+
+#[test]
+fn closing_days_past_the_end_of_the_month_are_clamped() {
+    let card = CardSchedule {
+        billing_day: 31,
+        payment_due_day: None,
+    };
+
+    assert!(card.is_closing_date(date("2025-02-28")));
+    assert!(!card.is_closing_date(date("2025-02-27")));
+    assert!(!card.is_closing_date(date("2025-03-30")));
+
+    // Without a due day, statements are due a fixed number of days after closing
+    let february = card.cycle_containing(date("2025-02-10"));
+    assert_eq!(
+        february,
+        Some(cycle("2025-02-01", "2025-02-28", "2025-03-15"))
+    );
+
+    let card = CardSchedule {
+        billing_day: 30,
+        payment_due_day: Some(30),
+    };
+    let march = card.cycle_closing_on(date("2025-03-30"));
+    assert_eq!(march, Some(cycle("2025-03-01", "2025-03-30", "2025-04-30")));
+    assert_eq!(
+        march.and_then(|m| card.previous(m)),
+        Some(cycle("2025-01-31", "2025-02-28", "2025-03-30"))
+    );
+}
@@ -1,5 +1,6 @@
 import { useState } from "react";
 import { useAccountStore } from "../../stores/accountStore";
+import { errorMessage } from "../../lib/tauri";
 import type { Account } from "../../types";
 
 interface ConfirmDeleteModalProps {
@@ -31,7 +32,7 @@ export const ConfirmDeleteModal = ({
       await archiveAccount(account.id);
       onArchived();
     } catch (e) {
-      setDeleteError(String(e));
+      setDeleteError(errorMessage(e));
     } finally {
       setIsArchiving(false);
     }
@@ -44,7 +45,7 @@ export const ConfirmDeleteModal = ({
       await deleteAccount(account.id);
       onDeleted();
     } catch (e) {
-      setDeleteError(String(e));
+      setDeleteError(errorMessage(e));
     } finally {
       setIsDeleting(false);
     }
@@ -4,6 +4,7 @@ import { zodResolver } from "@hookform/resolvers/zod";
 import { z } from "zod";
 import { useCategoryStore } from "../../stores/categoryStore";
 import { IconPicker } from "./IconPicker";
+import { errorMessage } from "../../lib/tauri";
 import type { Category, CategoryType, CreateCategoryInput, UpdateCategoryInput } from "../../types";
 
 const categorySchema = z.object({
@@ -134,7 +135,7 @@ export const CategoryFormModal = ({
         onSuccess("Categoria creada");
       }
     } catch (e) {
-      setFormError(String(e));
+      setFormError(errorMessage(e));
     }
   };
 
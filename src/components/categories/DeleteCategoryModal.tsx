@@ -1,5 +1,6 @@
 import { useState } from "react";
 import { useCategoryStore } from "../../stores/categoryStore";
+import { errorMessage } from "../../lib/tauri";
 import type { Category } from "../../types";
 
 interface DeleteCategoryModalProps {
@@ -32,7 +33,7 @@ export const DeleteCategoryModal = ({
       await deleteCategory(category.id);
       onSuccess("Categoria eliminada");
     } catch (e) {
-      setError(String(e));
+      setError(errorMessage(e));
     } finally {
       setIsDeleting(false);
     }
@@ -42,7 +42,7 @@ export const IncomeExpensePanel = ({ data, baseCurrencyCode }: IncomeExpensePane
           fontFamily: '"Share Tech Mono", "Courier New", monospace',
         }}
       >
-        {data.monthName} {data.year}
+        {data.period.label}
       </p>
 
       {/* Income and expense side by side */}
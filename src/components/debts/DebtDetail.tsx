@@ -1,5 +1,5 @@
 import { useEffect, useState, useCallback } from "react";
-import { debtApi } from "../../lib/tauri";
+import { debtApi, errorMessage } from "../../lib/tauri";
 import { formatCurrency } from "../../lib/formatters";
 import { InstallmentRow } from "./InstallmentRow";
 import type { DebtWithInstallments, Installment } from "../../types";
@@ -31,7 +31,7 @@ export const DebtDetail = ({
       const result = await debtApi.getDetail(debtId);
       setDetail(result);
     } catch (e) {
-      setError(String(e));
+      setError(errorMessage(e));
     } finally {
       setIsLoading(false);
     }
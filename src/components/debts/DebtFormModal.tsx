@@ -5,6 +5,7 @@ import { z } from "zod";
 import { useDebtStore } from "../../stores/debtStore";
 import { useAccountStore } from "../../stores/accountStore";
 import { toMinorUnits, fromMinorUnits } from "../../lib/formatters";
+import { errorMessage } from "../../lib/tauri";
 import type { Debt } from "../../types";
 
 const debtSchema = z.object({
@@ -154,7 +155,7 @@ export const DebtFormModal = ({
       }
       onClose();
     } catch (e) {
-      setFormError(String(e));
+      setFormError(errorMessage(e));
     }
   };
 
@@ -8,7 +8,8 @@ interface InstallmentRowProps {
 }
 
 const getDisplayStatus = (installment: Installment): InstallmentDisplayStatus => {
-  if (installment.status === "paid") return "pagado";
+  // A partial installment is still owed, so it stays payable
+  if (installment.status === "paid" || installment.status === "paid_late") return "pagado";
   const today = new Date().toISOString().split("T")[0];
   if (installment.dueDate < today) return "vencido";
   return "pendiente";
@@ -63,7 +64,7 @@ export const InstallmentRow = ({
         <p className="text-sm" style={{ color: "#c4d4a0" }}>
           {formatDate(installment.dueDate)}
         </p>
-        {installment.status === "paid" && installment.actualPaymentDate && (
+        {installment.status !== "pending" && installment.actualPaymentDate && (
           <p className="text-xs" style={{ color: "#6b7c3e" }}>
             Pagada: {formatDate(installment.actualPaymentDate)}
           </p>
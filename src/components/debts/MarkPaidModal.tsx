@@ -1,6 +1,7 @@
 import { useState, useMemo } from "react";
 import { useCategoryStore } from "../../stores/categoryStore";
 import { formatCurrency } from "../../lib/formatters";
+import { errorMessage } from "../../lib/tauri";
 import type { Installment } from "../../types";
 
 interface MarkPaidModalProps {
@@ -52,7 +53,7 @@ export const MarkPaidModal = ({
       setSelectedCategoryId("");
       onClose();
     } catch (e) {
-      setError(String(e));
+      setError(errorMessage(e));
     } finally {
       setIsProcessing(false);
     }
@@ -2,6 +2,7 @@ import { useState, useMemo } from "react";
 import { useTransactionStore } from "../../stores/transactionStore";
 import { useAccountStore } from "../../stores/accountStore";
 import { formatCurrency } from "../../lib/formatters";
+import { errorMessage } from "../../lib/tauri";
 import type { Transaction } from "../../types";
 
 interface DeleteTransactionModalProps {
@@ -39,7 +40,7 @@ export const DeleteTransactionModal = ({
       await deleteTransaction(transaction.id);
       onSuccess("Transaccion eliminada");
     } catch (e) {
-      setError(String(e));
+      setError(errorMessage(e));
     } finally {
       setIsDeleting(false);
     }
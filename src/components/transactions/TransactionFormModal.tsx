@@ -6,6 +6,7 @@ import { useTransactionStore } from "../../stores/transactionStore";
 import { useAccountStore } from "../../stores/accountStore";
 import { useCategoryStore } from "../../stores/categoryStore";
 import { toMinorUnits, fromMinorUnits } from "../../lib/formatters";
+import { errorMessage } from "../../lib/tauri";
 import type {
   Transaction,
   TransactionType,
@@ -185,7 +186,7 @@ export const TransactionFormModal = ({
         onSuccess("Transaccion creada");
       }
     } catch (e) {
-      setFormError(String(e));
+      setFormError(errorMessage(e));
     }
   };
 
@@ -1,29 +1,81 @@
 import { invoke } from "@tauri-apps/api/core";
 import type {
+  AppError,
   Account,
   CreateAccountInput,
   UpdateAccountInput,
   Currency,
+  ExchangeRate,
+  ConvertedAmount,
   Category,
   CreateCategoryInput,
   UpdateCategoryInput,
   Transaction,
+  TransactionWithTags,
+  DescriptionSuggestion,
+  Tag,
   CreateTransactionInput,
   UpdateTransactionInput,
   TransactionFilters,
   PaginatedResult,
   BalanceSummary,
+  BalanceGranularity,
+  BalanceHistory,
+  RecurringTransaction,
+  CreateRecurringInput,
+  UpdateRecurringInput,
+  RecurringRunSummary,
+  Transfer,
+  CreateTransferInput,
+  UpdateTransferInput,
+  CsvImportProfile,
+  CsvImportProfileInput,
+  CsvImportPreview,
+  CsvImportRow,
+  CsvImportResult,
+  OfxImportPreview,
+  OfxImportResult,
+  Budget,
+  CreateBudgetInput,
+  UpdateBudgetInput,
+  BudgetMonthStatus,
   Debt,
   Installment,
   DebtWithInstallments,
   CreateDebtInput,
   UpdateDebtInput,
+  PayInstallmentInput,
+  PrepayDebtInput,
+  DebtStatus,
+  DebtStatusChange,
   DebtFilter,
   CreditUtilization,
   MonthlyProjection,
   DashboardData,
+  AppSettings,
+  UpdateSettingsInput,
+  BackupSnapshot,
+  IntegrityReport,
+  Statement,
+  SettleStatementInput,
+  DashboardPeriod,
+  MonthlyComparison,
+  MonthlyComparisonFilter,
+  ExportSummary,
+  ImportSummary,
+  ImportMode,
 } from "../types";
 
+export const isAppError = (e: unknown): e is AppError =>
+  typeof e === "object" && e !== null && "code" in e && "message" in e;
+
+// Commands reject with an AppError object or, for those not migrated yet, a plain string
+export const errorMessage = (e: unknown): string => {
+  if (isAppError(e)) return e.message;
+  if (e instanceof Error) return e.message;
+  return String(e);
+};
+
 export const accountApi = {
   list: (): Promise<Account[]> => invoke("list_accounts"),
 
@@ -36,6 +88,7 @@ export const accountApi = {
       currencyId: input.currencyId,
       creditLimit: input.creditLimit ?? null,
       billingDay: input.billingDay ?? null,
+      paymentDueDay: input.paymentDueDay ?? null,
     }),
 
   update: (id: string, input: UpdateAccountInput): Promise<Account> =>
@@ -50,6 +103,31 @@ export const currencyApi = {
   list: (): Promise<Currency[]> => invoke("list_currencies"),
 };
 
+export const exchangeRateApi = {
+  set: (
+    fromCurrencyId: string,
+    toCurrencyId: string,
+    rate: number,
+    date: string
+  ): Promise<ExchangeRate> =>
+    invoke("set_exchange_rate", { fromCurrencyId, toCurrencyId, rate, date }),
+
+  getLatest: (fromCurrencyId: string, toCurrencyId: string): Promise<ExchangeRate | null> =>
+    invoke("get_exchange_rate", { fromCurrencyId, toCurrencyId }),
+
+  listHistory: (fromCurrencyId: string, toCurrencyId: string): Promise<ExchangeRate[]> =>
+    invoke("list_exchange_rates", { fromCurrencyId, toCurrencyId }),
+
+  delete: (id: string): Promise<void> => invoke("delete_exchange_rate", { id }),
+
+  convert: (
+    amount: number,
+    fromCurrencyId: string,
+    toCurrencyId: string
+  ): Promise<ConvertedAmount> =>
+    invoke("convert_amount", { amount, fromCurrencyId, toCurrencyId }),
+};
+
 export const categoryApi = {
   list: (): Promise<Category[]> => invoke("list_categories"),
 
@@ -70,7 +148,7 @@ export const categoryApi = {
 };
 
 export const transactionApi = {
-  list: (filters: TransactionFilters): Promise<PaginatedResult<Transaction>> =>
+  list: (filters: TransactionFilters): Promise<PaginatedResult<TransactionWithTags>> =>
     invoke("list_transactions", { filter: filters }),
 
   create: (input: CreateTransactionInput): Promise<Transaction> =>
@@ -90,6 +168,98 @@ export const transactionApi = {
 
   getBalanceSummary: (baseCurrencyId?: string): Promise<BalanceSummary> =>
     invoke("get_balance_summary", { baseCurrencyId: baseCurrencyId ?? null }),
+
+  getSuggestions: (prefix: string, limit?: number): Promise<DescriptionSuggestion[]> =>
+    invoke("get_suggestions", { prefix, limit: limit ?? null }),
+};
+
+export const tagApi = {
+  list: (): Promise<Tag[]> => invoke("list_tags"),
+
+  create: (name: string, color?: string): Promise<Tag> =>
+    invoke("create_tag", { name, color: color ?? null }),
+
+  update: (id: string, input: { name?: string; color?: string }): Promise<Tag> =>
+    invoke("update_tag", { id, ...input }),
+
+  delete: (id: string): Promise<void> => invoke("delete_tag", { id }),
+
+  setForTransaction: (transactionId: string, tagIds: string[]): Promise<Tag[]> =>
+    invoke("set_transaction_tags", { transactionId, tagIds }),
+};
+
+export const recurringApi = {
+  list: (includeInactive?: boolean): Promise<RecurringTransaction[]> =>
+    invoke("list_recurring", { includeInactive: includeInactive ?? null }),
+
+  create: (input: CreateRecurringInput): Promise<RecurringTransaction> =>
+    invoke("create_recurring", { input }),
+
+  update: (id: string, input: UpdateRecurringInput): Promise<RecurringTransaction> =>
+    invoke("update_recurring", { id, input }),
+
+  delete: (id: string): Promise<void> => invoke("delete_recurring", { id }),
+
+  process: (): Promise<RecurringRunSummary> => invoke("process_recurring"),
+
+  takeStartupSummary: (): Promise<RecurringRunSummary | null> =>
+    invoke("take_startup_recurring_summary"),
+};
+
+export const transferApi = {
+  list: (accountId?: string): Promise<Transfer[]> =>
+    invoke("list_transfers", { accountId: accountId ?? null }),
+
+  create: (input: CreateTransferInput): Promise<Transfer> => invoke("create_transfer", { input }),
+
+  update: (id: string, input: UpdateTransferInput): Promise<Transfer> =>
+    invoke("update_transfer", { id, input }),
+
+  delete: (id: string): Promise<void> => invoke("delete_transfer", { id }),
+};
+
+export const csvImportApi = {
+  listProfiles: (): Promise<CsvImportProfile[]> => invoke("list_csv_import_profiles"),
+
+  createProfile: (input: CsvImportProfileInput): Promise<CsvImportProfile> =>
+    invoke("create_csv_import_profile", { input }),
+
+  updateProfile: (id: string, input: CsvImportProfileInput): Promise<CsvImportProfile> =>
+    invoke("update_csv_import_profile", { id, input }),
+
+  deleteProfile: (id: string): Promise<void> => invoke("delete_csv_import_profile", { id }),
+
+  preview: (accountId: string, profileId: string, path: string): Promise<CsvImportPreview> =>
+    invoke("preview_csv_import", { accountId, profileId, path }),
+
+  commit: (accountId: string, rows: CsvImportRow[]): Promise<CsvImportResult> =>
+    invoke("commit_csv_import", { accountId, rows }),
+};
+
+export const ofxImportApi = {
+  preview: (accountId: string, path: string): Promise<OfxImportPreview> =>
+    invoke("preview_ofx_import", { accountId, path }),
+
+  import: (
+    accountId: string,
+    path: string,
+    expenseCategoryId: string,
+    incomeCategoryId: string,
+  ): Promise<OfxImportResult> =>
+    invoke("import_ofx", { accountId, path, expenseCategoryId, incomeCategoryId }),
+};
+
+export const budgetApi = {
+  list: (): Promise<Budget[]> => invoke("list_budgets"),
+
+  create: (input: CreateBudgetInput): Promise<Budget> => invoke("create_budget", { input }),
+
+  update: (id: string, input: UpdateBudgetInput): Promise<Budget> =>
+    invoke("update_budget", { id, input }),
+
+  delete: (id: string): Promise<void> => invoke("delete_budget", { id }),
+
+  getStatus: (month: string): Promise<BudgetMonthStatus> => invoke("get_budget_status", { month }),
 };
 
 export const debtApi = {
@@ -105,13 +275,92 @@ export const debtApi = {
 
   delete: (id: string): Promise<void> => invoke("delete_debt", { id }),
 
-  markInstallmentPaid: (installmentId: string, categoryId: string): Promise<Installment> =>
-    invoke("mark_installment_paid", { installmentId, categoryId }),
+  // Only defaulted and refinanced, or defaulted back to active; payments drive the rest
+  changeStatus: (id: string, status: DebtStatus, note?: string): Promise<Debt> =>
+    invoke("change_debt_status", { id, status, note: note ?? null }),
+
+  getStatusHistory: (id: string): Promise<DebtStatusChange[]> =>
+    invoke("list_debt_status_history", { id }),
+
+  // Without a category, the default installment category from settings is used
+  markInstallmentPaid: (
+    installmentId: string,
+    input: PayInstallmentInput = {},
+  ): Promise<Installment> => invoke("mark_installment_paid", { installmentId, input }),
+
+  // Deletes the payment transaction and sets the installment back to pending
+  unmarkInstallmentPaid: (installmentId: string): Promise<Installment> =>
+    invoke("unmark_installment_paid", { installmentId }),
+
+  prepay: (input: PrepayDebtInput): Promise<DebtWithInstallments> =>
+    invoke("prepay_debt", { input }),
+
+  payOff: (debtId: string, categoryId?: string): Promise<DebtWithInstallments> =>
+    invoke("pay_off_debt", { debtId, categoryId: categoryId ?? null }),
 
   getCreditUtilization: (): Promise<CreditUtilization[]> => invoke("get_credit_utilization"),
   getPaymentProjections: (): Promise<MonthlyProjection[]> => invoke("get_payment_projections"),
 };
 
 export const dashboardApi = {
-  getData: (): Promise<DashboardData> => invoke("get_dashboard_data"),
+  getData: (period?: DashboardPeriod): Promise<DashboardData> =>
+    invoke("get_dashboard_data", { period: period ?? null }),
+  getMonthlyComparison: (
+    months?: number,
+    filter?: MonthlyComparisonFilter,
+  ): Promise<MonthlyComparison> =>
+    invoke("get_monthly_comparison", { months: months ?? null, filter: filter ?? null }),
+  getBalanceHistory: (
+    dateFrom: string,
+    dateTo: string,
+    granularity: BalanceGranularity,
+  ): Promise<BalanceHistory> =>
+    invoke("get_balance_history", { dateFrom, dateTo, granularity }),
+};
+
+export const exportApi = {
+  exportData: (path: string): Promise<ExportSummary> => invoke("export_data", { path }),
+
+  importData: (path: string, mode: ImportMode): Promise<ImportSummary> =>
+    invoke("import_data", { path, mode }),
+};
+
+export const settingsApi = {
+  get: (): Promise<AppSettings> => invoke("get_settings"),
+  update: (input: UpdateSettingsInput): Promise<AppSettings> =>
+    invoke("update_settings", { input }),
+};
+
+export const backupApi = {
+  list: (): Promise<BackupSnapshot[]> => invoke("list_backups"),
+
+  // Reload every store afterwards: all data comes from the restored snapshot
+  restore: (fileName: string): Promise<void> => invoke("restore_backup", { fileName }),
+};
+
+export const statementApi = {
+  // Newest first, starting with the open cycle; count defaults to 6 (at most 24)
+  list: (accountId: string, count?: number): Promise<Statement[]> =>
+    invoke("list_statements", { accountId, count: count ?? null }),
+
+  get: (accountId: string, closingDate: string): Promise<Statement> =>
+    invoke("get_statement", { accountId, closingDate }),
+
+  settle: (input: SettleStatementInput): Promise<Statement> =>
+    invoke("settle_statement", { input }),
+};
+
+export const integrityApi = {
+  verify: (): Promise<IntegrityReport> => invoke("verify_integrity"),
+
+  // Returns what was found before fixing it; reload balances and debts afterwards
+  repair: (): Promise<IntegrityReport> => invoke("repair_integrity"),
+};
+
+export const migrationApi = {
+  // Names of the migrations the next startup would apply
+  dryRun: (): Promise<string[]> => invoke("dry_run_migrations"),
+
+  // Development builds only; returns the migration undone, null when none was applied
+  rollbackLast: (): Promise<string | null> => invoke("rollback_last_migration"),
 };
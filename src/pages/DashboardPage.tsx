@@ -1,5 +1,5 @@
 import { useEffect, useState, useCallback } from "react";
-import { dashboardApi } from "../lib/tauri";
+import { dashboardApi, errorMessage } from "../lib/tauri";
 import { HeroBalance } from "../components/dashboard/HeroBalance";
 import { IncomeExpensePanel } from "../components/dashboard/IncomeExpensePanel";
 import { TopCategoriesPanel } from "../components/dashboard/TopCategoriesPanel";
@@ -18,7 +18,7 @@ export const DashboardPage = () => {
       const result = await dashboardApi.getData();
       setData(result);
     } catch (err) {
-      setError(err instanceof Error ? err.message : String(err));
+      setError(errorMessage(err));
     } finally {
       setIsLoading(false);
     }
@@ -7,6 +7,7 @@ import { DebtFormModal } from "../components/debts/DebtFormModal";
 import { MarkPaidModal } from "../components/debts/MarkPaidModal";
 import { CreditUtilization } from "../components/debts/CreditUtilization";
 import { ProjectionTable } from "../components/debts/ProjectionTable";
+import { errorMessage } from "../lib/tauri";
 import type { Debt, Installment } from "../types";
 
 export const DebtsPage = () => {
@@ -110,7 +111,7 @@ export const DebtsPage = () => {
         await fetchProjections();
         setToast("Deuda eliminada");
       } catch (e) {
-        setToast(`Error: ${String(e)}`);
+        setToast(`Error: ${errorMessage(e)}`);
       }
     },
     [deleteDebt, expandedDebtId, fetchCreditUtilizations, fetchProjections]
@@ -127,7 +128,7 @@ export const DebtsPage = () => {
 
   const handleMarkPaidConfirm = async (categoryId: string) => {
     if (!markPaidInstallment) return;
-    await markInstallmentPaid(markPaidInstallment.id, categoryId);
+    await markInstallmentPaid(markPaidInstallment.id, { categoryId });
     await fetchCreditUtilizations();
     await fetchProjections();
     setToast("Cuota marcada como pagada");
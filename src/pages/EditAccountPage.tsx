@@ -1,7 +1,7 @@
 import { useEffect, useState } from "react";
 import { useParams, useNavigate } from "react-router";
 import { useAccountStore } from "../stores/accountStore";
-import { accountApi } from "../lib/tauri";
+import { accountApi, errorMessage } from "../lib/tauri";
 import { AccountForm } from "../components/accounts/AccountForm";
 import type { Account } from "../types";
 
@@ -34,7 +34,7 @@ export const EditAccountPage = () => {
         const fetched = await accountApi.get(id);
         setAccount(fetched);
       } catch (e) {
-        setError(String(e));
+        setError(errorMessage(e));
       } finally {
         setLoading(false);
       }
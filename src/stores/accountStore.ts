@@ -1,5 +1,6 @@
 import { create } from 'zustand';
 import { accountApi, currencyApi } from '../lib/tauri';
+import { errorMessage } from "../lib/tauri";
 import type { Account, Currency, CreateAccountInput, UpdateAccountInput } from '../types';
 
 interface AccountState {
@@ -27,7 +28,7 @@ export const useAccountStore = create<AccountState>((set, get) => ({
       const accounts = await accountApi.list();
       set({ accounts, isLoading: false });
     } catch (e) {
-      set({ error: String(e), isLoading: false });
+      set({ error: errorMessage(e), isLoading: false });
     }
   },
 
@@ -36,7 +37,7 @@ export const useAccountStore = create<AccountState>((set, get) => ({
       const currencies = await currencyApi.list();
       set({ currencies });
     } catch (e) {
-      set({ error: String(e) });
+      set({ error: errorMessage(e) });
     }
   },
 
@@ -1,5 +1,5 @@
 import { create } from "zustand";
-import { categoryApi } from "../lib/tauri";
+import { categoryApi, errorMessage } from "../lib/tauri";
 import type { Category, CreateCategoryInput, UpdateCategoryInput } from "../types";
 
 interface CategoryState {
@@ -23,7 +23,7 @@ export const useCategoryStore = create<CategoryState>((set, get) => ({
       const categories = await categoryApi.list();
       set({ categories, isLoading: false });
     } catch (e) {
-      set({ error: String(e), isLoading: false });
+      set({ error: errorMessage(e), isLoading: false });
     }
   },
 
@@ -1,9 +1,10 @@
 import { create } from "zustand";
-import { debtApi } from "../lib/tauri";
+import { debtApi, errorMessage } from "../lib/tauri";
 import type {
   Debt,
   DebtWithInstallments,
   Installment,
+  PayInstallmentInput,
   CreateDebtInput,
   UpdateDebtInput,
   DebtFilter,
@@ -40,7 +41,7 @@ interface DebtState {
   getDebtDetail: (id: string) => Promise<DebtWithInstallments>;
   markInstallmentPaid: (
     installmentId: string,
-    categoryId: string,
+    input: PayInstallmentInput,
   ) => Promise<Installment>;
   fetchCreditUtilizations: () => Promise<void>;
   fetchProjections: () => Promise<void>;
@@ -66,7 +67,7 @@ export const useDebtStore = create<DebtState>((set, get) => ({
       const debts = await debtApi.list(apiFilter);
       set({ debts, isLoading: false });
     } catch (e) {
-      set({ error: String(e), isLoading: false });
+      set({ error: errorMessage(e), isLoading: false });
     }
   },
 
@@ -101,11 +102,8 @@ export const useDebtStore = create<DebtState>((set, get) => ({
     return await debtApi.getDetail(id);
   },
 
-  markInstallmentPaid: async (installmentId, categoryId) => {
-    const result = await debtApi.markInstallmentPaid(
-      installmentId,
-      categoryId,
-    );
+  markInstallmentPaid: async (installmentId, input) => {
+    const result = await debtApi.markInstallmentPaid(installmentId, input);
     await get().fetchDebts();
     return result;
   },
@@ -1,5 +1,5 @@
 import { create } from "zustand";
-import { transactionApi } from "../lib/tauri";
+import { transactionApi, errorMessage } from "../lib/tauri";
 import type {
   Transaction,
   TransactionType,
@@ -96,7 +96,7 @@ export const useTransactionStore = create<TransactionState>((set, get) => ({
         isLoading: false,
       });
     } catch (e) {
-      set({ error: String(e), isLoading: false });
+      set({ error: errorMessage(e), isLoading: false });
     }
   },
 
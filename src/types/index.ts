@@ -8,6 +8,7 @@ export interface Account {
   balance: number; // integer minor units (never float)
   creditLimit: number | null;
   billingDay: number | null;
+  paymentDueDay: number | null; // credit cards: day statements are due
   isActive: number; // 1 or 0 (SQLite integer boolean)
   createdAt: string;
 }
@@ -18,6 +19,7 @@ export interface CreateAccountInput {
   currencyId: string;
   creditLimit?: number;
   billingDay?: number;
+  paymentDueDay?: number;
 }
 
 export interface UpdateAccountInput {
@@ -25,6 +27,7 @@ export interface UpdateAccountInput {
   currencyId?: string;
   creditLimit?: number;
   billingDay?: number;
+  paymentDueDay?: number;
 }
 
 export interface Currency {
@@ -35,6 +38,24 @@ export interface Currency {
   decimalPlaces: number;
 }
 
+export interface ExchangeRate {
+  id: string;
+  fromCurrencyId: string;
+  toCurrencyId: string;
+  rate: number; // 1 unit of from = rate units of to
+  date: string;
+  createdAt: string;
+}
+
+export interface ConvertedAmount {
+  amount: number; // minor units in source currency
+  fromCurrencyId: string;
+  toCurrencyId: string;
+  convertedAmount: number; // minor units in target currency
+  rate: number;
+  rateDate: string | null;
+}
+
 export type CategoryType = "income" | "expense";
 
 export interface Category {
@@ -72,9 +93,35 @@ export interface Transaction {
   description: string;
   date: string;
   notes: string | null;
+  recurringId: string | null; // set when generated by a recurring rule
+  transferId: string | null; // set when the transaction is a transfer leg
+  fitid: string | null; // bank transaction ID from an OFX/QFX import
   createdAt: string;
 }
 
+export interface Tag {
+  id: string;
+  name: string;
+  color: string; // hex "#rrggbb"
+  createdAt: string;
+}
+
+export interface TransactionWithTags extends Transaction {
+  tags: Tag[];
+}
+
+export interface DescriptionSuggestion {
+  description: string;
+  categoryId: string; // most common for this description
+  accountId: string; // most common for this description
+  amount: number; // most common, minor units of the account's currency
+  type: "income" | "expense";
+  useCount: number;
+  lastUsed: string; // YYYY-MM-DD
+}
+
+export type TagMatch = "any" | "all";
+
 export interface CreateTransactionInput {
   accountId: string;
   categoryId: string;
@@ -102,6 +149,8 @@ export interface TransactionFilters {
   amountMin?: number | null;
   amountMax?: number | null;
   search?: string | null;
+  tagIds?: string[] | null;
+  tagMatch?: TagMatch | null;
   sortBy?: string | null;
   sortDir?: "ASC" | "DESC" | null;
   page?: number | null;
@@ -129,6 +178,60 @@ export interface BalanceSummary {
   baseCurrencyCode: string;
 }
 
+export type RecurringFrequency = "weekly" | "biweekly" | "monthly" | "yearly";
+
+export interface RecurringTransaction {
+  id: string;
+  accountId: string;
+  categoryId: string;
+  amount: number; // integer minor units
+  type: TransactionType;
+  description: string;
+  frequency: RecurringFrequency;
+  nextDate: string;
+  anchorDay: number; // day of month for monthly/yearly rules
+  endDate: string | null;
+  isActive: number;
+  createdAt: string;
+}
+
+export interface CreateRecurringInput {
+  accountId: string;
+  categoryId: string;
+  amount: number;
+  transactionType: TransactionType;
+  description: string;
+  frequency: RecurringFrequency;
+  startDate: string;
+  endDate?: string | null;
+}
+
+export interface UpdateRecurringInput {
+  accountId?: string;
+  categoryId?: string;
+  amount?: number;
+  description?: string;
+  frequency?: RecurringFrequency;
+  nextDate?: string;
+  endDate?: string; // empty string clears the end date
+  isActive?: boolean;
+}
+
+export interface RecurringGenerated {
+  recurringId: string;
+  description: string;
+  accountId: string;
+  amount: number;
+  transactionType: TransactionType;
+  dates: string[];
+}
+
+export interface RecurringRunSummary {
+  runDate: string;
+  generatedCount: number;
+  rules: RecurringGenerated[];
+}
+
 export interface Debt {
   id: string;
   accountId: string;
@@ -138,13 +241,40 @@ export interface Debt {
   paidInstallments: number;
   monthlyPayment: number;
   interestRate: number;
+  rateType: RateType;
   startDate: string;
   isActive: number;
+  status: DebtStatus;
   notes: string | null;
   createdAt: string;
 }
 
-export type InstallmentStatus = "pending" | "paid";
+// Paid-off and refinanced debts have isActive = 0
+export type DebtStatus = "active" | "paid_off" | "defaulted" | "refinanced";
+
+export type DebtStatusReason =
+  | "created"
+  | "last_installment_paid"
+  | "early_payoff"
+  | "payment_undone"
+  | "manual";
+
+export interface DebtStatusChange {
+  id: string;
+  debtId: string;
+  // null for the entry recording the debt's creation
+  fromStatus: DebtStatus | null;
+  toStatus: DebtStatus;
+  reason: DebtStatusReason;
+  note: string | null;
+  changedAt: string;
+}
+
+// How a debt's interest rate is quoted; "cae" is an effective annual rate
+export type RateType = "monthly" | "annual" | "cae";
+
+// partial: paid short and still payable; paid_late: completed after the due date
+export type InstallmentStatus = "pending" | "paid" | "paid_late" | "partial";
 export type InstallmentDisplayStatus = "pagado" | "pendiente" | "vencido";
 
 export interface Installment {
@@ -153,7 +283,12 @@ export interface Installment {
   installmentNumber: number;
   dueDate: string;
   amount: number;
+  principal: number;
+  interest: number;
+  // Principal still owed once this installment is paid
+  balance: number;
   status: InstallmentStatus;
+  paidAmount: number | null; // paid so far, any excess being a late fee; null while pending
   actualPaymentDate: string | null;
   transactionId: string | null;
   createdAt: string;
@@ -165,29 +300,59 @@ export interface DebtWithInstallments {
   accountName: string;
   nextDueDate: string | null;
   remainingAmount: number;
+  interestPaid: number;
+  interestRemaining: number;
 }
 
 export interface CreateDebtInput {
-  accountId: string;
+  // Defaults to the account set in settings
+  accountId?: string;
   description: string;
   originalAmount: number;
   totalInstallments: number;
-  monthlyPayment: number;
+  // Computed from the rate and term when omitted
+  monthlyPayment?: number;
   interestRate: number;
+  // Defaults to "monthly"
+  rateType?: RateType;
   startDate: string;
   notes?: string;
 }
 
+// shorter_term keeps the installment amount and drops installments from the end;
+// lower_payment keeps the remaining installments and lowers their amount
+export type PrepaymentMode = "shorter_term" | "lower_payment";
+
+// Every field is optional
+export interface PayInstallmentInput {
+  // Defaults to the debt-payment category from settings
+  categoryId?: string;
+  date?: string; // YYYY-MM-DD, defaults to today; cannot be in the future
+  // Defaults to what is still owed; less leaves the rest owed, more records a late fee
+  amount?: number;
+  // Account paying, in the debt's currency; defaults to the debt's own account
+  sourceAccountId?: string;
+}
+
+export interface PrepayDebtInput {
+  debtId: string;
+  amount: number;
+  mode: PrepaymentMode;
+  // Defaults to the debt-payment category from settings
+  categoryId?: string;
+}
+
 export interface UpdateDebtInput {
   description?: string;
   interestRate?: number;
-  isActive?: boolean;
   notes?: string;
 }
 
 export interface DebtFilter {
   accountId?: string | null;
   isActive?: boolean | null;
+  // All states when null or empty
+  statuses?: DebtStatus[] | null;
   search?: string | null;
 }
 
@@ -212,6 +377,168 @@ export interface MonthlyProjection {
   total: number;
 }
 
+export interface Transfer {
+  id: string;
+  fromTransactionId: string;
+  toTransactionId: string;
+  fromAccountId: string;
+  toAccountId: string;
+  fromAmount: number; // minor units in source account currency
+  toAmount: number; // minor units in destination account currency
+  rate: number; // destination units per source unit (1 for same currency)
+  description: string;
+  date: string;
+  notes: string | null;
+  createdAt: string;
+}
+
+export interface CreateTransferInput {
+  fromAccountId: string;
+  toAccountId: string;
+  amount: number;
+  rate?: number | null; // required when currencies differ
+  description?: string | null;
+  date: string;
+  notes?: string | null;
+}
+
+export interface UpdateTransferInput {
+  fromAccountId?: string;
+  toAccountId?: string;
+  amount?: number;
+  rate?: number;
+  description?: string;
+  date?: string;
+  notes?: string; // empty string clears notes
+}
+
+export type CsvSignConvention =
+  | "negative_is_expense"
+  | "positive_is_expense"
+  | "debit_credit_columns";
+
+export interface CsvImportProfile {
+  id: string;
+  name: string;
+  delimiter: string;
+  skipRows: number; // header rows skipped before the first data row
+  dateColumn: number; // zero-based column indexes
+  dateFormat: string; // chrono format, e.g. "%d/%m/%Y"
+  descriptionColumn: number;
+  signConvention: CsvSignConvention;
+  amountColumn: number | null;
+  debitColumn: number | null;
+  creditColumn: number | null;
+  decimalSeparator: "," | ".";
+  defaultExpenseCategoryId: string | null;
+  defaultIncomeCategoryId: string | null;
+  createdAt: string;
+}
+
+export type CsvImportProfileInput = Omit<CsvImportProfile, "id" | "createdAt">;
+
+export interface CsvPreviewRow {
+  lineNumber: number;
+  date: string | null;
+  amount: number | null; // absolute, minor units of the account currency
+  transactionType: string | null; // "income" | "expense"
+  description: string;
+  categoryId: string | null;
+  isDuplicate: boolean;
+  error: string | null;
+}
+
+export interface CsvImportPreview {
+  accountId: string;
+  profileId: string;
+  rows: CsvPreviewRow[];
+  validCount: number;
+  duplicateCount: number;
+  errorCount: number;
+}
+
+export interface CsvImportRow {
+  date: string;
+  amount: number;
+  transactionType: string;
+  description: string;
+  categoryId: string;
+}
+
+export interface CsvImportResult {
+  accountId: string;
+  insertedCount: number;
+  balance: number;
+}
+
+export interface OfxPreviewRow {
+  fitid: string;
+  date: string;
+  amount: number | null; // absolute, minor units
+  transactionType: TransactionType | null;
+  description: string;
+  isDuplicate: boolean; // FITID already imported into the account
+  error: string | null;
+}
+
+export interface OfxImportPreview {
+  accountId: string;
+  currency: string | null;
+  accountNumber: string | null;
+  rows: OfxPreviewRow[];
+  newCount: number;
+  duplicateCount: number;
+  errorCount: number;
+}
+
+export interface OfxImportResult {
+  accountId: string;
+  insertedCount: number;
+  skippedCount: number;
+  balance: number;
+}
+
+export interface Budget {
+  id: string;
+  categoryId: string;
+  amount: number; // monthly limit, base currency minor units
+  rollover: number; // 1 = unspent amounts carry over to the next month
+  startMonth: string; // YYYY-MM
+  createdAt: string;
+}
+
+export interface CreateBudgetInput {
+  categoryId: string;
+  amount: number;
+  rollover?: boolean;
+  startMonth?: string; // YYYY-MM, defaults to the current month
+}
+
+export interface UpdateBudgetInput {
+  amount?: number;
+  rollover?: boolean;
+  startMonth?: string;
+}
+
+export interface BudgetStatus {
+  budgetId: string;
+  categoryId: string;
+  categoryName: string;
+  amount: number;
+  rolloverAmount: number; // carried over from previous months
+  available: number; // amount + rolloverAmount
+  spent: number;
+  remaining: number; // negative when overspent
+  percentage: number;
+  isOverspent: boolean;
+}
+
+export interface BudgetMonthStatus {
+  month: string; // YYYY-MM
+  baseCurrencyCode: string;
+  budgets: BudgetStatus[];
+}
+
 export interface DashboardData {
   balanceSummary: BalanceSummary;
   monthlyIncomeExpense: MonthlyIncomeExpense;
@@ -219,11 +546,36 @@ export interface DashboardData {
   recentTransactions: RecentTransaction[];
 }
 
+export type DashboardPeriodType =
+  | "this_month"
+  | "last_month"
+  | "last_n_months"
+  | "year_to_date"
+  | "custom";
+
+export interface DashboardPeriod {
+  periodType: DashboardPeriodType;
+  months?: number; // required for "last_n_months"
+  dateFrom?: string; // YYYY-MM-DD, required for "custom"
+  dateTo?: string; // YYYY-MM-DD inclusive, required for "custom"
+}
+
+export interface PeriodInfo {
+  periodType: DashboardPeriodType;
+  dateFrom: string; // YYYY-MM-DD
+  dateTo: string; // YYYY-MM-DD inclusive
+  label: string; // e.g., "Marzo 2025" or "Enero 2025 - Marzo 2025"
+}
+
 export interface MonthlyIncomeExpense {
   income: number; // minor units in base currency
   expense: number; // minor units in base currency
-  monthName: string; // Spanish month name (e.g., "Marzo")
-  year: number;
+  previousIncome: number; // previous equivalent period
+  previousExpense: number;
+  incomeChange: number | null; // percent vs previous period, null when previous is 0
+  expenseChange: number | null;
+  period: PeriodInfo;
+  previousPeriod: PeriodInfo;
 }
 
 export interface CategorySpending {
@@ -231,6 +583,8 @@ export interface CategorySpending {
   categoryName: string;
   amount: number; // minor units in base currency
   percentage: number; // 0-100
+  previousAmount: number; // minor units in base currency, previous equivalent period
+  change: number | null; // percent vs previous period, null when previous is 0
 }
 
 export interface RecentTransaction {
@@ -243,3 +597,204 @@ export interface RecentTransaction {
   date: string;
   currencyCode: string; // for formatting
 }
+
+export interface MonthlyComparisonFilter {
+  accountId?: string;
+  categoryId?: string; // includes subcategories
+}
+
+export interface MonthlyComparisonPoint {
+  month: string; // YYYY-MM
+  label: string; // e.g., "Marzo 2025"
+  income: number; // minor units in base currency
+  expense: number; // minor units in base currency
+  net: number; // income - expense
+}
+
+export interface MonthlyComparison {
+  baseCurrencyCode: string;
+  months: MonthlyComparisonPoint[]; // oldest first
+}
+
+export type BalanceGranularity = "daily" | "weekly" | "monthly";
+
+export interface BalancePoint {
+  date: string; // YYYY-MM-DD, end of the sampled day, week or month
+  balance: number; // minor units
+}
+
+export interface AccountBalanceHistory {
+  accountId: string;
+  accountName: string;
+  currencyCode: string;
+  points: BalancePoint[]; // in the account's currency
+}
+
+export interface BalanceHistory {
+  granularity: BalanceGranularity;
+  baseCurrencyCode: string;
+  accounts: AccountBalanceHistory[];
+  consolidated: BalancePoint[]; // in base currency
+}
+
+export type ImportMode = "merge" | "replace";
+
+export interface TableCounts {
+  currencies: number;
+  accounts: number;
+  categories: number;
+  transactions: number;
+  tags: number;
+  transactionTags: number;
+  recurringTransactions: number;
+  transfers: number;
+  statementPayments: number;
+  csvImportProfiles: number;
+  budgets: number;
+  debts: number;
+  installments: number;
+  installmentPayments: number;
+  debtStatusHistory: number;
+  debtPrepayments: number;
+  exchangeRates: number;
+  settings: number;
+}
+
+export interface ExportSummary {
+  path: string;
+  schemaVersion: string; // latest applied migration name
+  exportedAt: string;
+  counts: TableCounts;
+}
+
+export interface ImportSummary {
+  mode: ImportMode;
+  schemaVersion: string;
+  inserted: TableCounts;
+  skipped: TableCounts; // rows whose ID already existed
+}
+
+// Language of backend messages (errors, month names), persisted in settings
+export type Locale = "es" | "en";
+
+export type SnapshotReason = "startup" | "pre_migration" | "pre_restore";
+
+export interface BackupSnapshot {
+  fileName: string;
+  createdAt: string; // local time, YYYY-MM-DD HH:MM:SS
+  reason: SnapshotReason;
+  sizeBytes: number;
+}
+
+export interface BalanceDrift {
+  accountId: string;
+  accountName: string;
+  storedBalance: number; // accounts.balance
+  computedBalance: number; // sum of the account's transactions
+}
+
+export interface PaidInstallmentsMismatch {
+  debtId: string;
+  description: string;
+  storedCount: number;
+  actualCount: number;
+}
+
+// Installment linked to a deleted transaction
+export interface DanglingInstallment {
+  installmentId: string;
+  debtId: string;
+  installmentNumber: number;
+  transactionId: string;
+}
+
+// transaction_tags row whose transaction or tag is gone
+export interface OrphanedTagLink {
+  transactionId: string;
+  tagId: string;
+}
+
+// Installment billed on a credit-card statement
+export interface StatementInstallment {
+  installmentId: string;
+  debtId: string;
+  debtDescription: string;
+  installmentNumber: number;
+  totalInstallments: number;
+  amount: number;
+  dueDate: string;
+  status: InstallmentStatus;
+  paidElsewhere: number; // paid from another account, so not billed on the card
+}
+
+export interface Statement {
+  accountId: string;
+  periodStart: string; // YYYY-MM-DD, first day of the cycle
+  closingDate: string; // YYYY-MM-DD, last day of the cycle (inclusive)
+  dueDate: string;
+  isClosed: boolean;
+  charges: Transaction[]; // purchases and refunds; no transfers or installment payments
+  installments: StatementInstallment[];
+  chargesTotal: number;
+  installmentsTotal: number; // less what was paid from other accounts
+  total: number; // chargesTotal + installmentsTotal
+  paidAmount: number;
+  outstanding: number;
+}
+
+export interface SettleStatementInput {
+  accountId: string;
+  closingDate: string;
+  fromAccountId: string; // same currency as the card
+  date?: string; // defaults to today
+  description?: string;
+}
+
+export interface IntegrityReport {
+  balanceDrift: BalanceDrift[];
+  paidInstallmentsMismatches: PaidInstallmentsMismatch[];
+  danglingInstallments: DanglingInstallment[];
+  orphanedTagLinks: OrphanedTagLink[];
+  repaired: boolean; // false for verify, true once repair has fixed everything listed
+}
+
+export type Weekday =
+  | "monday"
+  | "tuesday"
+  | "wednesday"
+  | "thursday"
+  | "friday"
+  | "saturday"
+  | "sunday";
+
+export interface AppSettings {
+  baseCurrencyId: string;
+  locale: Locale;
+  firstDayOfWeek: Weekday;
+  defaultAccountId: string | null;
+  defaultDebtCategoryId: string | null;
+}
+
+// Omitted fields are left unchanged; an empty string resets a field to its default
+export interface UpdateSettingsInput {
+  baseCurrencyId?: string;
+  locale?: Locale | "";
+  firstDayOfWeek?: Weekday | "";
+  defaultAccountId?: string;
+  defaultDebtCategoryId?: string;
+}
+
+// Error returned by migrated backend commands
+export type AppErrorCode =
+  | "VALIDATION"
+  | "NOT_FOUND"
+  | "CONFLICT"
+  | "FOREIGN_KEY"
+  | "DATABASE_BUSY"
+  | "DATABASE";
+
+export interface AppError {
+  code: AppErrorCode;
+  field: string | null; // camelCase input name, e.g. "amount"
+  message: string;
+}